@@ -1,5 +1,26 @@
+pub mod binary_tree;
+pub mod catenable_list;
+pub mod deque;
+pub(crate) mod draw;
+pub mod finger_tree;
+pub mod hash_map;
+pub mod hash_set;
+pub mod int_map;
+pub mod int_set;
 mod key_value;
+pub mod lazy;
+pub mod leftist_heap;
 pub mod link;
 pub mod list;
+pub mod list_zipper;
+pub mod ordered_seq;
+pub mod priority_queue;
+pub mod queue;
+pub mod random_access_list;
+pub mod selector;
+pub mod seq;
+pub mod stream;
+pub mod tree;
+pub mod vector;
 
 pub use key_value::KeyValue;
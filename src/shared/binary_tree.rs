@@ -0,0 +1,531 @@
+use alloc::{collections::VecDeque, string::String, vec, vec::Vec};
+use core::{
+    cmp::max,
+    fmt::{self, Debug},
+};
+
+use super::draw::{self, Draw};
+use super::link::BinaryLink;
+
+/// A persistent binary tree.
+///
+/// Every operation that walks the tree keeps an explicit stack or queue,
+/// so degenerate trees of any depth are handled. On top of the plain
+/// tree, `inserted` and `contains` provide an unbalanced binary search
+/// tree; use `RBTree` when the input may be sorted.
+pub struct BinaryTree<B> {
+    root: Option<B>,
+}
+
+impl<B> Default for BinaryTree<B> {
+    fn default() -> Self {
+        BinaryTree { root: None }
+    }
+}
+
+impl<B: BinaryLink> Clone for BinaryTree<B> {
+    fn clone(&self) -> Self {
+        BinaryTree {
+            root: self.root.as_ref().map(B::clone),
+        }
+    }
+}
+
+impl<B: BinaryLink> BinaryTree<B> {
+    /// Creates an empty `BinaryTree`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::binary_tree::BinaryTree;
+    /// let tree: BinaryTree<u32> = BinaryTree::new();
+    /// ```
+    pub fn new() -> Self {
+        BinaryTree::default()
+    }
+
+    /// Creates an empty `BinaryTree`.
+    pub fn empty() -> Self {
+        BinaryTree::default()
+    }
+
+    /// Creates a tree with `value` at the root and the given subtrees.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::binary_tree::BinaryTree;
+    ///
+    /// let tree = BinaryTree::node(2, BinaryTree::leaf(1), BinaryTree::leaf(3));
+    ///
+    /// assert!(tree.inorder().copied().eq([1, 2, 3]));
+    /// ```
+    pub fn node(value: B::ValueType, left: Self, right: Self) -> Self {
+        BinaryTree {
+            root: Some(B::node(value, left.root, right.root)),
+        }
+    }
+
+    /// Creates a tree with `value` as its only element.
+    pub fn leaf(value: B::ValueType) -> Self {
+        BinaryTree::node(value, BinaryTree::new(), BinaryTree::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn root(&self) -> Option<&B::ValueType> {
+        self.root.as_ref().map(B::get_element)
+    }
+
+    /// Returns the left subtree.
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty tree.
+    pub fn left(&self) -> Self {
+        match &self.root {
+            None => panic!("An empty tree has no subtrees!"),
+            Some(node) => BinaryTree {
+                root: node.left_ref().map(B::clone),
+            },
+        }
+    }
+
+    /// Returns the right subtree.
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty tree.
+    pub fn right(&self) -> Self {
+        match &self.root {
+            None => panic!("An empty tree has no subtrees!"),
+            Some(node) => BinaryTree {
+                root: node.right_ref().map(B::clone),
+            },
+        }
+    }
+
+    /// Returns `true` if both trees share the same root node, or are
+    /// both empty.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            _ => false,
+        }
+    }
+
+    /// Iterates over the elements, each node before its subtrees.
+    pub fn preorder(&self) -> Preorder<'_, B> {
+        Preorder {
+            stack: self.root.iter().collect(),
+        }
+    }
+
+    /// Iterates over the elements, each node between its left and its
+    /// right subtree. For a search tree, this is ascending order.
+    pub fn inorder(&self) -> Inorder<'_, B> {
+        Inorder {
+            stack: Vec::new(),
+            next: self.root.as_ref(),
+        }
+    }
+
+    /// Iterates over the elements, each node after its subtrees.
+    pub fn postorder(&self) -> Postorder<'_, B> {
+        Postorder {
+            stack: self.root.iter().map(|node| (node, false)).collect(),
+        }
+    }
+
+    /// Iterates over the elements one level at a time, from left to
+    /// right.
+    pub fn level_order(&self) -> LevelOrder<'_, B> {
+        LevelOrder {
+            queue: self.root.iter().collect(),
+        }
+    }
+
+    /// Folds the tree bottom up: `f` gets every element together with
+    /// the results for its left and right subtree, `None` for an empty
+    /// one. Returns `None` for an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::binary_tree::BinaryTree;
+    ///
+    /// let tree = BinaryTree::node(2, BinaryTree::leaf(1), BinaryTree::leaf(3));
+    /// let sum = tree.fold(|x, l, r| x + l.unwrap_or(0) + r.unwrap_or(0));
+    ///
+    /// assert_eq!(sum, Some(6));
+    /// ```
+    pub fn fold<R, F>(&self, mut f: F) -> Option<R>
+    where
+        F: FnMut(&B::ValueType, Option<R>, Option<R>) -> R,
+    {
+        enum Task<'a, B> {
+            Visit(Option<&'a B>),
+            Combine(&'a B),
+        }
+
+        let mut stack = vec![Task::Visit(self.root.as_ref())];
+        let mut results = Vec::new();
+        while let Some(task) = stack.pop() {
+            match task {
+                Task::Visit(None) => results.push(None),
+                Task::Visit(Some(node)) => {
+                    stack.push(Task::Combine(node));
+                    stack.push(Task::Visit(node.right_ref()));
+                    stack.push(Task::Visit(node.left_ref()));
+                }
+                Task::Combine(node) => {
+                    let right = results.pop().expect("right result");
+                    let left = results.pop().expect("left result");
+                    results.push(Some(f(node.get_element(), left, right)));
+                }
+            }
+        }
+        results.pop().expect("root result")
+    }
+
+    /// Returns the number of levels in the tree, 0 for an empty tree.
+    ///
+    /// Complexity: O(n)
+    pub fn height(&self) -> usize {
+        self.fold(|_, left, right| 1 + max(left.unwrap_or(0), right.unwrap_or(0)))
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of elements in the tree.
+    ///
+    /// Complexity: O(n)
+    pub fn size(&self) -> usize {
+        self.preorder().count()
+    }
+
+    /// Returns a tree of the same shape, on any kind of link, with `f`
+    /// applied to every element in postorder.
+    pub(crate) fn mapped<M, F>(&self, mut f: F) -> BinaryTree<M>
+    where
+        M: BinaryLink,
+        F: FnMut(&B::ValueType) -> M::ValueType,
+    {
+        self.fold(|x, left, right| {
+            BinaryTree::node(f(x), left.unwrap_or_default(), right.unwrap_or_default())
+        })
+        .unwrap_or_default()
+    }
+
+    /// Returns `true` if the tree contains an element equal to `x`,
+    /// assuming it is a search tree built with `inserted`.
+    ///
+    /// Complexity: O(height)
+    pub fn contains<U>(&self, x: &U) -> bool
+    where
+        B::ValueType: PartialOrd<U>,
+    {
+        let mut next = self.root.as_ref();
+        while let Some(node) = next {
+            let element = node.get_element();
+            if element > x {
+                next = node.left_ref();
+            } else if element < x {
+                next = node.right_ref();
+            } else {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<B> BinaryTree<B>
+where
+    B: BinaryLink,
+    B::ValueType: Clone,
+{
+    /// Returns the tree with left and right swapped at every node.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::binary_tree::BinaryTree;
+    ///
+    /// let tree = BinaryTree::node(2, BinaryTree::leaf(1), BinaryTree::leaf(3));
+    ///
+    /// assert!(tree.mirror().inorder().copied().eq([3, 2, 1]));
+    /// ```
+    pub fn mirror(&self) -> Self {
+        self.fold(|x, left, right| {
+            BinaryTree::node(
+                x.clone(),
+                right.unwrap_or_default(),
+                left.unwrap_or_default(),
+            )
+        })
+        .unwrap_or_default()
+    }
+
+    /// Returns the search tree with `x` inserted as a new leaf, or `self`
+    /// if it already contains an element equal to `x`.
+    ///
+    /// The tree is not rebalanced. Only the path to the new leaf is
+    /// copied, the rest is shared with `self`.
+    ///
+    /// Complexity: O(height)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::binary_tree::BinaryTree;
+    ///
+    /// let t1 = BinaryTree::new().inserted(2).inserted(1).inserted(3);
+    /// let t2 = t1.inserted(4);
+    ///
+    /// assert!(t2.inorder().copied().eq(1..5));
+    /// assert!(t2.contains(&4));
+    /// assert!(!t1.contains(&4));
+    /// ```
+    pub fn inserted(&self, x: B::ValueType) -> Self
+    where
+        B::ValueType: PartialOrd,
+    {
+        let mut path = Vec::new();
+        let mut next = self.root.as_ref();
+        while let Some(node) = next {
+            let element = node.get_element();
+            if x < *element {
+                path.push((node, true));
+                next = node.left_ref();
+            } else if x > *element {
+                path.push((node, false));
+                next = node.right_ref();
+            } else {
+                return self.clone();
+            }
+        }
+        let mut tree = B::node(x, None, None);
+        for (node, went_left) in path.into_iter().rev() {
+            let element = node.get_element().clone();
+            tree = if went_left {
+                B::node(element, Some(tree), node.right_ref().map(B::clone))
+            } else {
+                B::node(element, node.left_ref().map(B::clone), Some(tree))
+            };
+        }
+        BinaryTree { root: Some(tree) }
+    }
+}
+
+impl<B> BinaryTree<B>
+where
+    B: BinaryLink,
+    B::ValueType: Debug,
+{
+    /// Draws the tree as an outline, one element per line, with the
+    /// left subtree above the right one. An empty subtree is shown as
+    /// `·` when its sibling is not empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::binary_tree::BinaryTree;
+    ///
+    /// let tree = BinaryTree::new().inserted(2).inserted(3);
+    ///
+    /// assert_eq!(tree.pretty(), "2\n├── ·\n└── 3");
+    /// ```
+    pub fn pretty(&self) -> String {
+        draw::pretty(self.root.as_ref().map(DrawNode))
+    }
+
+    /// Draws `versions` as one Graphviz digraph, with a `v<i>` label
+    /// pointing at the root of each. Nodes shared between versions are
+    /// drawn once.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::binary_tree::BinaryTree;
+    ///
+    /// let t1 = BinaryTree::new().inserted(2).inserted(1);
+    /// let t2 = t1.inserted(3);
+    /// let dot = BinaryTree::to_dot(&[&t1, &t2]);
+    ///
+    /// // 1 is shared, 2 is copied
+    /// assert_eq!(dot.matches("label=\"1\"").count(), 1);
+    /// assert_eq!(dot.matches("label=\"2\"").count(), 2);
+    /// ```
+    pub fn to_dot(versions: &[&Self]) -> String {
+        draw::dot(
+            versions
+                .iter()
+                .map(|tree| tree.root.as_ref().map(DrawNode))
+                .collect(),
+        )
+    }
+}
+
+struct DrawNode<'a, B>(&'a B);
+
+impl<'a, B> Draw for DrawNode<'a, B>
+where
+    B: BinaryLink,
+    B::ValueType: Debug,
+{
+    type Element = B::ValueType;
+
+    const BINARY: bool = true;
+
+    fn id(&self) -> *const () {
+        self.0.as_ptr()
+    }
+    fn element(&self) -> &Self::Element {
+        self.0.get_element()
+    }
+    fn children(&self) -> Vec<Option<Self>> {
+        match (self.0.left_ref(), self.0.right_ref()) {
+            (None, None) => Vec::new(),
+            (left, right) => vec![left.map(DrawNode), right.map(DrawNode)],
+        }
+    }
+}
+
+/// Unlinks uniquely owned descendants one node at a time, so that
+/// dropping a degenerate tree doesn't overflow the stack.
+pub(crate) fn drop_children<B: BinaryLink>(left: Option<B>, right: Option<B>) {
+    let mut stack: Vec<B> = left.into_iter().chain(right).collect();
+    while let Some(link) = stack.pop() {
+        if let Some((left, right)) = link.try_unwrap_children() {
+            stack.extend(left);
+            stack.extend(right);
+        }
+    }
+}
+
+pub struct Preorder<'a, B> {
+    stack: Vec<&'a B>,
+}
+
+impl<'a, B: BinaryLink> Iterator for Preorder<'a, B> {
+    type Item = &'a B::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.right_ref());
+        self.stack.extend(node.left_ref());
+        Some(node.get_element())
+    }
+}
+
+pub struct Inorder<'a, B> {
+    stack: Vec<&'a B>,
+    next: Option<&'a B>,
+}
+
+impl<'a, B: BinaryLink> Iterator for Inorder<'a, B> {
+    type Item = &'a B::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.next {
+            self.stack.push(node);
+            self.next = node.left_ref();
+        }
+        let node = self.stack.pop()?;
+        self.next = node.right_ref();
+        Some(node.get_element())
+    }
+}
+
+pub struct Postorder<'a, B> {
+    stack: Vec<(&'a B, bool)>,
+}
+
+impl<'a, B: BinaryLink> Iterator for Postorder<'a, B> {
+    type Item = &'a B::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, visited) = self.stack.pop()?;
+            if visited {
+                return Some(node.get_element());
+            }
+            self.stack.push((node, true));
+            self.stack
+                .extend(node.right_ref().map(|right| (right, false)));
+            self.stack.extend(node.left_ref().map(|left| (left, false)));
+        }
+    }
+}
+
+pub struct LevelOrder<'a, B> {
+    queue: VecDeque<&'a B>,
+}
+
+impl<'a, B: BinaryLink> Iterator for LevelOrder<'a, B> {
+    type Item = &'a B::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        self.queue.extend(node.left_ref());
+        self.queue.extend(node.right_ref());
+        Some(node.get_element())
+    }
+}
+
+impl<B> PartialEq for BinaryTree<B>
+where
+    B: BinaryLink,
+    B::ValueType: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let mut stack = vec![(self.root.as_ref(), other.root.as_ref())];
+        while let Some(pair) = stack.pop() {
+            match pair {
+                (None, None) => {}
+                (Some(a), Some(b)) if a.ptr_eq(b) => {}
+                (Some(a), Some(b)) => {
+                    if a.get_element() != b.get_element() {
+                        return false;
+                    }
+                    stack.push((a.left_ref(), b.left_ref()));
+                    stack.push((a.right_ref(), b.right_ref()));
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl<B> Debug for BinaryTree<B>
+where
+    B: BinaryLink,
+    B::ValueType: Debug,
+{
+    /// Prints the nested subtrees like a derived `Debug` would, on one
+    /// line, keeping the pending parts on an explicit stack.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut stack = vec![DebugStep::Tree(self.root.as_ref())];
+        while let Some(step) = stack.pop() {
+            match step {
+                DebugStep::Text(text) => f.write_str(text)?,
+                DebugStep::Tree(None) => f.write_str("BinaryTree")?,
+                DebugStep::Tree(Some(node)) => {
+                    f.write_str("BinaryTree { element: ")?;
+                    node.get_element().fmt(f)?;
+                    stack.push(DebugStep::Text(" }"));
+                    stack.push(DebugStep::Tree(node.right_ref()));
+                    stack.push(DebugStep::Text(", right: "));
+                    stack.push(DebugStep::Tree(node.left_ref()));
+                    stack.push(DebugStep::Text(", left: "));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What is left to print of a `BinaryTree`.
+enum DebugStep<'a, B> {
+    Tree(Option<&'a B>),
+    Text(&'static str),
+}
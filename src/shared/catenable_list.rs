@@ -0,0 +1,305 @@
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+use super::lazy::{Lazy, Reclaim, Suspend};
+use super::link::Link;
+use super::queue::{self, Queue};
+
+pub trait CatenableLink: Sized {
+    type ValueType;
+    /// A memoised suspension of a list, shared by the queues it is in.
+    type Suspended: Lazy<Value = CatenableList<Self>> + Clone;
+    type QueueLink: Link<ValueType = Self::Suspended>;
+
+    fn node(element: Self::ValueType, children: Queue<Self::QueueLink>) -> Self;
+    fn clone(&self) -> Self;
+    fn get_element(&self) -> &Self::ValueType;
+    fn children(&self) -> &Queue<Self::QueueLink>;
+    /// Returns `true` if both links point to the same node.
+    fn ptr_eq(&self, other: &Self) -> bool;
+    /// Returns the children of the node if this is the only link to it.
+    fn try_unwrap_children(self) -> Option<Queue<Self::QueueLink>>;
+}
+
+/// A persistent list with O(1) append.
+///
+/// This is Okasaki's catenable list: a tree whose root holds the front
+/// element and a queue of suspended lists that follow it. `appended`,
+/// `pushed_front` and `pushed_back` are O(1). `popped_front` links the
+/// children of the root together lazily: it forces the first child and
+/// suspends linking the rest. The suspensions are memoised and shared
+/// by every version that contains them, so `popped_front` is O(1)
+/// amortised also when a version is reused.
+pub struct CatenableList<C> {
+    root: Option<C>,
+}
+
+impl<C> Default for CatenableList<C> {
+    fn default() -> Self {
+        CatenableList { root: None }
+    }
+}
+
+impl<C: CatenableLink> Clone for CatenableList<C> {
+    fn clone(&self) -> Self {
+        CatenableList {
+            root: self.root.as_ref().map(C::clone),
+        }
+    }
+}
+
+impl<C: CatenableLink> CatenableList<C> {
+    /// Creates an empty `CatenableList`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::CatenableList;
+    /// let list: CatenableList<u32> = CatenableList::new();
+    /// ```
+    pub fn new() -> Self {
+        CatenableList::default()
+    }
+
+    pub fn from_value(element: C::ValueType) -> Self {
+        CatenableList {
+            root: Some(C::node(element, Queue::new())),
+        }
+    }
+
+    /// Returns `true` if this `CatenableList` is empty.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Provides a reference to the front element, or
+    /// `None` if the list is empty.
+    pub fn front(&self) -> Option<&C::ValueType> {
+        self.root.as_ref().map(C::get_element)
+    }
+
+    /// Returns `true` if both lists share the same root node.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            _ => false,
+        }
+    }
+
+    /// Iterates over the elements, front first.
+    pub fn iter(&self) -> Iter<'_, C> {
+        Iter {
+            next: self.root.as_ref(),
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl<C> CatenableList<C>
+where
+    C: CatenableLink,
+    C::ValueType: Clone,
+    C::Suspended: Suspend<Queue<C::QueueLink>>,
+{
+    /// Returns the elements of `self` followed by the elements of `other`.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::CatenableList;
+    ///
+    /// let a: CatenableList<_> = (0..3).collect();
+    /// let b: CatenableList<_> = (3..5).collect();
+    ///
+    /// assert!(a.appended(&b).iter().copied().eq(0..5));
+    /// ```
+    pub fn appended(&self, other: &Self) -> Self {
+        if other.is_empty() {
+            self.clone()
+        } else if self.is_empty() {
+            other.clone()
+        } else {
+            self.linked(C::Suspended::evaluated(other.clone()))
+        }
+    }
+
+    /// Returns a list with `value` added at the front.
+    ///
+    /// Complexity: O(1)
+    pub fn pushed_front(&self, value: C::ValueType) -> Self {
+        if self.is_empty() {
+            return CatenableList::from_value(value);
+        }
+        CatenableList {
+            root: Some(C::node(
+                value,
+                Queue::new().pushed_back(C::Suspended::evaluated(self.clone())),
+            )),
+        }
+    }
+
+    /// Returns a list with `value` added at the back.
+    ///
+    /// Complexity: O(1)
+    pub fn pushed_back(&self, value: C::ValueType) -> Self {
+        self.appended(&CatenableList::from_value(value))
+    }
+
+    /// Returns the list without its front element.
+    ///
+    /// Complexity: O(1) amortised
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty list.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::CatenableList;
+    ///
+    /// let l1: CatenableList<_> = (0..3).collect();
+    /// let l2 = l1.popped_front();
+    ///
+    /// assert_eq!(l2.front(), Some(&1));
+    /// assert_eq!(l1.front(), Some(&0));
+    /// ```
+    pub fn popped_front(&self) -> Self {
+        let root = match &self.root {
+            None => panic!("You can't pop an empty list!"),
+            Some(root) => root,
+        };
+        if root.children().is_empty() {
+            return CatenableList::new();
+        }
+        linked_all(root.children())
+    }
+
+    /// Adds `other` as the last child of the root of `self`.
+    fn linked(&self, other: C::Suspended) -> Self {
+        let root = self.root.as_ref().expect("only non-empty lists are linked");
+        CatenableList {
+            root: Some(C::node(
+                root.get_element().clone(),
+                root.children().pushed_back(other),
+            )),
+        }
+    }
+}
+
+/// Links the lists in `queue`, which is not empty, into one list: the
+/// first list is forced, and linking the others is suspended.
+fn linked_all<C>(queue: &Queue<C::QueueLink>) -> CatenableList<C>
+where
+    C: CatenableLink,
+    C::ValueType: Clone,
+    C::Suspended: Suspend<Queue<C::QueueLink>>,
+{
+    let first = queue.peek().expect("only non-empty queues are linked");
+    let rest = queue.popped_front();
+    match rest.len() {
+        0 => first.force().clone(),
+        // a single list is linked as it is, rather than behind one more
+        // suspension
+        1 => first.force().linked(rest.peek().unwrap().clone()),
+        _ => {
+            let rest = C::Suspended::suspended(rest, |rest| linked_all(&rest));
+            first.force().linked(rest)
+        }
+    }
+}
+
+/// Unlinks uniquely owned descendants one queue at a time, so that
+/// dropping a long chain of nodes or of suspensions doesn't overflow
+/// the stack.
+pub(crate) fn drop_children<C>(children: Queue<C::QueueLink>)
+where
+    C: CatenableLink,
+    C::Suspended: Reclaim<Queue<C::QueueLink>>,
+{
+    let mut stack = vec![children];
+    while let Some(queue) = stack.pop() {
+        let suspended: Vec<_> = queue.iter().cloned().collect();
+        drop(queue);
+        for mut list in suspended {
+            if let Some(queue) = list.reclaim() {
+                stack.push(queue);
+            }
+            let root = list.get_mut().and_then(|list| list.root.take());
+            if let Some(children) = root.and_then(C::try_unwrap_children) {
+                stack.push(children);
+            }
+        }
+    }
+}
+
+pub struct Iter<'a, C: CatenableLink> {
+    next: Option<&'a C>,
+    stack: Vec<queue::Iter<'a, C::QueueLink>>,
+}
+
+impl<'a, C: CatenableLink> Iterator for Iter<'a, C> {
+    type Item = &'a C::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(node) = self.next.take() {
+                self.stack.push(node.children().iter());
+                return Some(node.get_element());
+            }
+            match self.stack.last_mut()?.next() {
+                Some(list) => self.next = list.force().root.as_ref(),
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+impl<'a, C: CatenableLink> IntoIterator for &'a CatenableList<C> {
+    type Item = &'a C::ValueType;
+    type IntoIter = Iter<'a, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<C> FromIterator<C::ValueType> for CatenableList<C>
+where
+    C: CatenableLink,
+    C::ValueType: Clone,
+    C::Suspended: Suspend<Queue<C::QueueLink>>,
+{
+    fn from_iter<I: IntoIterator<Item = C::ValueType>>(iter: I) -> Self {
+        let mut list = CatenableList::new();
+        for x in iter {
+            list = list.pushed_back(x);
+        }
+        list
+    }
+}
+
+impl<C> PartialEq for CatenableList<C>
+where
+    C: CatenableLink,
+    C::ValueType: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.iter().eq(other.iter())
+    }
+}
+
+impl<C> Debug for CatenableList<C>
+where
+    C: CatenableLink,
+    C::ValueType: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,384 @@
+use alloc::vec::{self, Vec};
+use core::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+use super::lazy::Suspend;
+use super::stream::{self, Stream, StreamLink};
+
+/// How much longer one end may get than the other before the deque
+/// is rebalanced.
+const BALANCE: usize = 3;
+
+/// A persistent double-ended queue.
+///
+/// This is Okasaki's banker's deque. The elements are kept in two
+/// streams, the front in order and the rear reversed. When one stream
+/// grows more than `BALANCE` times longer than the other, the elements
+/// are split evenly between them again, but lazily: the new streams are
+/// suspensions that copy the elements as they are popped, and the
+/// reversal of the moved half runs when the first of them is needed.
+/// The suspensions are memoised and shared by every version built from
+/// the rebalanced one, so the work is done at most once however often a
+/// version is reused, and pushes and pops are O(1) amortised, also under
+/// persistence. A single pop can still take O(n) when it forces a
+/// reversal.
+///
+/// The laziness is what keeps the bound under persistence. Two strict
+/// `List`s would have to rebalance eagerly, and a version that sits
+/// just before a rebalance could be popped again and again, paying O(n)
+/// each time.
+pub struct Deque<L> {
+    front_len: usize,
+    front: Stream<L>,
+    rear_len: usize,
+    rear: Stream<L>,
+}
+
+impl<L> Default for Deque<L> {
+    fn default() -> Self {
+        Deque {
+            front_len: 0,
+            front: Stream::default(),
+            rear_len: 0,
+            rear: Stream::default(),
+        }
+    }
+}
+
+impl<L: StreamLink> Clone for Deque<L> {
+    fn clone(&self) -> Self {
+        Deque {
+            front_len: self.front_len,
+            front: self.front.clone(),
+            rear_len: self.rear_len,
+            rear: self.rear.clone(),
+        }
+    }
+}
+
+impl<L: StreamLink> Deque<L> {
+    /// Creates an empty `Deque`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Deque;
+    /// let deque: Deque<u32> = Deque::new();
+    /// ```
+    pub fn new() -> Self {
+        Deque::default()
+    }
+
+    /// Returns `true` if this `Deque` is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of elements in the deque.
+    ///
+    /// Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.front_len + self.rear_len
+    }
+
+    /// Provides a reference to the front element, or
+    /// `None` if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use persi_ds::unsync::Deque;
+    ///
+    /// let d = Deque::new().pushed_back(1).pushed_back(2);
+    /// assert_eq!(d.front(), Some(&1));
+    /// ```
+    pub fn front(&self) -> Option<&L::Element> {
+        // the balance invariant leaves at most one element in the rear
+        // when the front is empty
+        self.front.front().or_else(|| self.rear.front())
+    }
+
+    /// Provides a reference to the back element, or
+    /// `None` if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use persi_ds::unsync::Deque;
+    ///
+    /// let d = Deque::new().pushed_front(1).pushed_front(2);
+    /// assert_eq!(d.back(), Some(&1));
+    /// ```
+    pub fn back(&self) -> Option<&L::Element> {
+        self.rear.front().or_else(|| self.front.front())
+    }
+
+    /// Iterates over the elements from front to back.
+    ///
+    /// The front stream is walked lazily. The rear has to be reversed, so
+    /// the elements left in it are collected the first time `next` gets
+    /// to them, and likewise for the front when iterating from the back.
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter {
+            front: self.front.iter(),
+            front_len: self.front_len,
+            rear: self.rear.iter(),
+            rear_len: self.rear_len,
+            rest: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<L> Deque<L>
+where
+    L: StreamLink,
+    L::Element: Clone,
+    L::Tail: Suspend<(L, usize)> + Suspend<(Stream<L>, Stream<L>, usize)>,
+{
+    /// Returns a deque with `value` added at the front.
+    ///
+    /// Complexity: O(1) amortised
+    pub fn pushed_front(&self, value: L::Element) -> Self {
+        Deque {
+            front_len: self.front_len + 1,
+            front: Stream::cons(value, &self.front),
+            rear_len: self.rear_len,
+            rear: self.rear.clone(),
+        }
+        .check()
+    }
+
+    /// Returns a deque with `value` added at the back.
+    ///
+    /// Complexity: O(1) amortised
+    pub fn pushed_back(&self, value: L::Element) -> Self {
+        Deque {
+            front_len: self.front_len,
+            front: self.front.clone(),
+            rear_len: self.rear_len + 1,
+            rear: Stream::cons(value, &self.rear),
+        }
+        .check()
+    }
+
+    /// Returns the deque without its front element.
+    ///
+    /// Complexity: O(1) amortised
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use persi_ds::unsync::Deque;
+    ///
+    /// let d1 = Deque::new().pushed_back(1).pushed_back(2);
+    /// let d2 = d1.popped_front();
+    ///
+    /// assert_eq!(d2.front(), Some(&2));
+    /// assert_eq!(d1.front(), Some(&1));
+    /// ```
+    pub fn popped_front(&self) -> Self {
+        if self.is_empty() {
+            panic!("You can't pop an empty deque!");
+        }
+        if self.front.is_empty() {
+            return Deque::new();
+        }
+        Deque {
+            front_len: self.front_len - 1,
+            front: self.front.popped_front(),
+            rear_len: self.rear_len,
+            rear: self.rear.clone(),
+        }
+        .check()
+    }
+
+    /// Returns the deque without its back element.
+    ///
+    /// Complexity: O(1) amortised
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use persi_ds::unsync::Deque;
+    ///
+    /// let d1 = Deque::new().pushed_back(1).pushed_back(2);
+    /// let d2 = d1.popped_back();
+    ///
+    /// assert_eq!(d2.back(), Some(&1));
+    /// assert_eq!(d1.back(), Some(&2));
+    /// ```
+    pub fn popped_back(&self) -> Self {
+        if self.is_empty() {
+            panic!("You can't pop an empty deque!");
+        }
+        if self.rear.is_empty() {
+            return Deque::new();
+        }
+        Deque {
+            front_len: self.front_len,
+            front: self.front.clone(),
+            rear_len: self.rear_len - 1,
+            rear: self.rear.popped_front(),
+        }
+        .check()
+    }
+
+    fn check(self) -> Self {
+        let len = self.len();
+        if self.front_len > BALANCE * self.rear_len + 1 {
+            let keep = len / 2;
+            Deque {
+                front_len: keep,
+                front: self.front.take(keep),
+                rear_len: len - keep,
+                rear: appended_reversed(&self.rear, &self.front, keep),
+            }
+        } else if self.rear_len > BALANCE * self.front_len + 1 {
+            let keep = len / 2;
+            Deque {
+                front_len: len - keep,
+                front: appended_reversed(&self.front, &self.rear, keep),
+                rear_len: keep,
+                rear: self.rear.take(keep),
+            }
+        } else {
+            self
+        }
+    }
+}
+
+/// Returns `short` followed by the elements of `long` after the first
+/// `skip`, in reverse.
+///
+/// The elements of `short` are copied one at a time as the tails are
+/// forced. The reversal is done all at once, by the tail that follows
+/// the last of them, or right away if `short` is empty, which the
+/// balance invariant allows only for a handful of elements.
+fn appended_reversed<L>(short: &Stream<L>, long: &Stream<L>, skip: usize) -> Stream<L>
+where
+    L: StreamLink,
+    L::Element: Clone,
+    L::Tail: Suspend<(Stream<L>, Stream<L>, usize)>,
+{
+    match short.front() {
+        Some(x) => Stream::suspended(
+            x.clone(),
+            (short.clone(), long.clone(), skip),
+            |(short, long, skip)| appended_reversed(&short.popped_front(), &long, skip),
+        ),
+        None => {
+            let mut reversed = Stream::new();
+            for x in long.iter().skip(skip) {
+                reversed = Stream::cons(x.clone(), &reversed);
+            }
+            reversed
+        }
+    }
+}
+
+pub struct Iter<'a, L: StreamLink> {
+    front: stream::Iter<'a, L>,
+    front_len: usize,
+    rear: stream::Iter<'a, L>,
+    rear_len: usize,
+    /// The elements left once one of the streams has been used up, in
+    /// order.
+    rest: vec::IntoIter<&'a L::Element>,
+}
+
+impl<'a, L: StreamLink> Iterator for Iter<'a, L> {
+    type Item = &'a L::Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front_len > 0 {
+            self.front_len -= 1;
+            return self.front.next();
+        }
+        if self.rear_len > 0 {
+            let mut rest: Vec<_> = self.rear.by_ref().take(self.rear_len).collect();
+            rest.reverse();
+            self.rest = rest.into_iter();
+            self.rear_len = 0;
+        }
+        self.rest.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front_len + self.rear_len + self.rest.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, L: StreamLink> DoubleEndedIterator for Iter<'a, L> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.rear_len > 0 {
+            self.rear_len -= 1;
+            return self.rear.next();
+        }
+        if self.front_len > 0 {
+            self.rest = self
+                .front
+                .by_ref()
+                .take(self.front_len)
+                .collect::<Vec<_>>()
+                .into_iter();
+            self.front_len = 0;
+        }
+        self.rest.next_back()
+    }
+}
+
+impl<'a, L: StreamLink> ExactSizeIterator for Iter<'a, L> {}
+
+impl<'a, L: StreamLink> IntoIterator for &'a Deque<L> {
+    type Item = &'a L::Element;
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<L> FromIterator<L::Element> for Deque<L>
+where
+    L: StreamLink,
+    L::Element: Clone,
+    L::Tail: Suspend<(L, usize)> + Suspend<(Stream<L>, Stream<L>, usize)>,
+{
+    fn from_iter<I: IntoIterator<Item = L::Element>>(iter: I) -> Self {
+        let mut deque = Deque::new();
+        for x in iter {
+            deque = deque.pushed_back(x);
+        }
+        deque
+    }
+}
+
+impl<L> PartialEq for Deque<L>
+where
+    L: StreamLink,
+    L::Element: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<L> Debug for Deque<L>
+where
+    L: StreamLink,
+    L::Element: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,161 @@
+//! Text renderings of tree nodes: an indented box-drawing outline and
+//! Graphviz DOT.
+
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use core::fmt::{self, Debug, Write};
+
+/// Marks an empty subtree that is drawn because its sibling is not.
+const EMPTY: &str = "·";
+
+/// A node as seen by the renderers. Implemented for references to the
+/// nodes of each tree type.
+pub(crate) trait Draw: Sized {
+    type Element: Debug;
+
+    /// Children have a left and a right side, which the DOT output shows
+    /// by attaching the edges to the lower corners of the parent.
+    const BINARY: bool;
+
+    fn id(&self) -> *const ();
+    fn element(&self) -> &Self::Element;
+    /// `Some(true)` for red and `Some(false)` for black nodes.
+    fn red(&self) -> Option<bool> {
+        None
+    }
+    /// The children in order, with `None` for an empty subtree that
+    /// still takes up a slot.
+    fn children(&self) -> Vec<Option<Self>>;
+}
+
+/// Renders the tree under `root` as an outline, one node per line.
+pub(crate) fn pretty<D: Draw>(root: Option<D>) -> String {
+    let mut out = String::new();
+    write_pretty(&mut out, root).expect("writing to a String cannot fail");
+    out
+}
+
+/// Renders all `versions` as one DOT digraph. Every node is drawn once,
+/// however many of the versions share it.
+pub(crate) fn dot<D: Draw>(versions: Vec<Option<D>>) -> String {
+    let mut out = String::new();
+    write_dot(&mut out, versions).expect("writing to a String cannot fail");
+    out
+}
+
+fn write_pretty<D: Draw>(out: &mut String, root: Option<D>) -> fmt::Result {
+    let mut stack = vec![(root, String::new(), "")];
+    while let Some((node, prefix, connector)) = stack.pop() {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&prefix);
+        out.push_str(connector);
+        let node = match node {
+            None => {
+                out.push_str(EMPTY);
+                continue;
+            }
+            Some(node) => node,
+        };
+        write!(out, "{:?}", node.element())?;
+        match node.red() {
+            Some(true) => out.push_str(" [R]"),
+            Some(false) => out.push_str(" [B]"),
+            None => {}
+        }
+        let child_prefix = prefix
+            + match connector {
+                "├── " => "│   ",
+                "└── " => "    ",
+                _ => "",
+            };
+        let children = node.children();
+        let last = children.len().saturating_sub(1);
+        for (i, child) in children.into_iter().enumerate().rev() {
+            let connector = if i == last {
+                "└── "
+            } else {
+                "├── "
+            };
+            stack.push((child, child_prefix.clone(), connector));
+        }
+    }
+    Ok(())
+}
+
+fn write_dot<D: Draw>(out: &mut String, versions: Vec<Option<D>>) -> fmt::Result {
+    writeln!(out, "digraph {{")?;
+    writeln!(out, "    ordering=out;")?;
+    let mut ids = BTreeMap::new();
+    let mut stack = Vec::new();
+    for (version, root) in versions.into_iter().enumerate() {
+        writeln!(out, "    v{} [shape=plaintext];", version)?;
+        let root = match root {
+            None => continue,
+            Some(root) => root,
+        };
+        let (id, new) = node_id(&mut ids, &root);
+        writeln!(out, "    v{} -> n{} [style=dashed];", version, id)?;
+        if new {
+            stack.push((root, id));
+        }
+        while let Some((node, id)) = stack.pop() {
+            write!(out, "    n{} [label=\"", id)?;
+            write_escaped(out, node.element())?;
+            out.push('"');
+            match node.red() {
+                Some(true) => out.push_str(", style=filled, fillcolor=red, fontcolor=white"),
+                Some(false) => out.push_str(", style=filled, fillcolor=black, fontcolor=white"),
+                None => {}
+            }
+            out.push_str("];\n");
+            for (i, child) in node.children().into_iter().enumerate() {
+                let child = match child {
+                    None => continue,
+                    Some(child) => child,
+                };
+                let (child_id, new) = node_id(&mut ids, &child);
+                write!(out, "    n{} -> n{}", id, child_id)?;
+                if D::BINARY {
+                    out.push_str(if i == 0 {
+                        " [tailport=sw]"
+                    } else {
+                        " [tailport=se]"
+                    });
+                }
+                out.push_str(";\n");
+                if new {
+                    stack.push((child, child_id));
+                }
+            }
+        }
+    }
+    writeln!(out, "}}")
+}
+
+/// Returns the number of `node` in the drawing, and whether it was just
+/// assigned.
+fn node_id<D: Draw>(ids: &mut BTreeMap<*const (), usize>, node: &D) -> (usize, bool) {
+    let next = ids.len();
+    let id = *ids.entry(node.id()).or_insert(next);
+    (id, id == next)
+}
+
+/// Writes the `Debug` form of `element` as the inside of a DOT string.
+fn write_escaped<E: Debug>(out: &mut String, element: &E) -> fmt::Result {
+    struct Escaped<'a>(&'a mut String);
+
+    impl Write for Escaped<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for c in s.chars() {
+                if c == '"' || c == '\\' {
+                    self.0.push('\\');
+                }
+                self.0.push(c);
+            }
+            Ok(())
+        }
+    }
+
+    write!(Escaped(out), "{:?}", element)
+}
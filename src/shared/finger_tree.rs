@@ -0,0 +1,703 @@
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+/// A type with an associative `combine` that has `empty` as identity.
+pub trait Monoid: Clone {
+    fn empty() -> Self;
+    fn combine(&self, other: &Self) -> Self;
+}
+
+/// Both measures side by side, so a tree can be searched by either.
+impl<A: Monoid, B: Monoid> Monoid for (A, B) {
+    fn empty() -> Self {
+        (A::empty(), B::empty())
+    }
+
+    fn combine(&self, other: &Self) -> Self {
+        (self.0.combine(&other.0), self.1.combine(&other.1))
+    }
+}
+
+/// An element of a `FingerTree`. The tree caches the combined measure of
+/// every subtree, which is what `split` and `find` search by.
+pub trait Measured {
+    type Measure: Monoid;
+
+    fn measure(&self) -> Self::Measure;
+}
+
+pub trait FingerLink: Sized {
+    type Element: Measured;
+
+    fn new(node: FingerNode<Self>) -> Self;
+    fn clone(&self) -> Self;
+    fn node(&self) -> &FingerNode<Self>;
+    /// Returns `true` if both links point to the same node.
+    fn ptr_eq(&self, other: &Self) -> bool;
+}
+
+type Measure<L> = <<L as FingerLink>::Element as Measured>::Measure;
+
+/// A node of a `FingerTree`.
+pub struct FingerNode<L: FingerLink>(Kind<L>);
+
+enum Kind<L: FingerLink> {
+    /// Two or three items one level further down the spine than the
+    /// digit holding this node.
+    Branch {
+        measure: Measure<L>,
+        items: Vec<Item<L>>,
+    },
+    /// A tree with one to four items at each end and the items between
+    /// them grouped into branches, one level down.
+    Deep {
+        measure: Measure<L>,
+        prefix: Vec<Item<L>>,
+        middle: FingerTree<L>,
+        suffix: Vec<Item<L>>,
+    },
+}
+
+/// An item of a digit: an element at the top level of the spine, and a
+/// branch at every level below.
+enum Item<L: FingerLink> {
+    Element(L::Element),
+    Branch(L),
+}
+
+impl<L> Clone for Item<L>
+where
+    L: FingerLink,
+    L::Element: Clone,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Item::Element(x) => Item::Element(x.clone()),
+            Item::Branch(link) => Item::Branch(L::clone(link)),
+        }
+    }
+}
+
+impl<L: FingerLink> Item<L> {
+    fn measure(&self) -> Measure<L> {
+        match self {
+            Item::Element(x) => x.measure(),
+            Item::Branch(link) => match &link.node().0 {
+                Kind::Branch { measure, .. } => measure.clone(),
+                Kind::Deep { .. } => unreachable!("items only link to branches"),
+            },
+        }
+    }
+
+    fn children(&self) -> &[Item<L>] {
+        match self {
+            Item::Branch(link) => match &link.node().0 {
+                Kind::Branch { items, .. } => items,
+                Kind::Deep { .. } => unreachable!("items only link to branches"),
+            },
+            Item::Element(_) => unreachable!("elements are only on the top level"),
+        }
+    }
+
+    /// Returns the first or last element under the item.
+    fn outer_element(&self, last: bool) -> &L::Element {
+        let mut item = self;
+        loop {
+            match item {
+                Item::Element(x) => return x,
+                Item::Branch(_) => {
+                    let children = item.children();
+                    item = if last {
+                        &children[children.len() - 1]
+                    } else {
+                        &children[0]
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// A persistent 2-3 finger tree, after Hinze and Paterson.
+///
+/// The elements sit in short digits at both ends of a spine, whose
+/// middle holds a finger tree of 2-3 branches of items, and so on down.
+/// Adding and removing at either end is O(1) amortised as long as old
+/// versions are not reused. The middle tree is strict, not suspended,
+/// so pushing onto a version with full digits, or popping from one with
+/// a single-element digit, rebuilds a path down the spine each time it
+/// is done again: these are O(log n) in the worst case. Joining two
+/// trees is O(log n).
+///
+/// Every branch caches the combined `Measure` of its elements. `split`
+/// and `find` search the tree for the point where a predicate over the
+/// measure of a prefix turns true, in O(log n). What that means depends
+/// on the measure: with element counts it is indexing, with the largest
+/// priority it is finding the maximum. `Seq`, `PriorityQueue` and
+/// `OrderedSeq` are built like this; other measures work the same.
+pub struct FingerTree<L: FingerLink>(Shape<L>);
+
+enum Shape<L: FingerLink> {
+    Empty,
+    Single(Item<L>),
+    Deep(L),
+}
+
+impl<L: FingerLink> Default for FingerTree<L> {
+    fn default() -> Self {
+        FingerTree(Shape::Empty)
+    }
+}
+
+impl<L> Clone for FingerTree<L>
+where
+    L: FingerLink,
+    L::Element: Clone,
+{
+    fn clone(&self) -> Self {
+        FingerTree(match &self.0 {
+            Shape::Empty => Shape::Empty,
+            Shape::Single(item) => Shape::Single(item.clone()),
+            Shape::Deep(link) => Shape::Deep(L::clone(link)),
+        })
+    }
+}
+
+impl<L: FingerLink> FingerTree<L> {
+    /// Creates an empty `FingerTree`.
+    pub fn new() -> Self {
+        FingerTree::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self.0, Shape::Empty)
+    }
+
+    /// Returns the combined measure of all elements.
+    ///
+    /// Complexity: O(1)
+    pub fn measure(&self) -> <L::Element as Measured>::Measure {
+        match &self.0 {
+            Shape::Empty => Monoid::empty(),
+            Shape::Single(item) => item.measure(),
+            Shape::Deep(link) => match &link.node().0 {
+                Kind::Deep { measure, .. } => measure.clone(),
+                Kind::Branch { .. } => unreachable!("trees only link to deep nodes"),
+            },
+        }
+    }
+
+    /// Complexity: O(1)
+    pub fn front(&self) -> Option<&L::Element> {
+        match &self.0 {
+            Shape::Empty => None,
+            Shape::Single(item) => Some(item.outer_element(false)),
+            Shape::Deep(link) => Some(parts(link).0[0].outer_element(false)),
+        }
+    }
+
+    /// Complexity: O(1)
+    pub fn back(&self) -> Option<&L::Element> {
+        match &self.0 {
+            Shape::Empty => None,
+            Shape::Single(item) => Some(item.outer_element(true)),
+            Shape::Deep(link) => {
+                let suffix = parts(link).2;
+                Some(suffix[suffix.len() - 1].outer_element(true))
+            }
+        }
+    }
+
+    /// Returns `true` if both trees share the same root node, or are both
+    /// empty. Trees of a single element have no node and compare unequal.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Shape::Empty, Shape::Empty) => true,
+            (Shape::Deep(a), Shape::Deep(b)) => a.ptr_eq(b),
+            _ => false,
+        }
+    }
+
+    /// Returns the first element whose measure, combined with the
+    /// measures of all elements before it, satisfies `p`, or `None` if
+    /// even the measure of the whole tree doesn't.
+    ///
+    /// `p` must be monotonic: once true for a prefix, it must stay true
+    /// for every longer one.
+    ///
+    /// Complexity: O(log n)
+    pub fn find<P>(&self, p: P) -> Option<&L::Element>
+    where
+        P: Fn(&<L::Element as Measured>::Measure) -> bool,
+    {
+        if self.is_empty() || !p(&self.measure()) {
+            return None;
+        }
+        let mut before = Measure::<L>::empty();
+        let mut tree = self;
+        let mut item = loop {
+            match &tree.0 {
+                Shape::Empty => unreachable!("the predicate holds within the tree"),
+                Shape::Single(item) => break item,
+                Shape::Deep(link) => {
+                    let (prefix, middle, suffix) = parts(link);
+                    let after_prefix = before.combine(&measure_all(prefix));
+                    if p(&after_prefix) {
+                        break &prefix[search(&p, &mut before, prefix)];
+                    }
+                    let after_middle = after_prefix.combine(&middle.measure());
+                    if p(&after_middle) {
+                        before = after_prefix;
+                        tree = middle;
+                    } else {
+                        before = after_middle;
+                        break &suffix[search(&p, &mut before, suffix)];
+                    }
+                }
+            }
+        };
+        loop {
+            match item {
+                Item::Element(x) => return Some(x),
+                Item::Branch(_) => {
+                    let children = item.children();
+                    item = &children[search(&p, &mut before, children)];
+                }
+            }
+        }
+    }
+
+    /// Iterates over the elements from front to back.
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter {
+            stack: vec![Pending::Tree(self)],
+        }
+    }
+}
+
+impl<L> FingerTree<L>
+where
+    L: FingerLink,
+    L::Element: Clone,
+{
+    /// Returns a tree with `value` added at the front.
+    ///
+    /// Complexity: O(log n), O(1) amortised if old versions are not reused
+    pub fn pushed_front(&self, value: L::Element) -> Self {
+        self.pushed_front_item(Item::Element(value))
+    }
+
+    /// Returns a tree with `value` added at the back.
+    ///
+    /// Complexity: O(log n), O(1) amortised if old versions are not reused
+    pub fn pushed_back(&self, value: L::Element) -> Self {
+        self.pushed_back_item(Item::Element(value))
+    }
+
+    /// Returns the tree without its front element.
+    ///
+    /// Complexity: O(log n), O(1) amortised if old versions are not reused
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty tree.
+    pub fn popped_front(&self) -> Self {
+        match self.view_front() {
+            None => panic!("You can't pop an empty finger tree!"),
+            Some((_, rest)) => rest,
+        }
+    }
+
+    /// Returns the tree without its back element.
+    ///
+    /// Complexity: O(log n), O(1) amortised if old versions are not reused
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty tree.
+    pub fn popped_back(&self) -> Self {
+        match self.view_back() {
+            None => panic!("You can't pop an empty finger tree!"),
+            Some((_, rest)) => rest,
+        }
+    }
+
+    /// Returns the elements of `self` followed by those of `other`.
+    ///
+    /// Complexity: O(log min(m, n))
+    pub fn appended(&self, other: &Self) -> Self {
+        concatenated(self, Vec::new(), other)
+    }
+
+    /// Splits the tree in front of the first element for which the
+    /// measure of all elements up to and including it satisfies `p`. If
+    /// there is none, the second tree is empty.
+    ///
+    /// `p` must be monotonic, as for `find`.
+    ///
+    /// Complexity: O(log n)
+    pub fn split<P>(&self, p: P) -> (Self, Self)
+    where
+        P: Fn(&<L::Element as Measured>::Measure) -> bool,
+    {
+        if self.is_empty() || !p(&self.measure()) {
+            return (self.clone(), FingerTree::new());
+        }
+        let (left, item, right) = self.split_tree(&p, &Measure::<L>::empty());
+        (left, right.pushed_front_item(item))
+    }
+
+    fn pushed_front_item(&self, item: Item<L>) -> Self {
+        match &self.0 {
+            Shape::Empty => FingerTree(Shape::Single(item)),
+            Shape::Single(only) => deep(vec![item], FingerTree::new(), vec![only.clone()]),
+            Shape::Deep(link) => {
+                let (prefix, middle, suffix) = parts(link);
+                if prefix.len() == 4 {
+                    let middle = middle.pushed_front_item(branch(prefix[1..].to_vec()));
+                    deep(vec![item, prefix[0].clone()], middle, suffix.to_vec())
+                } else {
+                    let mut items = Vec::with_capacity(prefix.len() + 1);
+                    items.push(item);
+                    items.extend_from_slice(prefix);
+                    deep(items, middle.clone(), suffix.to_vec())
+                }
+            }
+        }
+    }
+
+    fn pushed_back_item(&self, item: Item<L>) -> Self {
+        match &self.0 {
+            Shape::Empty => FingerTree(Shape::Single(item)),
+            Shape::Single(only) => deep(vec![only.clone()], FingerTree::new(), vec![item]),
+            Shape::Deep(link) => {
+                let (prefix, middle, suffix) = parts(link);
+                if suffix.len() == 4 {
+                    let middle = middle.pushed_back_item(branch(suffix[..3].to_vec()));
+                    deep(prefix.to_vec(), middle, vec![suffix[3].clone(), item])
+                } else {
+                    let mut items = suffix.to_vec();
+                    items.push(item);
+                    deep(prefix.to_vec(), middle.clone(), items)
+                }
+            }
+        }
+    }
+
+    /// Returns the front item and the tree without it.
+    fn view_front(&self) -> Option<(Item<L>, Self)> {
+        match &self.0 {
+            Shape::Empty => None,
+            Shape::Single(item) => Some((item.clone(), FingerTree::new())),
+            Shape::Deep(link) => {
+                let (prefix, middle, suffix) = parts(link);
+                Some((prefix[0].clone(), deep_left(&prefix[1..], middle, suffix)))
+            }
+        }
+    }
+
+    /// Returns the back item and the tree without it.
+    fn view_back(&self) -> Option<(Item<L>, Self)> {
+        match &self.0 {
+            Shape::Empty => None,
+            Shape::Single(item) => Some((item.clone(), FingerTree::new())),
+            Shape::Deep(link) => {
+                let (prefix, middle, suffix) = parts(link);
+                let last = suffix.len() - 1;
+                Some((
+                    suffix[last].clone(),
+                    deep_right(prefix, middle, &suffix[..last]),
+                ))
+            }
+        }
+    }
+
+    /// Splits a non-empty tree around the item where `p` turns true,
+    /// given the measure `before` of everything in front of the tree.
+    fn split_tree<P>(&self, p: &P, before: &Measure<L>) -> (Self, Item<L>, Self)
+    where
+        P: Fn(&Measure<L>) -> bool,
+    {
+        let (prefix, middle, suffix) = match &self.0 {
+            Shape::Empty => unreachable!("only non-empty trees are split"),
+            Shape::Single(item) => return (FingerTree::new(), item.clone(), FingerTree::new()),
+            Shape::Deep(link) => parts(link),
+        };
+        let after_prefix = before.combine(&measure_all(prefix));
+        if p(&after_prefix) {
+            let (left, item, right) = split_items(p, before, prefix);
+            return (from_items(left), item, deep_left(right, middle, suffix));
+        }
+        let after_middle = after_prefix.combine(&middle.measure());
+        if p(&after_middle) {
+            let (middle_left, branch, middle_right) = middle.split_tree(p, &after_prefix);
+            let before = after_prefix.combine(&middle_left.measure());
+            let (left, item, right) = split_items(p, &before, branch.children());
+            return (
+                deep_right(prefix, &middle_left, left),
+                item,
+                deep_left(right, &middle_right, suffix),
+            );
+        }
+        let (left, item, right) = split_items(p, &after_middle, suffix);
+        (deep_right(prefix, middle, left), item, from_items(right))
+    }
+}
+
+fn parts<L: FingerLink>(link: &L) -> (&[Item<L>], &FingerTree<L>, &[Item<L>]) {
+    match &link.node().0 {
+        Kind::Deep {
+            prefix,
+            middle,
+            suffix,
+            ..
+        } => (prefix, middle, suffix),
+        Kind::Branch { .. } => unreachable!("trees only link to deep nodes"),
+    }
+}
+
+fn measure_all<L: FingerLink>(items: &[Item<L>]) -> Measure<L> {
+    items.iter().fold(Measure::<L>::empty(), |measure, item| {
+        measure.combine(&item.measure())
+    })
+}
+
+fn branch<L: FingerLink>(items: Vec<Item<L>>) -> Item<L> {
+    let measure = measure_all(&items);
+    Item::Branch(L::new(FingerNode(Kind::Branch { measure, items })))
+}
+
+fn deep<L: FingerLink>(
+    prefix: Vec<Item<L>>,
+    middle: FingerTree<L>,
+    suffix: Vec<Item<L>>,
+) -> FingerTree<L> {
+    let measure = measure_all(&prefix)
+        .combine(&middle.measure())
+        .combine(&measure_all(&suffix));
+    FingerTree(Shape::Deep(L::new(FingerNode(Kind::Deep {
+        measure,
+        prefix,
+        middle,
+        suffix,
+    }))))
+}
+
+fn from_items<L>(items: &[Item<L>]) -> FingerTree<L>
+where
+    L: FingerLink,
+    L::Element: Clone,
+{
+    items.iter().fold(FingerTree::new(), |tree, item| {
+        tree.pushed_back_item(item.clone())
+    })
+}
+
+/// Makes a deep tree whose prefix may be empty, borrowing one from the
+/// middle if so.
+fn deep_left<L>(prefix: &[Item<L>], middle: &FingerTree<L>, suffix: &[Item<L>]) -> FingerTree<L>
+where
+    L: FingerLink,
+    L::Element: Clone,
+{
+    if !prefix.is_empty() {
+        return deep(prefix.to_vec(), middle.clone(), suffix.to_vec());
+    }
+    match middle.view_front() {
+        None => from_items(suffix),
+        Some((branch, middle)) => deep(branch.children().to_vec(), middle, suffix.to_vec()),
+    }
+}
+
+/// Makes a deep tree whose suffix may be empty, borrowing one from the
+/// middle if so.
+fn deep_right<L>(prefix: &[Item<L>], middle: &FingerTree<L>, suffix: &[Item<L>]) -> FingerTree<L>
+where
+    L: FingerLink,
+    L::Element: Clone,
+{
+    if !suffix.is_empty() {
+        return deep(prefix.to_vec(), middle.clone(), suffix.to_vec());
+    }
+    match middle.view_back() {
+        None => from_items(prefix),
+        Some((branch, middle)) => deep(prefix.to_vec(), middle, branch.children().to_vec()),
+    }
+}
+
+/// Joins `left`, `items` and `right`. The items are at the level of the
+/// two trees' digits.
+fn concatenated<L>(
+    left: &FingerTree<L>,
+    items: Vec<Item<L>>,
+    right: &FingerTree<L>,
+) -> FingerTree<L>
+where
+    L: FingerLink,
+    L::Element: Clone,
+{
+    match (&left.0, &right.0) {
+        (Shape::Empty, _) => items
+            .into_iter()
+            .rev()
+            .fold(right.clone(), |tree, item| tree.pushed_front_item(item)),
+        (_, Shape::Empty) => items
+            .into_iter()
+            .fold(left.clone(), |tree, item| tree.pushed_back_item(item)),
+        (Shape::Single(only), _) => {
+            let mut all = vec![only.clone()];
+            all.extend(items);
+            concatenated(&FingerTree::new(), all, right)
+        }
+        (_, Shape::Single(only)) => {
+            let mut all = items;
+            all.push(only.clone());
+            concatenated(left, all, &FingerTree::new())
+        }
+        (Shape::Deep(left), Shape::Deep(right)) => {
+            let (prefix, left_middle, left_suffix) = parts(left);
+            let (right_prefix, right_middle, suffix) = parts(right);
+            let mut between = left_suffix.to_vec();
+            between.extend(items);
+            between.extend_from_slice(right_prefix);
+            let middle = concatenated(left_middle, branches(&between), right_middle);
+            deep(prefix.to_vec(), middle, suffix.to_vec())
+        }
+    }
+}
+
+/// Groups two or more items into branches of two or three.
+fn branches<L>(items: &[Item<L>]) -> Vec<Item<L>>
+where
+    L: FingerLink,
+    L::Element: Clone,
+{
+    let mut grouped = Vec::with_capacity(items.len() / 2);
+    let mut rest = items;
+    while rest.len() == 3 || rest.len() > 4 {
+        grouped.push(branch(rest[..3].to_vec()));
+        rest = &rest[3..];
+    }
+    if !rest.is_empty() {
+        // Two or four left.
+        let (a, b) = rest.split_at(2);
+        grouped.push(branch(a.to_vec()));
+        if !b.is_empty() {
+            grouped.push(branch(b.to_vec()));
+        }
+    }
+    grouped
+}
+
+/// Returns the index of the first of `items` where `p` turns true, or of
+/// the last one, and advances `before` to the measure in front of it.
+fn search<L, P>(p: &P, before: &mut Measure<L>, items: &[Item<L>]) -> usize
+where
+    L: FingerLink,
+    P: Fn(&Measure<L>) -> bool,
+{
+    let last = items.len() - 1;
+    for (index, item) in items[..last].iter().enumerate() {
+        let after = before.combine(&item.measure());
+        if p(&after) {
+            return index;
+        }
+        *before = after;
+    }
+    last
+}
+
+/// Splits `items` around the first one where `p` turns true, or the last
+/// one.
+fn split_items<'a, L, P>(
+    p: &P,
+    before: &Measure<L>,
+    items: &'a [Item<L>],
+) -> (&'a [Item<L>], Item<L>, &'a [Item<L>])
+where
+    L: FingerLink,
+    L::Element: Clone,
+    P: Fn(&Measure<L>) -> bool,
+{
+    let index = search(p, &mut before.clone(), items);
+    (&items[..index], items[index].clone(), &items[index + 1..])
+}
+
+enum Pending<'a, L: FingerLink> {
+    Tree(&'a FingerTree<L>),
+    Item(&'a Item<L>),
+}
+
+pub struct Iter<'a, L: FingerLink> {
+    stack: Vec<Pending<'a, L>>,
+}
+
+impl<'a, L: FingerLink> Iterator for Iter<'a, L> {
+    type Item = &'a L::Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(pending) = self.stack.pop() {
+            match pending {
+                Pending::Item(Item::Element(x)) => return Some(x),
+                Pending::Item(item) => {
+                    self.stack
+                        .extend(item.children().iter().rev().map(Pending::Item));
+                }
+                Pending::Tree(tree) => match &tree.0 {
+                    Shape::Empty => {}
+                    Shape::Single(item) => self.stack.push(Pending::Item(item)),
+                    Shape::Deep(link) => {
+                        let (prefix, middle, suffix) = parts(link);
+                        self.stack.extend(suffix.iter().rev().map(Pending::Item));
+                        self.stack.push(Pending::Tree(middle));
+                        self.stack.extend(prefix.iter().rev().map(Pending::Item));
+                    }
+                },
+            }
+        }
+        None
+    }
+}
+
+impl<'a, L: FingerLink> IntoIterator for &'a FingerTree<L> {
+    type Item = &'a L::Element;
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<L> FromIterator<L::Element> for FingerTree<L>
+where
+    L: FingerLink,
+    L::Element: Clone,
+{
+    fn from_iter<I: IntoIterator<Item = L::Element>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(FingerTree::new(), |tree, x| tree.pushed_back(x))
+    }
+}
+
+impl<L> PartialEq for FingerTree<L>
+where
+    L: FingerLink,
+    L::Element: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.iter().eq(other.iter())
+    }
+}
+
+impl<L> Debug for FingerTree<L>
+where
+    L: FingerLink,
+    L::Element: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,734 @@
+use alloc::{vec, vec::Vec};
+use core::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    hash::{BuildHasher, BuildHasherDefault, Hash, Hasher},
+    iter::FromIterator,
+    slice,
+};
+
+/// The number of hash bits consumed at each level of the trie.
+const BITS: u32 = 5;
+const MASK: u64 = (1 << BITS) - 1;
+
+pub trait HashLink: Sized {
+    type Key;
+    type Value;
+
+    fn new(node: HashNode<Self>) -> Self;
+    fn clone(&self) -> Self;
+    fn node(&self) -> &HashNode<Self>;
+    /// Returns `true` if both links point to the same node.
+    fn ptr_eq(&self, other: &Self) -> bool;
+}
+
+/// A node of the trie behind `HashMap`.
+pub struct HashNode<L: HashLink>(Kind<L>);
+
+enum Kind<L: HashLink> {
+    /// The entries present among the 32 slots of this level, in slot
+    /// order; bit `i` of `bitmap` is set if slot `i` is occupied.
+    Branch { bitmap: u32, entries: Vec<Entry<L>> },
+    /// Keys whose hashes are equal in all 64 bits.
+    Collision {
+        hash: u64,
+        entries: Vec<(L::Key, L::Value)>,
+    },
+}
+
+enum Entry<L: HashLink> {
+    Leaf {
+        hash: u64,
+        key: L::Key,
+        value: L::Value,
+    },
+    Sub(L),
+}
+
+impl<L> Clone for Entry<L>
+where
+    L: HashLink,
+    L::Key: Clone,
+    L::Value: Clone,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Entry::Leaf { hash, key, value } => Entry::Leaf {
+                hash: *hash,
+                key: key.clone(),
+                value: value.clone(),
+            },
+            Entry::Sub(link) => Entry::Sub(link.clone()),
+        }
+    }
+}
+
+/// A persistent hash map, implemented as a hash array mapped trie.
+///
+/// Every level of the trie uses five bits of a key's 64 bit hash to
+/// pick one of 32 slots, and keys with equal hashes share a collision
+/// node. Inserting or removing copies the nodes on the path to the key,
+/// at most 13 of them, and shares the rest with the original map. An
+/// operation that doesn't change the map returns a map sharing its root,
+/// so `ptr_eq` detects it.
+///
+/// The hasher is pluggable through `S`. The default, `DefaultHashBuilder`,
+/// works without `std` but is not randomly keyed; use a keyed
+/// `BuildHasher` such as `std`'s `RandomState` for untrusted keys.
+pub struct HashMap<L, S> {
+    root: Option<L>,
+    len: usize,
+    hasher: S,
+}
+
+impl<L, S: Default> Default for HashMap<L, S> {
+    fn default() -> Self {
+        HashMap::with_hasher(S::default())
+    }
+}
+
+impl<L: HashLink, S: Clone> Clone for HashMap<L, S> {
+    fn clone(&self) -> Self {
+        HashMap {
+            root: self.root.as_ref().map(L::clone),
+            len: self.len,
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+
+impl<L> HashMap<L, DefaultHashBuilder> {
+    /// Creates an empty `HashMap` with the default hasher.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::HashMap;
+    ///
+    /// let map: HashMap<&str, u32> = HashMap::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        HashMap::default()
+    }
+}
+
+impl<L, S> HashMap<L, S> {
+    /// Creates an empty `HashMap` that hashes its keys with `hasher`.
+    pub fn with_hasher(hasher: S) -> Self {
+        HashMap {
+            root: None,
+            len: 0,
+            hasher,
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.hasher
+    }
+
+    /// Returns the number of entries.
+    ///
+    /// Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<L: HashLink, S> HashMap<L, S> {
+    /// Returns `true` if both maps share the same root node, or are both
+    /// empty.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            _ => false,
+        }
+    }
+
+    /// Iterates over the entries, in an order that depends on the hashes
+    /// of the keys.
+    pub fn iter(&self) -> Iter<'_, L> {
+        let stack = match &self.root {
+            None => Vec::new(),
+            Some(root) => match &root.node().0 {
+                Kind::Branch { entries, .. } => vec![entries.iter()],
+                Kind::Collision { .. } => unreachable!("the root is a branch"),
+            },
+        };
+        Iter {
+            stack,
+            collision: [].iter(),
+            remaining: self.len,
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &L::Key> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &L::Value> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<L, S> HashMap<L, S>
+where
+    L: HashLink,
+    S: BuildHasher,
+{
+    fn hash<Q: Hash + ?Sized>(&self, key: &Q) -> u64 {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the entry for `key`, if there is one.
+    ///
+    /// Complexity: O(1), at most 13 levels
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&L::Key, &L::Value)>
+    where
+        L::Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hash(key);
+        let mut link = self.root.as_ref()?;
+        let mut shift = 0;
+        loop {
+            match &link.node().0 {
+                Kind::Branch { bitmap, entries } => {
+                    let bit = 1 << fragment(hash, shift);
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+                    match &entries[index(*bitmap, bit)] {
+                        Entry::Leaf {
+                            hash: h,
+                            key: k,
+                            value,
+                        } => {
+                            return if *h == hash && k.borrow() == key {
+                                Some((k, value))
+                            } else {
+                                None
+                            };
+                        }
+                        Entry::Sub(sub) => {
+                            link = sub;
+                            shift += BITS;
+                        }
+                    }
+                }
+                Kind::Collision { hash: h, entries } => {
+                    if *h != hash {
+                        return None;
+                    }
+                    return entries
+                        .iter()
+                        .find(|(k, _)| k.borrow() == key)
+                        .map(|(k, v)| (k, v));
+                }
+            }
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&L::Value>
+    where
+        L::Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    pub fn get_or_default<'a, Q>(&'a self, key: &Q, default: &'a L::Value) -> &'a L::Value
+    where
+        L::Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).unwrap_or(default)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        L::Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_key_value(key).is_some()
+    }
+}
+
+impl<L, S> HashMap<L, S>
+where
+    L: HashLink,
+    L::Key: Clone + Hash + Eq,
+    L::Value: Clone,
+    S: BuildHasher + Clone,
+{
+    /// Returns the map with `key` mapped to `value`, or `self` if `key`
+    /// is already present.
+    ///
+    /// Complexity: O(1), at most 13 levels
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::HashMap;
+    ///
+    /// let m1 = HashMap::new().inserted("a", 1);
+    /// let m2 = m1.inserted("b", 2);
+    ///
+    /// assert_eq!(m2.get("b"), Some(&2));
+    /// assert_eq!(m1.get("b"), None);
+    /// assert!(m2.inserted("a", 3).ptr_eq(&m2));
+    /// ```
+    pub fn inserted(&self, key: L::Key, value: L::Value) -> Self {
+        self.inserted_with(key, value, false)
+    }
+
+    /// Returns the map with `key` mapped to `value`, replacing the value
+    /// it had.
+    ///
+    /// Complexity: O(1), at most 13 levels
+    pub fn inserted_or_replaced(&self, key: L::Key, value: L::Value) -> Self {
+        self.inserted_with(key, value, true)
+    }
+
+    fn inserted_with(&self, key: L::Key, value: L::Value, replace: bool) -> Self {
+        let hash = self.hash(&key);
+        let (root, added) = match &self.root {
+            None => {
+                let leaf = Entry::Leaf { hash, key, value };
+                let node = Kind::Branch {
+                    bitmap: 1 << fragment(hash, 0),
+                    entries: vec![leaf],
+                };
+                (L::new(HashNode(node)), true)
+            }
+            Some(root) => match inserted_node(root, 0, hash, key, value, replace) {
+                None => return self.clone(),
+                Some(inserted) => inserted,
+            },
+        };
+        HashMap {
+            root: Some(root),
+            len: self.len + usize::from(added),
+            hasher: self.hasher.clone(),
+        }
+    }
+
+    /// Returns the map without `key`, or `self` if `key` is not present.
+    ///
+    /// Nodes left with a single entry are merged into their parent, so
+    /// the trie stays as shallow as the remaining keys allow.
+    ///
+    /// Complexity: O(1), at most 13 levels
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::HashMap;
+    ///
+    /// let m1 = HashMap::new().inserted(1, "a").inserted(2, "b");
+    /// let m2 = m1.removed(&1);
+    ///
+    /// assert_eq!(m2.len(), 1);
+    /// assert!(!m2.contains_key(&1));
+    /// assert!(m2.removed(&1).ptr_eq(&m2));
+    /// ```
+    pub fn removed<Q>(&self, key: &Q) -> Self
+    where
+        L::Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let root = match &self.root {
+            None => return self.clone(),
+            Some(root) => root,
+        };
+        let root = match removed_node(root, 0, self.hash(key), key) {
+            Removed::Unchanged => return self.clone(),
+            Removed::Gone => None,
+            Removed::Node(node) => Some(node),
+            Removed::Leaf(..) => unreachable!("the root is never merged"),
+        };
+        HashMap {
+            root,
+            len: self.len - 1,
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+
+/// Returns the slot of `hash` at the level that starts at bit `shift`.
+fn fragment(hash: u64, shift: u32) -> u32 {
+    ((hash >> shift) & MASK) as u32
+}
+
+/// Returns the position in the entries of the slot marked by `bit`.
+fn index(bitmap: u32, bit: u32) -> usize {
+    (bitmap & (bit - 1)).count_ones() as usize
+}
+
+/// Returns a copy of the node with `key` inserted, and whether it is a
+/// new key, or `None` if nothing changes.
+fn inserted_node<L>(
+    link: &L,
+    shift: u32,
+    hash: u64,
+    key: L::Key,
+    value: L::Value,
+    replace: bool,
+) -> Option<(L, bool)>
+where
+    L: HashLink,
+    L::Key: Clone + Eq,
+    L::Value: Clone,
+{
+    match &link.node().0 {
+        Kind::Branch { bitmap, entries } => {
+            let bit = 1 << fragment(hash, shift);
+            let i = index(*bitmap, bit);
+            if bitmap & bit == 0 {
+                let mut entries = entries.clone();
+                entries.insert(i, Entry::Leaf { hash, key, value });
+                let node = Kind::Branch {
+                    bitmap: bitmap | bit,
+                    entries,
+                };
+                return Some((L::new(HashNode(node)), true));
+            }
+            let (entry, added) = match &entries[i] {
+                Entry::Leaf {
+                    hash: h,
+                    key: k,
+                    value: v,
+                } => {
+                    if *h == hash && *k == key {
+                        if !replace {
+                            return None;
+                        }
+                        (Entry::Leaf { hash, key, value }, false)
+                    } else {
+                        let old = Entry::Leaf {
+                            hash: *h,
+                            key: k.clone(),
+                            value: v.clone(),
+                        };
+                        let new = Entry::Leaf { hash, key, value };
+                        (
+                            Entry::Sub(paired(shift + BITS, (*h, old), (hash, new))),
+                            true,
+                        )
+                    }
+                }
+                Entry::Sub(sub) => {
+                    let (sub, added) = inserted_node(sub, shift + BITS, hash, key, value, replace)?;
+                    (Entry::Sub(sub), added)
+                }
+            };
+            let mut entries = entries.clone();
+            entries[i] = entry;
+            let node = Kind::Branch {
+                bitmap: *bitmap,
+                entries,
+            };
+            Some((L::new(HashNode(node)), added))
+        }
+        Kind::Collision { hash: h, entries } => {
+            if *h != hash {
+                let old = Entry::Sub(link.clone());
+                let new = Entry::Leaf { hash, key, value };
+                return Some((paired(shift, (*h, old), (hash, new)), true));
+            }
+            let mut entries = entries.clone();
+            let added = match entries.iter().position(|(k, _)| *k == key) {
+                Some(_) if !replace => return None,
+                Some(i) => {
+                    entries[i] = (key, value);
+                    false
+                }
+                None => {
+                    entries.push((key, value));
+                    true
+                }
+            };
+            let node = Kind::Collision { hash: *h, entries };
+            Some((L::new(HashNode(node)), added))
+        }
+    }
+}
+
+/// Builds the smallest subtrie, starting at bit `shift`, that holds two
+/// entries with the given hashes. Only leaves may have equal hashes.
+fn paired<L: HashLink>(shift: u32, a: (u64, Entry<L>), b: (u64, Entry<L>)) -> L {
+    let ((ha, a), (hb, b)) = (a, b);
+    if ha == hb {
+        let entries = match (a, b) {
+            (
+                Entry::Leaf {
+                    key: ka, value: va, ..
+                },
+                Entry::Leaf {
+                    key: kb, value: vb, ..
+                },
+            ) => vec![(ka, va), (kb, vb)],
+            _ => unreachable!("only leaves collide"),
+        };
+        return L::new(HashNode(Kind::Collision { hash: ha, entries }));
+    }
+    let (fa, fb) = (fragment(ha, shift), fragment(hb, shift));
+    let node = if fa == fb {
+        Kind::Branch {
+            bitmap: 1 << fa,
+            entries: vec![Entry::Sub(paired(shift + BITS, (ha, a), (hb, b)))],
+        }
+    } else {
+        Kind::Branch {
+            bitmap: (1 << fa) | (1 << fb),
+            entries: if fa < fb { vec![a, b] } else { vec![b, a] },
+        }
+    };
+    L::new(HashNode(node))
+}
+
+enum Removed<L: HashLink> {
+    Unchanged,
+    /// The node lost its last entry.
+    Gone,
+    Node(L),
+    /// The node is left with a single key, to be stored in the parent.
+    Leaf(u64, L::Key, L::Value),
+}
+
+fn removed_node<L, Q>(link: &L, shift: u32, hash: u64, key: &Q) -> Removed<L>
+where
+    L: HashLink,
+    L::Key: Clone + Borrow<Q>,
+    L::Value: Clone,
+    Q: Eq + ?Sized,
+{
+    match &link.node().0 {
+        Kind::Branch { bitmap, entries } => {
+            let bit = 1 << fragment(hash, shift);
+            if bitmap & bit == 0 {
+                return Removed::Unchanged;
+            }
+            let i = index(*bitmap, bit);
+            let replacement = match &entries[i] {
+                Entry::Leaf {
+                    hash: h, key: k, ..
+                } => {
+                    if *h != hash || k.borrow() != key {
+                        return Removed::Unchanged;
+                    }
+                    None
+                }
+                Entry::Sub(sub) => match removed_node(sub, shift + BITS, hash, key) {
+                    Removed::Unchanged => return Removed::Unchanged,
+                    Removed::Gone => None,
+                    Removed::Node(sub) => Some(Entry::Sub(sub)),
+                    Removed::Leaf(hash, key, value) => Some(Entry::Leaf { hash, key, value }),
+                },
+            };
+            let mut entries = entries.clone();
+            let bitmap = match replacement {
+                Some(entry) => {
+                    entries[i] = entry;
+                    *bitmap
+                }
+                None => {
+                    entries.remove(i);
+                    bitmap & !bit
+                }
+            };
+            if entries.is_empty() {
+                return Removed::Gone;
+            }
+            if shift > 0 && entries.len() == 1 {
+                if let Entry::Leaf { .. } = entries[0] {
+                    match entries.pop() {
+                        Some(Entry::Leaf { hash, key, value }) => {
+                            return Removed::Leaf(hash, key, value)
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+            }
+            Removed::Node(L::new(HashNode(Kind::Branch { bitmap, entries })))
+        }
+        Kind::Collision { hash: h, entries } => {
+            if *h != hash {
+                return Removed::Unchanged;
+            }
+            let i = match entries.iter().position(|(k, _)| k.borrow() == key) {
+                None => return Removed::Unchanged,
+                Some(i) => i,
+            };
+            let mut entries = entries.clone();
+            entries.remove(i);
+            if entries.len() == 1 {
+                let (key, value) = entries.pop().expect("one entry");
+                return Removed::Leaf(*h, key, value);
+            }
+            Removed::Node(L::new(HashNode(Kind::Collision { hash: *h, entries })))
+        }
+    }
+}
+
+pub struct Iter<'a, L: HashLink> {
+    stack: Vec<slice::Iter<'a, Entry<L>>>,
+    collision: slice::Iter<'a, (L::Key, L::Value)>,
+    remaining: usize,
+}
+
+impl<'a, L: HashLink> Iterator for Iter<'a, L> {
+    type Item = (&'a L::Key, &'a L::Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, value)) = self.collision.next() {
+                self.remaining -= 1;
+                return Some((key, value));
+            }
+            match self.stack.last_mut()?.next() {
+                None => {
+                    self.stack.pop();
+                }
+                Some(Entry::Leaf { key, value, .. }) => {
+                    self.remaining -= 1;
+                    return Some((key, value));
+                }
+                Some(Entry::Sub(sub)) => match &sub.node().0 {
+                    Kind::Branch { entries, .. } => self.stack.push(entries.iter()),
+                    Kind::Collision { entries, .. } => self.collision = entries.iter(),
+                },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, L: HashLink> ExactSizeIterator for Iter<'a, L> {}
+
+impl<'a, L: HashLink, S> IntoIterator for &'a HashMap<L, S> {
+    type Item = (&'a L::Key, &'a L::Value);
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<L, S> FromIterator<(L::Key, L::Value)> for HashMap<L, S>
+where
+    L: HashLink,
+    L::Key: Clone + Hash + Eq,
+    L::Value: Clone,
+    S: BuildHasher + Clone + Default,
+{
+    /// Later entries replace earlier ones with the same key.
+    fn from_iter<I: IntoIterator<Item = (L::Key, L::Value)>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(HashMap::default(), |map, (key, value)| {
+                map.inserted_or_replaced(key, value)
+            })
+    }
+}
+
+impl<L, S> PartialEq for HashMap<L, S>
+where
+    L: HashLink,
+    L::Key: Hash + Eq,
+    L::Value: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.ptr_eq(other) {
+            return true;
+        }
+        self.len == other.len
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<L, S> Debug for HashMap<L, S>
+where
+    L: HashLink,
+    L::Key: Debug,
+    L::Value: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// The hasher `HashMap` uses by default.
+///
+/// It mixes each word into the state with a rotate, xor and multiply,
+/// like the hasher in rustc, and finishes with the 64 bit finaliser of
+/// MurmurHash3, so that every bit of the result depends on every input
+/// bit; the trie looks at the low bits first. It needs no `std` and no
+/// source of randomness, and is therefore predictable: an adversary who
+/// picks the keys can force collisions.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultHasher(u64);
+
+pub type DefaultHashBuilder = BuildHasherDefault<DefaultHasher>;
+
+impl DefaultHasher {
+    fn add(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+}
+
+impl Hasher for DefaultHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            self.add(u64::from_le_bytes(word));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add(u64::from_le_bytes(word) ^ ((rest.len() as u64) << 56));
+        }
+    }
+
+    fn write_u8(&mut self, n: u8) {
+        self.add(u64::from(n));
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.add(u64::from(n));
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.add(u64::from(n));
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.add(n);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.add(n as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut h = self.0;
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        h ^ (h >> 33)
+    }
+}
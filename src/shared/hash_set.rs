@@ -0,0 +1,186 @@
+use core::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hash},
+    iter::FromIterator,
+};
+
+use super::hash_map::{self, DefaultHashBuilder, HashLink, HashMap};
+
+/// A persistent hash set: a `HashMap` whose values are `()`, with the
+/// same sharing between versions.
+pub struct HashSet<L, S>(HashMap<L, S>);
+
+impl<L, S: Default> Default for HashSet<L, S> {
+    fn default() -> Self {
+        HashSet(HashMap::default())
+    }
+}
+
+impl<L: HashLink, S: Clone> Clone for HashSet<L, S> {
+    fn clone(&self) -> Self {
+        HashSet(self.0.clone())
+    }
+}
+
+impl<L> HashSet<L, DefaultHashBuilder> {
+    /// Creates an empty `HashSet` with the default hasher.
+    pub fn new() -> Self {
+        HashSet::default()
+    }
+}
+
+impl<L, S> HashSet<L, S> {
+    /// Creates an empty `HashSet` that hashes its elements with `hasher`.
+    pub fn with_hasher(hasher: S) -> Self {
+        HashSet(HashMap::with_hasher(hasher))
+    }
+
+    pub fn hasher(&self) -> &S {
+        self.0.hasher()
+    }
+
+    /// Returns the number of elements.
+    ///
+    /// Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<L: HashLink<Value = ()>, S> HashSet<L, S> {
+    /// Returns `true` if both sets share the same root node, or are both
+    /// empty.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+
+    /// Iterates over the elements, in an order that depends on their
+    /// hashes.
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter(self.0.iter())
+    }
+}
+
+impl<L, S> HashSet<L, S>
+where
+    L: HashLink<Value = ()>,
+    S: BuildHasher,
+{
+    pub fn contains<Q>(&self, x: &Q) -> bool
+    where
+        L::Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.0.contains_key(x)
+    }
+
+    /// Provides a reference to the element equal to `x`, if there is one.
+    pub fn get<Q>(&self, x: &Q) -> Option<&L::Key>
+    where
+        L::Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.0.get_key_value(x).map(|(key, _)| key)
+    }
+}
+
+impl<L, S> HashSet<L, S>
+where
+    L: HashLink<Value = ()>,
+    L::Key: Clone + Hash + Eq,
+    S: BuildHasher + Clone,
+{
+    /// Returns the set with `x` added, or `self` if it already contains
+    /// an equal element.
+    ///
+    /// Complexity: O(1), at most 13 levels
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::HashSet;
+    ///
+    /// let s1 = HashSet::new().inserted(1).inserted(2);
+    /// let s2 = s1.inserted(3);
+    ///
+    /// assert!(s2.contains(&3));
+    /// assert!(!s1.contains(&3));
+    /// assert!(s2.inserted(1).ptr_eq(&s2));
+    /// ```
+    pub fn inserted(&self, x: L::Key) -> Self {
+        HashSet(self.0.inserted(x, ()))
+    }
+
+    /// Returns the set without `x`, or `self` if it doesn't contain it.
+    ///
+    /// Complexity: O(1), at most 13 levels
+    pub fn removed<Q>(&self, x: &Q) -> Self
+    where
+        L::Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        HashSet(self.0.removed(x))
+    }
+}
+
+pub struct Iter<'a, L: HashLink>(hash_map::Iter<'a, L>);
+
+impl<'a, L: HashLink> Iterator for Iter<'a, L> {
+    type Item = &'a L::Key;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, L: HashLink> ExactSizeIterator for Iter<'a, L> {}
+
+impl<'a, L: HashLink<Value = ()>, S> IntoIterator for &'a HashSet<L, S> {
+    type Item = &'a L::Key;
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<L, S> FromIterator<L::Key> for HashSet<L, S>
+where
+    L: HashLink<Value = ()>,
+    L::Key: Clone + Hash + Eq,
+    S: BuildHasher + Clone + Default,
+{
+    fn from_iter<I: IntoIterator<Item = L::Key>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(HashSet::default(), |set, x| set.inserted(x))
+    }
+}
+
+impl<L, S> PartialEq for HashSet<L, S>
+where
+    L: HashLink<Value = ()>,
+    L::Key: Hash + Eq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<L, S> Debug for HashSet<L, S>
+where
+    L: HashLink<Value = ()>,
+    L::Key: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,585 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+/// A reference counted pointer to a node of an `IntMap`.
+pub trait IntLink: Sized {
+    type Value;
+
+    fn new(node: IntNode<Self>) -> Self;
+    fn clone(&self) -> Self;
+    fn node(&self) -> &IntNode<Self>;
+    /// Returns `true` if both links point to the same node.
+    fn ptr_eq(&self, other: &Self) -> bool;
+}
+
+/// A node of the trie behind `IntMap`.
+pub struct IntNode<L: IntLink>(Kind<L>);
+
+enum Kind<L: IntLink> {
+    Leaf {
+        key: u64,
+        value: L::Value,
+    },
+    /// All keys below agree with `prefix` on the bits above `mask`, a
+    /// single bit. Keys with that bit clear go left, the others right.
+    Branch {
+        prefix: u64,
+        mask: u64,
+        len: usize,
+        left: L,
+        right: L,
+    },
+}
+
+/// A persistent map from `u64` keys, implemented as a big-endian
+/// Patricia trie.
+///
+/// Every branch tests the highest bit in which the keys below it differ,
+/// so the shape of the trie depends only on the keys, not on the order
+/// they were inserted in, and the keys are kept in ascending order. A
+/// path has at most 65 nodes. Inserting or removing copies the nodes on
+/// the path to the key and shares the rest with the original map, and
+/// values are never copied.
+///
+/// `union` and `intersection` walk both tries together, skip every
+/// subtrie that only one of them has a prefix for, and reuse subtries
+/// the two maps share.
+pub struct IntMap<L> {
+    root: Option<L>,
+}
+
+impl<L> Default for IntMap<L> {
+    fn default() -> Self {
+        IntMap { root: None }
+    }
+}
+
+impl<L: IntLink> Clone for IntMap<L> {
+    fn clone(&self) -> Self {
+        IntMap {
+            root: self.root.as_ref().map(L::clone),
+        }
+    }
+}
+
+impl<L: IntLink> IntMap<L> {
+    /// Creates an empty `IntMap`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::IntMap;
+    ///
+    /// let map: IntMap<&str> = IntMap::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        IntMap::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the number of entries.
+    ///
+    /// Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.root.as_ref().map_or(0, len)
+    }
+
+    /// Returns `true` if both maps share the same root node, or are both
+    /// empty.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            _ => false,
+        }
+    }
+
+    /// Iterates over the entries in ascending order of their keys.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::IntMap;
+    ///
+    /// let map: IntMap<_> = [(3, "c"), (1, "a"), (2, "b")].into_iter().collect();
+    ///
+    /// assert!(map.values().copied().eq(["a", "b", "c"]));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter {
+            stack: self.root.iter().collect(),
+            remaining: self.len(),
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &u64> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &L::Value> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Returns the entry for `key`, if there is one.
+    ///
+    /// Complexity: O(min(n, 64))
+    pub fn get_key_value(&self, key: &u64) -> Option<(&u64, &L::Value)> {
+        match &leaf(self.root.as_ref()?, *key)?.node().0 {
+            Kind::Leaf { key, value } => Some((key, value)),
+            Kind::Branch { .. } => unreachable!("leaf returns leaves"),
+        }
+    }
+
+    pub fn get(&self, key: &u64) -> Option<&L::Value> {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    pub fn get_or_default<'a>(&'a self, key: &u64, default: &'a L::Value) -> &'a L::Value {
+        self.get(key).unwrap_or(default)
+    }
+
+    pub fn contains_key(&self, key: &u64) -> bool {
+        self.get_key_value(key).is_some()
+    }
+
+    /// Returns the map with `key` mapped to `value`, or `self` if `key`
+    /// is already present.
+    ///
+    /// Complexity: O(min(n, 64))
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::IntMap;
+    ///
+    /// let m1 = IntMap::new().inserted(1, "a");
+    /// let m2 = m1.inserted(2, "b");
+    ///
+    /// assert_eq!(m2.get(&2), Some(&"b"));
+    /// assert_eq!(m1.get(&2), None);
+    /// assert!(m2.inserted(1, "c").ptr_eq(&m2));
+    /// ```
+    pub fn inserted(&self, key: u64, value: L::Value) -> Self {
+        self.inserted_with(key, value, false)
+    }
+
+    /// Returns the map with `key` mapped to `value`, replacing the value
+    /// it had.
+    ///
+    /// Complexity: O(min(n, 64))
+    pub fn inserted_or_replaced(&self, key: u64, value: L::Value) -> Self {
+        self.inserted_with(key, value, true)
+    }
+
+    fn inserted_with(&self, key: u64, value: L::Value, replace: bool) -> Self {
+        let new = L::new(IntNode(Kind::Leaf { key, value }));
+        let root = match &self.root {
+            None => new,
+            Some(root) => match inserted_leaf(root, key, &new, replace) {
+                None => return self.clone(),
+                Some(root) => root,
+            },
+        };
+        IntMap { root: Some(root) }
+    }
+
+    /// Returns the map without `key`, or `self` if `key` is not present.
+    ///
+    /// Complexity: O(min(n, 64))
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::IntMap;
+    ///
+    /// let m1 = IntMap::new().inserted(1, "a").inserted(2, "b");
+    /// let m2 = m1.removed(&1);
+    ///
+    /// assert_eq!(m2.len(), 1);
+    /// assert!(!m2.contains_key(&1));
+    /// assert!(m2.removed(&1).ptr_eq(&m2));
+    /// ```
+    pub fn removed(&self, key: &u64) -> Self {
+        let root = match &self.root {
+            None => return self.clone(),
+            Some(root) => root,
+        };
+        match removed_node(root, *key) {
+            Removed::Unchanged => self.clone(),
+            Removed::Gone => IntMap::new(),
+            Removed::Node(root) => IntMap { root: Some(root) },
+        }
+    }
+
+    /// Returns a map with the entries of both maps. Where both have a
+    /// key, the value from `self` is kept.
+    ///
+    /// Complexity: O(n + m), but subtries only one of the maps has keys
+    /// in are shared without being visited.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::IntMap;
+    ///
+    /// let m1: IntMap<_> = [(1, "a"), (2, "b")].into_iter().collect();
+    /// let m2: IntMap<_> = [(2, "x"), (3, "y")].into_iter().collect();
+    /// let union = m1.union(&m2);
+    ///
+    /// assert!(union.iter().eq([(&1, &"a"), (&2, &"b"), (&3, &"y")]));
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        match (&self.root, &other.root) {
+            (_, None) => self.clone(),
+            (None, _) => other.clone(),
+            (Some(a), Some(b)) => IntMap {
+                root: Some(union(a, b)),
+            },
+        }
+    }
+
+    /// Returns a map with the entries of `self` whose keys are also in
+    /// `other`.
+    ///
+    /// Complexity: O(n + m), but subtries only one of the maps has keys
+    /// in are skipped without being visited.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::IntMap;
+    ///
+    /// let m1: IntMap<_> = [(1, "a"), (2, "b")].into_iter().collect();
+    /// let m2: IntMap<_> = [(2, "x"), (3, "y")].into_iter().collect();
+    ///
+    /// assert!(m1.intersection(&m2).iter().eq([(&2, &"b")]));
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        match (&self.root, &other.root) {
+            (Some(a), Some(b)) => IntMap {
+                root: intersection(a, b),
+            },
+            _ => IntMap::new(),
+        }
+    }
+}
+
+/// Returns the bits of `key` above `mask`.
+fn masked(key: u64, mask: u64) -> u64 {
+    key & !(mask | (mask - 1))
+}
+
+/// Returns `true` if `key` belongs in a branch with the given prefix.
+fn matches(key: u64, prefix: u64, mask: u64) -> bool {
+    masked(key, mask) == prefix
+}
+
+/// Returns `true` if `key` belongs on the left of a branch with `mask`.
+fn is_left(key: u64, mask: u64) -> bool {
+    key & mask == 0
+}
+
+/// Returns the highest bit in which `a` and `b` differ.
+fn branching_bit(a: u64, b: u64) -> u64 {
+    1 << (63 - (a ^ b).leading_zeros())
+}
+
+fn len<L: IntLink>(link: &L) -> usize {
+    match &link.node().0 {
+        Kind::Leaf { .. } => 1,
+        Kind::Branch { len, .. } => *len,
+    }
+}
+
+/// Returns a key or prefix shared by all keys of the subtrie.
+fn prefix<L: IntLink>(link: &L) -> u64 {
+    match &link.node().0 {
+        Kind::Leaf { key, .. } => *key,
+        Kind::Branch { prefix, .. } => *prefix,
+    }
+}
+
+fn branch<L: IntLink>(prefix: u64, mask: u64, left: L, right: L) -> L {
+    L::new(IntNode(Kind::Branch {
+        prefix,
+        mask,
+        len: len(&left) + len(&right),
+        left,
+        right,
+    }))
+}
+
+/// Like `branch`, but either side may be empty.
+fn branch_or<L: IntLink>(prefix: u64, mask: u64, left: Option<L>, right: Option<L>) -> Option<L> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(branch(prefix, mask, left, right)),
+        (left, right) => left.or(right),
+    }
+}
+
+/// Joins two subtries whose prefixes disagree.
+fn joined<L: IntLink>(a: &L, b: &L) -> L {
+    let (pa, pb) = (prefix(a), prefix(b));
+    let mask = branching_bit(pa, pb);
+    if is_left(pa, mask) {
+        branch(masked(pa, mask), mask, a.clone(), b.clone())
+    } else {
+        branch(masked(pa, mask), mask, b.clone(), a.clone())
+    }
+}
+
+/// Finds the leaf with `key`.
+fn leaf<L: IntLink>(mut link: &L, key: u64) -> Option<&L> {
+    loop {
+        match &link.node().0 {
+            Kind::Leaf { key: k, .. } => return if *k == key { Some(link) } else { None },
+            Kind::Branch {
+                prefix,
+                mask,
+                left,
+                right,
+                ..
+            } => {
+                if !matches(key, *prefix, *mask) {
+                    return None;
+                }
+                link = if is_left(key, *mask) { left } else { right };
+            }
+        }
+    }
+}
+
+/// Returns a copy of the trie with the leaf `new`, which holds `key`,
+/// added, or `None` if nothing changes.
+fn inserted_leaf<L: IntLink>(link: &L, key: u64, new: &L, replace: bool) -> Option<L> {
+    match &link.node().0 {
+        Kind::Leaf { key: k, .. } if *k == key => {
+            if replace {
+                Some(new.clone())
+            } else {
+                None
+            }
+        }
+        Kind::Leaf { .. } => Some(joined(new, link)),
+        Kind::Branch {
+            prefix,
+            mask,
+            left,
+            right,
+            ..
+        } => {
+            if !matches(key, *prefix, *mask) {
+                Some(joined(new, link))
+            } else if is_left(key, *mask) {
+                let left = inserted_leaf(left, key, new, replace)?;
+                Some(branch(*prefix, *mask, left, right.clone()))
+            } else {
+                let right = inserted_leaf(right, key, new, replace)?;
+                Some(branch(*prefix, *mask, left.clone(), right))
+            }
+        }
+    }
+}
+
+enum Removed<L> {
+    Unchanged,
+    /// The node was the leaf being removed.
+    Gone,
+    Node(L),
+}
+
+fn removed_node<L: IntLink>(link: &L, key: u64) -> Removed<L> {
+    match &link.node().0 {
+        Kind::Leaf { key: k, .. } => {
+            if *k == key {
+                Removed::Gone
+            } else {
+                Removed::Unchanged
+            }
+        }
+        Kind::Branch {
+            prefix,
+            mask,
+            left,
+            right,
+            ..
+        } => {
+            if !matches(key, *prefix, *mask) {
+                return Removed::Unchanged;
+            }
+            // A branch left with one side is replaced by that side.
+            if is_left(key, *mask) {
+                match removed_node(left, key) {
+                    Removed::Unchanged => Removed::Unchanged,
+                    Removed::Gone => Removed::Node(right.clone()),
+                    Removed::Node(left) => {
+                        Removed::Node(branch(*prefix, *mask, left, right.clone()))
+                    }
+                }
+            } else {
+                match removed_node(right, key) {
+                    Removed::Unchanged => Removed::Unchanged,
+                    Removed::Gone => Removed::Node(left.clone()),
+                    Removed::Node(right) => {
+                        Removed::Node(branch(*prefix, *mask, left.clone(), right))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Merges two tries, keeping the leaves of `a` where both have a key.
+fn union<L: IntLink>(a: &L, b: &L) -> L {
+    if a.ptr_eq(b) {
+        return a.clone();
+    }
+    match (&a.node().0, &b.node().0) {
+        (Kind::Leaf { key, .. }, _) => inserted_leaf(b, *key, a, true).expect("replacing changes"),
+        (_, Kind::Leaf { key, .. }) => {
+            inserted_leaf(a, *key, b, false).unwrap_or_else(|| a.clone())
+        }
+        (
+            Kind::Branch {
+                prefix: p1,
+                mask: m1,
+                left: l1,
+                right: r1,
+                ..
+            },
+            Kind::Branch {
+                prefix: p2,
+                mask: m2,
+                left: l2,
+                right: r2,
+                ..
+            },
+        ) => {
+            if m1 > m2 && matches(*p2, *p1, *m1) {
+                // `b` fits below one side of `a`.
+                if is_left(*p2, *m1) {
+                    branch(*p1, *m1, union(l1, b), r1.clone())
+                } else {
+                    branch(*p1, *m1, l1.clone(), union(r1, b))
+                }
+            } else if m1 < m2 && matches(*p1, *p2, *m2) {
+                if is_left(*p1, *m2) {
+                    branch(*p2, *m2, union(a, l2), r2.clone())
+                } else {
+                    branch(*p2, *m2, l2.clone(), union(a, r2))
+                }
+            } else if m1 == m2 && p1 == p2 {
+                branch(*p1, *m1, union(l1, l2), union(r1, r2))
+            } else {
+                joined(a, b)
+            }
+        }
+    }
+}
+
+/// Returns the leaves of `a` whose keys are also in `b`.
+fn intersection<L: IntLink>(a: &L, b: &L) -> Option<L> {
+    if a.ptr_eq(b) {
+        return Some(a.clone());
+    }
+    match (&a.node().0, &b.node().0) {
+        (Kind::Leaf { key, .. }, _) => leaf(b, *key).map(|_| a.clone()),
+        (_, Kind::Leaf { key, .. }) => leaf(a, *key).map(L::clone),
+        (
+            Kind::Branch {
+                prefix: p1,
+                mask: m1,
+                left: l1,
+                right: r1,
+                ..
+            },
+            Kind::Branch {
+                prefix: p2,
+                mask: m2,
+                left: l2,
+                right: r2,
+                ..
+            },
+        ) => {
+            if m1 > m2 && matches(*p2, *p1, *m1) {
+                intersection(if is_left(*p2, *m1) { l1 } else { r1 }, b)
+            } else if m1 < m2 && matches(*p1, *p2, *m2) {
+                intersection(a, if is_left(*p1, *m2) { l2 } else { r2 })
+            } else if m1 == m2 && p1 == p2 {
+                branch_or(*p1, *m1, intersection(l1, l2), intersection(r1, r2))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+pub struct Iter<'a, L> {
+    stack: Vec<&'a L>,
+    remaining: usize,
+}
+
+impl<'a, L: IntLink> Iterator for Iter<'a, L> {
+    type Item = (&'a u64, &'a L::Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match &self.stack.pop()?.node().0 {
+                Kind::Leaf { key, value } => {
+                    self.remaining -= 1;
+                    return Some((key, value));
+                }
+                Kind::Branch { left, right, .. } => {
+                    self.stack.push(right);
+                    self.stack.push(left);
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, L: IntLink> ExactSizeIterator for Iter<'a, L> {}
+
+impl<'a, L: IntLink> IntoIterator for &'a IntMap<L> {
+    type Item = (&'a u64, &'a L::Value);
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<L: IntLink> FromIterator<(u64, L::Value)> for IntMap<L> {
+    /// Later entries replace earlier ones with the same key.
+    fn from_iter<I: IntoIterator<Item = (u64, L::Value)>>(iter: I) -> Self {
+        iter.into_iter().fold(IntMap::new(), |map, (key, value)| {
+            map.inserted_or_replaced(key, value)
+        })
+    }
+}
+
+impl<L> PartialEq for IntMap<L>
+where
+    L: IntLink,
+    L::Value: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || (self.len() == other.len() && self.iter().eq(other.iter()))
+    }
+}
+
+impl<L> Debug for IntMap<L>
+where
+    L: IntLink,
+    L::Value: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
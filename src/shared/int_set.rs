@@ -0,0 +1,163 @@
+use core::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+use super::int_map::{self, IntLink, IntMap};
+
+/// A persistent set of `u64`: an `IntMap` whose values are `()`, with
+/// the same sharing between versions and the same ordered iteration.
+pub struct IntSet<L>(IntMap<L>);
+
+impl<L> Default for IntSet<L> {
+    fn default() -> Self {
+        IntSet(IntMap::default())
+    }
+}
+
+impl<L: IntLink> Clone for IntSet<L> {
+    fn clone(&self) -> Self {
+        IntSet(self.0.clone())
+    }
+}
+
+impl<L: IntLink<Value = ()>> IntSet<L> {
+    /// Creates an empty `IntSet`.
+    pub fn new() -> Self {
+        IntSet::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of elements.
+    ///
+    /// Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if both sets share the same root node, or are both
+    /// empty.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+
+    /// Iterates over the elements in ascending order.
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter(self.0.iter())
+    }
+
+    /// Complexity: O(min(n, 64))
+    pub fn contains(&self, x: &u64) -> bool {
+        self.0.contains_key(x)
+    }
+
+    /// Returns the set with `x` added, or `self` if it already contains
+    /// `x`.
+    ///
+    /// Complexity: O(min(n, 64))
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::IntSet;
+    ///
+    /// let s1 = IntSet::new().inserted(1).inserted(2);
+    /// let s2 = s1.inserted(3);
+    ///
+    /// assert!(s2.contains(&3));
+    /// assert!(!s1.contains(&3));
+    /// assert!(s2.inserted(1).ptr_eq(&s2));
+    /// ```
+    pub fn inserted(&self, x: u64) -> Self {
+        IntSet(self.0.inserted(x, ()))
+    }
+
+    /// Returns the set without `x`, or `self` if it doesn't contain it.
+    ///
+    /// Complexity: O(min(n, 64))
+    pub fn removed(&self, x: &u64) -> Self {
+        IntSet(self.0.removed(x))
+    }
+
+    /// Returns a set with the elements of both sets.
+    ///
+    /// Complexity: O(n + m), but subtries only one of the sets has
+    /// elements in are shared without being visited.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::IntSet;
+    ///
+    /// let s1: IntSet = [1, 2, 5].into_iter().collect();
+    /// let s2: IntSet = [2, 3, 4].into_iter().collect();
+    ///
+    /// assert!(s1.union(&s2).iter().copied().eq(1..=5));
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        IntSet(self.0.union(&other.0))
+    }
+
+    /// Returns a set with the elements that are in both sets.
+    ///
+    /// Complexity: O(n + m), but subtries only one of the sets has
+    /// elements in are skipped without being visited.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::IntSet;
+    ///
+    /// let s1: IntSet = [1, 2, 5].into_iter().collect();
+    /// let s2: IntSet = [2, 3, 5].into_iter().collect();
+    ///
+    /// assert!(s1.intersection(&s2).iter().copied().eq([2, 5]));
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        IntSet(self.0.intersection(&other.0))
+    }
+}
+
+pub struct Iter<'a, L>(int_map::Iter<'a, L>);
+
+impl<'a, L: IntLink> Iterator for Iter<'a, L> {
+    type Item = &'a u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, L: IntLink> ExactSizeIterator for Iter<'a, L> {}
+
+impl<'a, L: IntLink<Value = ()>> IntoIterator for &'a IntSet<L> {
+    type Item = &'a u64;
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<L: IntLink<Value = ()>> FromIterator<u64> for IntSet<L> {
+    fn from_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(IntSet::new(), |set, x| set.inserted(x))
+    }
+}
+
+impl<L: IntLink<Value = ()>> PartialEq for IntSet<L> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<L: IntLink<Value = ()>> Debug for IntSet<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
@@ -43,7 +43,7 @@ where
     K: PartialOrd,
 {
     fn partial_cmp(&self, other: &K) -> Option<Ordering> {
-        self.0.partial_cmp(&other)
+        self.0.partial_cmp(other)
     }
 }
 
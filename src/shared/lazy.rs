@@ -0,0 +1,37 @@
+/// A memoised suspension: a value that is computed on first use and
+/// then kept, so it is computed at most once however many versions of
+/// a structure share it.
+///
+/// The flavours implement this over their once-cells, `unsync` over
+/// `once_cell::unsync::OnceCell` and `sync` over `spin::Once`.
+pub trait Lazy: Sized {
+    type Value;
+
+    /// Creates a suspension that is already evaluated.
+    fn evaluated(value: Self::Value) -> Self;
+    /// Returns the value, computing it first if needed.
+    fn force(&self) -> &Self::Value;
+    /// Returns the value if it has been computed.
+    fn get(&self) -> Option<&Self::Value>;
+    fn get_mut(&mut self) -> Option<&mut Self::Value>;
+}
+
+/// A `Lazy` that can suspend a computation over captured values of
+/// type `X`.
+///
+/// The computation is a plain function of what it captures, so that
+/// each flavour can state what may be captured: anything `'static` for
+/// `unsync`, and also `Send` for `sync`.
+pub trait Suspend<X>: Lazy {
+    /// Creates a suspension that computes `body(captured)` when forced.
+    fn suspended(captured: X, body: fn(X) -> Self::Value) -> Self;
+}
+
+/// A `Suspend` whose captured values can be taken back out while it has
+/// not been forced, so that a structure can drop a long chain of
+/// suspensions one at a time.
+pub trait Reclaim<X>: Suspend<X> {
+    /// Takes the captured values out of a suspension that is not forced
+    /// and not shared, which must not be forced afterwards.
+    fn reclaim(&mut self) -> Option<X>;
+}
@@ -0,0 +1,382 @@
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+use super::link::BinaryLink;
+
+/// A persistent min-heap.
+///
+/// This is a leftist heap: a binary tree in heap order whose right spine
+/// is never longer than the left one, in every subtree. Merging walks
+/// down the right spines of both heaps, which have O(log n) nodes, and
+/// copies only those; pushing and popping are merges too. All other
+/// nodes are shared with the heaps the result was made from.
+///
+/// Elements that compare equal are popped in no particular order.
+pub struct LeftistHeap<B> {
+    root: Option<B>,
+    len: usize,
+}
+
+/// A heap element together with the length of the right spine of its
+/// subtree.
+pub struct Ranked<T> {
+    rank: usize,
+    element: T,
+}
+
+impl<B> Default for LeftistHeap<B> {
+    fn default() -> Self {
+        LeftistHeap { root: None, len: 0 }
+    }
+}
+
+impl<B: BinaryLink> Clone for LeftistHeap<B> {
+    fn clone(&self) -> Self {
+        LeftistHeap {
+            root: self.root.as_ref().map(B::clone),
+            len: self.len,
+        }
+    }
+}
+
+impl<T, B> LeftistHeap<B>
+where
+    B: BinaryLink<ValueType = Ranked<T>>,
+{
+    /// Creates an empty `LeftistHeap`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::LeftistHeap;
+    ///
+    /// let heap: LeftistHeap<u32> = LeftistHeap::new();
+    ///
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        LeftistHeap::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the number of elements.
+    ///
+    /// Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Provides a reference to the smallest element, or `None` if the
+    /// heap is empty.
+    ///
+    /// Complexity: O(1)
+    pub fn peek_min(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.get_element().element)
+    }
+
+    /// Returns `true` if both heaps share the same root node, or are both
+    /// empty.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Iterates over the elements in heap order: every element comes
+    /// before the elements below it, but the order is not sorted.
+    pub fn iter(&self) -> Iter<'_, B> {
+        Iter {
+            stack: self.root.iter().collect(),
+        }
+    }
+}
+
+impl<T, B> LeftistHeap<B>
+where
+    B: BinaryLink<ValueType = Ranked<T>>,
+    T: Ord,
+{
+    /// Iterates over the elements from the smallest to the largest,
+    /// without changing the heap.
+    ///
+    /// Complexity: O(log n) per element
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::LeftistHeap;
+    ///
+    /// let heap: LeftistHeap<_> = [5, 1, 4, 2, 3].into_iter().collect();
+    ///
+    /// assert!(heap.iter_sorted().copied().eq(1..=5));
+    /// ```
+    pub fn iter_sorted(&self) -> IterSorted<'_, B> {
+        IterSorted {
+            frontier: self.root.iter().map(Smallest).collect(),
+        }
+    }
+}
+
+impl<T, B> LeftistHeap<B>
+where
+    B: BinaryLink<ValueType = Ranked<T>>,
+    T: Ord + Clone,
+{
+    /// Returns a heap with `value` added.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::LeftistHeap;
+    ///
+    /// let h1 = LeftistHeap::new().pushed(3).pushed(1);
+    /// let h2 = h1.pushed(0);
+    ///
+    /// assert_eq!(h2.peek_min(), Some(&0));
+    /// assert_eq!(h1.peek_min(), Some(&1));
+    /// ```
+    pub fn pushed(&self, value: T) -> Self {
+        let single = B::node(
+            Ranked {
+                rank: 1,
+                element: value,
+            },
+            None,
+            None,
+        );
+        LeftistHeap {
+            root: Some(merged(self.root.as_ref(), Some(&single))),
+            len: self.len + 1,
+        }
+    }
+
+    /// Returns the heap without its smallest element.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty heap.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::LeftistHeap;
+    ///
+    /// let h1 = LeftistHeap::new().pushed(2).pushed(1);
+    /// let h2 = h1.popped_min();
+    ///
+    /// assert_eq!(h2.peek_min(), Some(&2));
+    /// assert_eq!(h1.len(), 2);
+    /// ```
+    pub fn popped_min(&self) -> Self {
+        let root = match &self.root {
+            None => panic!("You can't pop an empty heap!"),
+            Some(root) => root,
+        };
+        let left = root.left_ref();
+        let right = root.right_ref();
+        LeftistHeap {
+            root: match (left, right) {
+                (None, None) => None,
+                _ => Some(merged(left, right)),
+            },
+            len: self.len - 1,
+        }
+    }
+
+    /// Returns a heap with the elements of both `self` and `other`.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::LeftistHeap;
+    ///
+    /// let h1: LeftistHeap<_> = [4, 2, 6].into_iter().collect();
+    /// let h2: LeftistHeap<_> = [5, 1, 3].into_iter().collect();
+    /// let merged = h1.merged(&h2);
+    ///
+    /// assert_eq!(merged.len(), 6);
+    /// assert!(merged.iter_sorted().copied().eq(1..=6));
+    /// ```
+    pub fn merged(&self, other: &Self) -> Self {
+        match (&self.root, &other.root) {
+            (_, None) => self.clone(),
+            (None, _) => other.clone(),
+            (Some(a), Some(b)) => LeftistHeap {
+                root: Some(merged(Some(a), Some(b))),
+                len: self.len + other.len,
+            },
+        }
+    }
+}
+
+fn rank<T, B: BinaryLink<ValueType = Ranked<T>>>(node: Option<&B>) -> usize {
+    node.map_or(0, |node| node.get_element().rank)
+}
+
+/// Merges two heaps, at least one of which is not empty, copying the
+/// nodes on their right spines.
+fn merged<T, B>(a: Option<&B>, b: Option<&B>) -> B
+where
+    B: BinaryLink<ValueType = Ranked<T>>,
+    T: Ord + Clone,
+{
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        (Some(single), None) | (None, Some(single)) => return B::clone(single),
+        (None, None) => unreachable!("only non-empty heaps are merged"),
+    };
+    let (top, other) = if a.get_element().element <= b.get_element().element {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let left = top.left_ref().map(B::clone);
+    let right = Some(merged(top.right_ref(), Some(other)));
+    // Keep the shorter right spine on the right.
+    let (left, right) = if rank(left.as_ref()) >= rank(right.as_ref()) {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    B::node(
+        Ranked {
+            rank: rank(right.as_ref()) + 1,
+            element: top.get_element().element.clone(),
+        },
+        left,
+        right,
+    )
+}
+
+pub struct Iter<'a, B> {
+    stack: Vec<&'a B>,
+}
+
+impl<'a, T: 'a, B> Iterator for Iter<'a, B>
+where
+    B: BinaryLink<ValueType = Ranked<T>>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.right_ref());
+        self.stack.extend(node.left_ref());
+        Some(&node.get_element().element)
+    }
+}
+
+impl<'a, T: 'a, B> IntoIterator for &'a LeftistHeap<B>
+where
+    B: BinaryLink<ValueType = Ranked<T>>,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The nodes not visited yet whose parents have been, ordered so that
+/// the node with the smallest element is popped first.
+pub struct IterSorted<'a, B> {
+    frontier: BinaryHeap<Smallest<'a, B>>,
+}
+
+struct Smallest<'a, B>(&'a B);
+
+impl<'a, T: Ord, B: BinaryLink<ValueType = Ranked<T>>> Ord for Smallest<'a, B> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let a = &self.0.get_element().element;
+        let b = &other.0.get_element().element;
+        b.cmp(a)
+    }
+}
+
+impl<'a, T: Ord, B: BinaryLink<ValueType = Ranked<T>>> PartialOrd for Smallest<'a, B> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, T: Ord, B: BinaryLink<ValueType = Ranked<T>>> PartialEq for Smallest<'a, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a, T: Ord, B: BinaryLink<ValueType = Ranked<T>>> Eq for Smallest<'a, B> {}
+
+impl<'a, T: Ord + 'a, B> Iterator for IterSorted<'a, B>
+where
+    B: BinaryLink<ValueType = Ranked<T>>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Smallest(node) = self.frontier.pop()?;
+        self.frontier.extend(node.left_ref().map(Smallest));
+        self.frontier.extend(node.right_ref().map(Smallest));
+        Some(&node.get_element().element)
+    }
+}
+
+impl<T, B> FromIterator<T> for LeftistHeap<B>
+where
+    B: BinaryLink<ValueType = Ranked<T>>,
+    T: Ord + Clone,
+{
+    /// Merges the elements pairwise, round by round, which takes O(n)
+    /// steps instead of the O(n log n) of pushing them one by one.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heaps: Vec<B> = iter
+            .into_iter()
+            .map(|element| B::node(Ranked { rank: 1, element }, None, None))
+            .collect();
+        let len = heaps.len();
+        while heaps.len() > 1 {
+            heaps = heaps
+                .chunks(2)
+                .map(|pair| merged(pair.first(), pair.get(1)))
+                .collect();
+        }
+        LeftistHeap {
+            root: heaps.pop(),
+            len,
+        }
+    }
+}
+
+impl<T, B> PartialEq for LeftistHeap<B>
+where
+    B: BinaryLink<ValueType = Ranked<T>>,
+    T: Ord,
+{
+    /// Heaps are equal if they hold the same elements, however they are
+    /// arranged.
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && (self.ptr_eq(other) || self.iter_sorted().eq(other.iter_sorted()))
+    }
+}
+
+impl<T, B> Debug for LeftistHeap<B>
+where
+    B: BinaryLink<ValueType = Ranked<T>>,
+    T: Ord + Debug,
+{
+    /// Lists the elements in sorted order.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter_sorted()).finish()
+    }
+}
@@ -9,6 +9,28 @@ pub trait Link: Sized {
     fn next_cloned(&self) -> Option<Self>;
     fn next_ref(&self) -> Option<&Self>;
     fn link_ref(&self) -> &Self;
+    /// Returns `true` if both links point to the same node.
+    fn ptr_eq(&self, other: &Self) -> bool;
+    /// Returns the address of the node, which identifies it for as long
+    /// as the link is alive.
+    fn as_ptr(&self) -> *const ();
+}
+
+pub trait BinaryLink: Sized {
+    type ValueType;
+
+    fn node(element: Self::ValueType, left: Option<Self>, right: Option<Self>) -> Self;
+    fn clone(&self) -> Self;
+    fn get_element(&self) -> &Self::ValueType;
+    fn left_ref(&self) -> Option<&Self>;
+    fn right_ref(&self) -> Option<&Self>;
+    /// Returns `true` if both links point to the same node.
+    fn ptr_eq(&self, other: &Self) -> bool;
+    /// Returns the address of the node, which identifies it for as long
+    /// as the link is alive.
+    fn as_ptr(&self) -> *const ();
+    /// Takes the subtrees out of the node if this is its only link.
+    fn try_unwrap_children(self) -> Option<(Option<Self>, Option<Self>)>;
 }
 
 // impl Debug for Link
@@ -1,10 +1,12 @@
-use core::{borrow::Borrow, fmt::Debug};
-
-use itertools::{EitherOrBoth, Itertools};
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
+};
 
 use super::link::Link;
 
-#[derive(Debug)]
 pub struct List<L> {
     head: Option<L>,
 }
@@ -160,17 +162,305 @@ impl<L: Link> List<L> {
     //         reverse(self)
     //     }
 
+    /// Returns `true` if both lists share the same head node, or are
+    /// both empty.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::List;
+    ///
+    /// let l1 = List::cons(1, List::new());
+    /// let l2 = l1.clone();
+    /// let l3 = List::cons(1, List::new());
+    ///
+    /// assert!(l1.ptr_eq(&l2));
+    /// assert!(!l1.ptr_eq(&l3));
+    /// assert_eq!(l1, l3);
+    /// ```
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            _ => false,
+        }
+    }
+
+    /// Returns the longest tail that `a` and `b` physically share.
+    ///
+    /// Complexity: O(n + m)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::List;
+    ///
+    /// let tail = List::cons(2, List::cons(1, List::new()));
+    /// let a = tail.pushed_front(3);
+    /// let b = tail.pushed_front(4).pushed_front(5);
+    ///
+    /// assert!(List::common_suffix(&a, &b).ptr_eq(&tail));
+    /// ```
+    pub fn common_suffix(a: &Self, b: &Self) -> Self {
+        let len_a = a.iter().count();
+        let len_b = b.iter().count();
+        let mut a = a.head.as_ref();
+        let mut b = b.head.as_ref();
+        for _ in len_b..len_a {
+            a = a.and_then(Link::next_ref);
+        }
+        for _ in len_a..len_b {
+            b = b.and_then(Link::next_ref);
+        }
+        loop {
+            match (a, b) {
+                (Some(x), Some(y)) if x.ptr_eq(y) => {
+                    return List {
+                        head: Some(x.clone()),
+                    }
+                }
+                (Some(x), Some(y)) => {
+                    a = x.next_ref();
+                    b = y.next_ref();
+                }
+                _ => return List::new(),
+            }
+        }
+    }
+
     pub fn iter(&self) -> Iter<'_, L> {
         Iter {
             next: self.head.as_ref().map(Link::link_ref),
             // next: self.head.as_ref().map(|node| &**node),
         }
     }
+
+    /// Rebuilds the list on another kind of link, converting each
+    /// element with `f`.
+    ///
+    /// `memo` maps the nodes of lists already converted to their copies,
+    /// so suffixes shared between lists converted with the same memo are
+    /// shared between the copies too. The elements are converted back to
+    /// front.
+    pub(crate) fn converted<M, F>(&self, memo: &mut BTreeMap<*const (), M>, mut f: F) -> List<M>
+    where
+        M: Link,
+        F: FnMut(&L::ValueType) -> M::ValueType,
+    {
+        let mut pending = Vec::new();
+        let mut next = self.head.as_ref();
+        let mut tail = None;
+        while let Some(link) = next {
+            if let Some(copy) = memo.get(&link.as_ptr()) {
+                tail = Some(copy.clone());
+                break;
+            }
+            pending.push(link);
+            next = link.next_ref();
+        }
+        for link in pending.into_iter().rev() {
+            let copy = M::cons(f(link.get_element()), tail);
+            memo.insert(link.as_ptr(), copy.clone());
+            tail = Some(copy);
+        }
+        List { head: tail }
+    }
+}
+
+impl<L> List<L>
+where
+    L: Link,
+    L::ValueType: Clone,
+{
+    /// Returns the list sorted in ascending order.
+    ///
+    /// This is a stable bottom-up merge sort. The longest already sorted
+    /// tail of the list is shared with the result instead of copied.
+    ///
+    /// Complexity: O(n log n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::List;
+    ///
+    /// let list = List::new().pushed_front(2).pushed_front(3).pushed_front(1);
+    /// let sorted = list.sorted();
+    ///
+    /// assert!(sorted.iter().copied().eq([1, 2, 3]));
+    /// ```
+    pub fn sorted(&self) -> Self
+    where
+        L::ValueType: Ord,
+    {
+        self.sorted_by(Ord::cmp)
+    }
+
+    /// Returns the list sorted with the comparator function `cmp`.
+    ///
+    /// See [`List::sorted`].
+    pub fn sorted_by<F>(&self, cmp: F) -> Self
+    where
+        F: FnMut(&L::ValueType, &L::ValueType) -> Ordering,
+    {
+        let mut cmp = cmp;
+        // split off the last ascending run, it is kept as is
+        let mut prefix = Vec::new();
+        let mut run_start = 0;
+        let mut suffix = self.head.as_ref();
+        let mut next = self.head.as_ref();
+        while let Some(node) = next {
+            if let Some(&last) = prefix.last() {
+                if cmp(last, node.get_element()) == Ordering::Greater {
+                    run_start = prefix.len();
+                    suffix = Some(node);
+                }
+            }
+            prefix.push(node.get_element());
+            next = node.next_ref();
+        }
+        let suffix = List {
+            head: suffix.map(Link::clone),
+        };
+        prefix.truncate(run_start);
+
+        // copy the remaining ascending runs into lists and merge them
+        // pairwise until one is left
+        let mut runs = Vec::new();
+        let mut start = 0;
+        for i in 1..=prefix.len() {
+            if i == prefix.len() || cmp(prefix[i - 1], prefix[i]) == Ordering::Greater {
+                runs.push(List::from_elements(&prefix[start..i]));
+                start = i;
+            }
+        }
+        while runs.len() > 1 {
+            let mut merged = Vec::with_capacity((runs.len() + 1) / 2);
+            for pair in runs.chunks(2) {
+                merged.push(match pair {
+                    [a, b] => List::merge_sorted_by(a, b, &mut cmp),
+                    [a] => a.clone(),
+                    _ => unreachable!(),
+                });
+            }
+            runs = merged;
+        }
+        match runs.pop() {
+            None => suffix,
+            Some(sorted) => List::merge_sorted_by(&sorted, &suffix, &mut cmp),
+        }
+    }
+
+    /// Returns the list sorted by the key extracted with `f`.
+    ///
+    /// See [`List::sorted`].
+    pub fn sorted_by_key<K, F>(&self, f: F) -> Self
+    where
+        K: Ord,
+        F: FnMut(&L::ValueType) -> K,
+    {
+        let mut f = f;
+        self.sorted_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Merges two sorted lists into one sorted list.
+    ///
+    /// Elements of `a` come before equal elements of `b`. Once one of the
+    /// lists is exhausted, the rest of the other is shared with the
+    /// result.
+    ///
+    /// Complexity: O(n + m)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::List;
+    ///
+    /// let tail = List::new().pushed_front(9).pushed_front(8);
+    /// let a = tail.pushed_front(5).pushed_front(1);
+    /// let b = List::new().pushed_front(4).pushed_front(2);
+    /// let merged = List::merge_sorted(&a, &b);
+    ///
+    /// assert!(merged.iter().copied().eq([1, 2, 4, 5, 8, 9]));
+    /// assert!(List::common_suffix(&merged, &a).ptr_eq(&a.popped_front()));
+    /// ```
+    pub fn merge_sorted(a: &Self, b: &Self) -> Self
+    where
+        L::ValueType: Ord,
+    {
+        List::merge_sorted_by(a, b, &mut Ord::cmp)
+    }
+
+    fn merge_sorted_by<F>(a: &Self, b: &Self, cmp: &mut F) -> Self
+    where
+        F: FnMut(&L::ValueType, &L::ValueType) -> Ordering,
+    {
+        let mut prefix = Vec::new();
+        let mut a = a.head.as_ref();
+        let mut b = b.head.as_ref();
+        let tail = loop {
+            match (a, b) {
+                (Some(x), Some(y)) => {
+                    if cmp(y.get_element(), x.get_element()) == Ordering::Less {
+                        prefix.push(y.get_element());
+                        b = y.next_ref();
+                    } else {
+                        prefix.push(x.get_element());
+                        a = x.next_ref();
+                    }
+                }
+                (rest, None) | (None, rest) => break rest,
+            }
+        };
+        List::from_elements_onto(&prefix, tail.map(Link::clone))
+    }
+
+    /// Inserts `value` into a sorted list, after any equal elements.
+    ///
+    /// The elements after `value` are shared with `self`.
+    ///
+    /// Complexity: O(n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::List;
+    ///
+    /// let list = List::new().pushed_front(3).pushed_front(1);
+    ///
+    /// assert!(list.insert_sorted(2).iter().copied().eq([1, 2, 3]));
+    /// ```
+    pub fn insert_sorted(&self, value: L::ValueType) -> Self
+    where
+        L::ValueType: Ord,
+    {
+        let mut prefix = Vec::new();
+        let mut next = self.head.as_ref();
+        while let Some(node) = next {
+            if node.get_element() > &value {
+                break;
+            }
+            prefix.push(node.get_element());
+            next = node.next_ref();
+        }
+        let tail = Some(L::cons(value, next.map(Link::clone)));
+        List::from_elements_onto(&prefix, tail)
+    }
+
+    fn from_elements(elements: &[&L::ValueType]) -> Self {
+        List::from_elements_onto(elements, None)
+    }
+
+    /// Copies `elements`, in order, in front of `tail`.
+    fn from_elements_onto(elements: &[&L::ValueType], tail: Option<L>) -> Self {
+        let mut list = List { head: tail };
+        for x in elements.iter().rev() {
+            list = list.pushed_front((*x).clone());
+        }
+        list
+    }
 }
 
 impl<L> AsRef<List<L>> for List<L> {
     fn as_ref(&self) -> &List<L> {
-        &self
+        self
     }
 }
 
@@ -204,9 +494,98 @@ where
     L::ValueType: PartialEq, // T: PartialEq + Clone,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.iter()
-            .zip_longest(other.iter())
-            .all(|x| matches!(x, EitherOrBoth::Both(a, b) if a == b))
+        let mut a = self.head.as_ref();
+        let mut b = other.head.as_ref();
+        loop {
+            match (a, b) {
+                (None, None) => return true,
+                (Some(x), Some(y)) => {
+                    if x.ptr_eq(y) {
+                        // the rest of the list is shared
+                        return true;
+                    }
+                    if x.get_element() != y.get_element() {
+                        return false;
+                    }
+                    a = x.next_ref();
+                    b = y.next_ref();
+                }
+                _ => return false,
+            }
+        }
+    }
+}
+
+impl<L> Eq for List<L>
+where
+    L: Link,
+    L::ValueType: Eq,
+{
+}
+
+impl<L> PartialOrd for List<L>
+where
+    L: Link,
+    L::ValueType: PartialOrd,
+{
+    /// Compares lists lexicographically, front to back.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<L> Ord for List<L>
+where
+    L: Link,
+    L::ValueType: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<L> Hash for List<L>
+where
+    L: Link,
+    L::ValueType: Hash,
+{
+    /// Hashes the elements followed by the length, so that nested
+    /// lists don't collide when their elements are concatenated.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut len = 0;
+        for x in self {
+            x.hash(state);
+            len += 1;
+        }
+        state.write_usize(len);
+    }
+}
+
+impl<L> Debug for List<L>
+where
+    L: Link,
+    L::ValueType: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<L> Display for List<L>
+where
+    L: Link,
+    L::ValueType: Display,
+{
+    /// Formats the list as `[1, 2, 3]`, front first.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, x) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            Display::fmt(x, f)?;
+        }
+        f.write_str("]")
     }
 }
 
@@ -235,7 +614,7 @@ where
 {
     let mut result = List::default();
     for x in list {
-        result = result.pushed_front(f(&x));
+        result = result.pushed_front(f(x));
     }
     result
 }
@@ -267,7 +646,7 @@ where
 {
     match a.front() {
         None => b.clone(),
-        Some(head) => List::cons(head.clone(), &concat(&a.popped_front(), b)),
+        Some(head) => List::cons(head.clone(), concat(&a.popped_front(), b)),
     }
 }
 
@@ -289,7 +668,7 @@ where
 
 // List Monad
 pub fn mreturn<L: Link>(t: L::ValueType) -> List<L> {
-    List::cons(t, &List::new())
+    List::cons(t, List::new())
 }
 
 pub fn mbind<L1, L2, L3>(list: &List<L1>, k: impl Fn(&L1::ValueType) -> List<L2> + Copy) -> List<L2>
@@ -0,0 +1,226 @@
+use core::fmt::{self, Debug};
+
+use super::link::Link;
+use super::list::List;
+
+/// A cursor into a persistent `List`.
+///
+/// The zipper keeps the elements before the cursor as a reversed list
+/// and the elements from the focus onwards as the original tail, so
+/// moving, inserting, replacing and removing at the cursor are all O(1).
+/// The focus may also be one past the last element, which makes it
+/// possible to insert at the end.
+pub struct ListZipper<L> {
+    left: List<L>,
+    right: List<L>,
+}
+
+impl<L> Default for ListZipper<L> {
+    fn default() -> Self {
+        ListZipper {
+            left: List::default(),
+            right: List::default(),
+        }
+    }
+}
+
+impl<L: Link> Clone for ListZipper<L> {
+    fn clone(&self) -> Self {
+        ListZipper {
+            left: self.left.clone(),
+            right: self.right.clone(),
+        }
+    }
+}
+
+impl<L: Link> ListZipper<L> {
+    /// Creates a zipper focused on the front of `list`.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{List, ListZipper};
+    ///
+    /// let list = List::new().pushed_front(2).pushed_front(1);
+    /// let zipper = ListZipper::new(&list);
+    ///
+    /// assert_eq!(zipper.focus(), Some(&1));
+    /// ```
+    pub fn new(list: &List<L>) -> Self {
+        ListZipper {
+            left: List::new(),
+            right: list.clone(),
+        }
+    }
+
+    /// Provides a reference to the element at the cursor, or `None` if
+    /// the cursor is past the last element.
+    pub fn focus(&self) -> Option<&L::ValueType> {
+        self.right.front()
+    }
+
+    /// Returns the elements before the cursor, nearest first.
+    pub fn left(&self) -> &List<L> {
+        &self.left
+    }
+
+    /// Returns the elements from the focus to the end of the list.
+    pub fn right(&self) -> &List<L> {
+        &self.right
+    }
+
+    /// Returns `true` if there are no elements before the cursor.
+    pub fn is_at_start(&self) -> bool {
+        self.left.is_empty()
+    }
+
+    /// Returns `true` if the cursor is past the last element.
+    pub fn is_at_end(&self) -> bool {
+        self.right.is_empty()
+    }
+
+    /// Returns a zipper with `value` inserted at the cursor, so that it
+    /// becomes the new focus and the old focus follows it.
+    ///
+    /// Complexity: O(1)
+    pub fn inserted(&self, value: L::ValueType) -> Self {
+        ListZipper {
+            left: self.left.clone(),
+            right: self.right.pushed_front(value),
+        }
+    }
+
+    /// Returns a zipper with the focus replaced by `value`.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Panics
+    ///
+    /// This method panics when the cursor is past the last element.
+    pub fn replaced(&self, value: L::ValueType) -> Self {
+        if self.is_at_end() {
+            panic!("There is no focus to replace!");
+        }
+        ListZipper {
+            left: self.left.clone(),
+            right: self.right.popped_front().pushed_front(value),
+        }
+    }
+
+    /// Returns a zipper with the focus removed; the element after it
+    /// becomes the new focus.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Panics
+    ///
+    /// This method panics when the cursor is past the last element.
+    pub fn removed(&self) -> Self {
+        if self.is_at_end() {
+            panic!("There is no focus to remove!");
+        }
+        ListZipper {
+            left: self.left.clone(),
+            right: self.right.popped_front(),
+        }
+    }
+}
+
+impl<L> ListZipper<L>
+where
+    L: Link,
+    L::ValueType: Clone,
+{
+    /// Returns a zipper with the cursor moved one element towards the
+    /// front, or `None` if it is already at the start.
+    ///
+    /// Complexity: O(1)
+    pub fn move_left(&self) -> Option<Self> {
+        let x = self.left.front()?;
+        Some(ListZipper {
+            left: self.left.popped_front(),
+            right: self.right.pushed_front(x.clone()),
+        })
+    }
+
+    /// Returns a zipper with the cursor moved one element towards the
+    /// end, or `None` if it is already past the last element.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{List, ListZipper};
+    ///
+    /// let list = List::new().pushed_front(2).pushed_front(1);
+    /// let zipper = ListZipper::new(&list).move_right().unwrap();
+    ///
+    /// assert_eq!(zipper.focus(), Some(&2));
+    /// assert!(zipper.move_right().unwrap().move_right().is_none());
+    /// ```
+    pub fn move_right(&self) -> Option<Self> {
+        let x = self.right.front()?;
+        Some(ListZipper {
+            left: self.left.pushed_front(x.clone()),
+            right: self.right.popped_front(),
+        })
+    }
+
+    /// Rebuilds the list.
+    ///
+    /// The elements from the focus onwards are shared, only the elements
+    /// before the cursor are copied.
+    ///
+    /// Complexity: O(k), where k is the number of elements before the
+    /// cursor
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{List, ListZipper};
+    ///
+    /// let list = List::new().pushed_front(3).pushed_front(1);
+    /// let zipper = ListZipper::new(&list).move_right().unwrap().inserted(2);
+    ///
+    /// assert!(zipper.to_list().iter().copied().eq([1, 2, 3]));
+    /// ```
+    pub fn to_list(&self) -> List<L> {
+        let mut list = self.right.clone();
+        for x in &self.left {
+            list = list.pushed_front(x.clone());
+        }
+        list
+    }
+}
+
+impl<L> From<&List<L>> for ListZipper<L>
+where
+    L: Link,
+{
+    fn from(list: &List<L>) -> Self {
+        ListZipper::new(list)
+    }
+}
+
+impl<L> PartialEq for ListZipper<L>
+where
+    L: Link,
+    L::ValueType: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.left == other.left && self.right == other.right
+    }
+}
+
+impl<L> Debug for ListZipper<L>
+where
+    L: Link,
+    L::ValueType: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ListZipper")
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .finish()
+    }
+}
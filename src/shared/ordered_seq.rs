@@ -0,0 +1,238 @@
+use core::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+use super::finger_tree::{self, FingerLink, FingerTree, Measured, Monoid};
+use super::seq::Size;
+
+/// A persistent sorted sequence, which may hold equal elements.
+///
+/// This is a `FingerTree` of the elements in ascending order, measured
+/// by their last element, which is the largest, and by their count.
+/// Looking up, inserting and removing an element split the tree where
+/// the elements stop being smaller than it, in O(log n).
+pub struct OrderedSeq<L: FingerLink>(FingerTree<L>);
+
+/// An `OrderedSeq` element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keyed<T>(T);
+
+/// The last element, as a measure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Last<T>(pub Option<T>);
+
+impl<T: Clone> Monoid for Last<T> {
+    fn empty() -> Self {
+        Last(None)
+    }
+
+    fn combine(&self, other: &Self) -> Self {
+        if other.0.is_some() {
+            other.clone()
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl<T: Clone> Measured for Keyed<T> {
+    type Measure = (Size, Last<T>);
+
+    fn measure(&self) -> Self::Measure {
+        (Size(1), Last(Some(self.0.clone())))
+    }
+}
+
+impl<L: FingerLink> Default for OrderedSeq<L> {
+    fn default() -> Self {
+        OrderedSeq(FingerTree::default())
+    }
+}
+
+impl<T, L> Clone for OrderedSeq<L>
+where
+    L: FingerLink<Element = Keyed<T>>,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        OrderedSeq(self.0.clone())
+    }
+}
+
+impl<T, L> OrderedSeq<L>
+where
+    L: FingerLink<Element = Keyed<T>>,
+    T: Ord + Clone,
+{
+    /// Creates an empty `OrderedSeq`.
+    pub fn new() -> Self {
+        OrderedSeq::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of elements.
+    ///
+    /// Complexity: O(1)
+    pub fn len(&self) -> usize {
+        (self.0.measure().0).0
+    }
+
+    /// Returns the smallest element.
+    pub fn first(&self) -> Option<&T> {
+        self.0.front().map(|Keyed(x)| x)
+    }
+
+    /// Returns the largest element.
+    pub fn last(&self) -> Option<&T> {
+        self.0.back().map(|Keyed(x)| x)
+    }
+
+    /// Complexity: O(log n)
+    pub fn contains(&self, x: &T) -> bool {
+        self.0
+            .find(|(_, last)| last.0.as_ref().map_or(false, |key| key >= x))
+            .map_or(false, |Keyed(found)| found == x)
+    }
+
+    /// Returns the sequence with `x` added after the elements that are
+    /// smaller or equal.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::OrderedSeq;
+    ///
+    /// let seq: OrderedSeq<_> = [5, 1, 3].into_iter().collect();
+    ///
+    /// assert!(seq.inserted(2).iter().copied().eq([1, 2, 3, 5]));
+    /// ```
+    pub fn inserted(&self, x: T) -> Self {
+        let (smaller, larger) = self.split_by(|key| key > &x);
+        OrderedSeq(smaller.pushed_back(Keyed(x)).appended(&larger))
+    }
+
+    /// Returns the sequence with one element equal to `x` removed, or
+    /// `self` if there is none.
+    ///
+    /// Complexity: O(log n)
+    pub fn removed(&self, x: &T) -> Self {
+        let (smaller, rest) = self.split_by(|key| key >= x);
+        match rest.front() {
+            Some(Keyed(found)) if found == x => OrderedSeq(smaller.appended(&rest.popped_front())),
+            _ => self.clone(),
+        }
+    }
+
+    /// Splits the sequence into the elements smaller than `x` and the
+    /// others.
+    ///
+    /// Complexity: O(log n)
+    pub fn split(&self, x: &T) -> (Self, Self) {
+        let (smaller, rest) = self.split_by(|key| key >= x);
+        (OrderedSeq(smaller), OrderedSeq(rest))
+    }
+
+    /// Returns a sequence with the elements of both `self` and `other`.
+    ///
+    /// The two are interleaved run by run: every step splits off the
+    /// elements of one sequence that come before the front of the other.
+    ///
+    /// Complexity: O(k log n) for k runs
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::OrderedSeq;
+    ///
+    /// let odds: OrderedSeq<_> = (1..10).step_by(2).collect();
+    /// let evens: OrderedSeq<_> = (0..10).step_by(2).collect();
+    ///
+    /// assert!(odds.merged(&evens).iter().copied().eq(0..10));
+    /// ```
+    pub fn merged(&self, other: &Self) -> Self {
+        let mut merged = FingerTree::new();
+        let mut from = self.0.clone();
+        let mut into = other.0.clone();
+        while let Some(Keyed(next)) = into.front() {
+            let (smaller, rest) =
+                from.split(|(_, last)| last.0.as_ref().map_or(false, |key| key > next));
+            merged = merged.appended(&smaller);
+            from = into;
+            into = rest;
+        }
+        OrderedSeq(merged.appended(&from))
+    }
+
+    /// Iterates over the elements in ascending order.
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter(self.0.iter())
+    }
+
+    /// Splits the tree in front of the first element for which `p` holds.
+    fn split_by<P>(&self, p: P) -> (FingerTree<L>, FingerTree<L>)
+    where
+        P: Fn(&T) -> bool,
+    {
+        self.0.split(|(_, last)| last.0.as_ref().map_or(false, &p))
+    }
+}
+
+pub struct Iter<'a, L: FingerLink>(finger_tree::Iter<'a, L>);
+
+impl<'a, T: 'a, L: FingerLink<Element = Keyed<T>>> Iterator for Iter<'a, L> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|Keyed(x)| x)
+    }
+}
+
+impl<'a, T, L> IntoIterator for &'a OrderedSeq<L>
+where
+    L: FingerLink<Element = Keyed<T>>,
+    T: Ord + Clone + 'a,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, L> FromIterator<T> for OrderedSeq<L>
+where
+    L: FingerLink<Element = Keyed<T>>,
+    T: Ord + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(OrderedSeq::new(), |seq, x| seq.inserted(x))
+    }
+}
+
+impl<T, L> PartialEq for OrderedSeq<L>
+where
+    L: FingerLink<Element = Keyed<T>>,
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T, L> Debug for OrderedSeq<L>
+where
+    L: FingerLink<Element = Keyed<T>>,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|Keyed(x)| x))
+            .finish()
+    }
+}
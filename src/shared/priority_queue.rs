@@ -0,0 +1,195 @@
+use core::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+use super::finger_tree::{self, FingerLink, FingerTree, Measured, Monoid};
+
+/// A persistent max-priority queue that can be merged in O(log n).
+///
+/// This is a `FingerTree` of the elements in the order they were pushed,
+/// measured by their maximum. The largest element is found by following
+/// the subtrees whose maximum equals the overall one. Among equal
+/// elements, the one pushed first is popped first.
+pub struct PriorityQueue<L: FingerLink> {
+    tree: FingerTree<L>,
+    len: usize,
+}
+
+/// A `PriorityQueue` element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prioritized<T>(T);
+
+/// The largest element, as a measure. `None` is smaller than every
+/// element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Max<T>(pub Option<T>);
+
+impl<T: Ord + Clone> Monoid for Max<T> {
+    fn empty() -> Self {
+        Max(None)
+    }
+
+    fn combine(&self, other: &Self) -> Self {
+        if self.0 >= other.0 {
+            self.clone()
+        } else {
+            other.clone()
+        }
+    }
+}
+
+impl<T: Ord + Clone> Measured for Prioritized<T> {
+    type Measure = Max<T>;
+
+    fn measure(&self) -> Max<T> {
+        Max(Some(self.0.clone()))
+    }
+}
+
+impl<L: FingerLink> Default for PriorityQueue<L> {
+    fn default() -> Self {
+        PriorityQueue {
+            tree: FingerTree::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<T, L> Clone for PriorityQueue<L>
+where
+    L: FingerLink<Element = Prioritized<T>>,
+    T: Ord + Clone,
+{
+    fn clone(&self) -> Self {
+        PriorityQueue {
+            tree: self.tree.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T, L> PriorityQueue<L>
+where
+    L: FingerLink<Element = Prioritized<T>>,
+    T: Ord + Clone,
+{
+    /// Creates an empty `PriorityQueue`.
+    pub fn new() -> Self {
+        PriorityQueue::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements.
+    ///
+    /// Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Provides a reference to the largest element, or `None` if the
+    /// queue is empty.
+    ///
+    /// Complexity: O(log n)
+    pub fn peek_max(&self) -> Option<&T> {
+        let max = self.tree.measure();
+        self.tree
+            .find(|prefix| prefix.0 >= max.0)
+            .map(|Prioritized(x)| x)
+    }
+
+    /// Returns a queue with `value` added.
+    ///
+    /// Complexity: O(log n), O(1) amortised if old versions are not reused
+    pub fn pushed(&self, value: T) -> Self {
+        PriorityQueue {
+            tree: self.tree.pushed_back(Prioritized(value)),
+            len: self.len + 1,
+        }
+    }
+
+    /// Returns the queue without its largest element.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty queue.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::PriorityQueue;
+    ///
+    /// let q1: PriorityQueue<_> = [3, 9, 4].into_iter().collect();
+    /// let q2 = q1.popped_max();
+    ///
+    /// assert_eq!(q1.peek_max(), Some(&9));
+    /// assert_eq!(q2.peek_max(), Some(&4));
+    /// ```
+    pub fn popped_max(&self) -> Self {
+        if self.is_empty() {
+            panic!("You can't pop an empty queue!");
+        }
+        let max = self.tree.measure();
+        let (front, back) = self.tree.split(|prefix| prefix.0 >= max.0);
+        PriorityQueue {
+            tree: front.appended(&back.popped_front()),
+            len: self.len - 1,
+        }
+    }
+
+    /// Returns a queue with the elements of both `self` and `other`.
+    ///
+    /// Complexity: O(log min(m, n))
+    pub fn merged(&self, other: &Self) -> Self {
+        PriorityQueue {
+            tree: self.tree.appended(&other.tree),
+            len: self.len + other.len,
+        }
+    }
+
+    /// Iterates over the elements in the order they were pushed.
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter(self.tree.iter())
+    }
+}
+
+pub struct Iter<'a, L: FingerLink>(finger_tree::Iter<'a, L>);
+
+impl<'a, T: 'a, L: FingerLink<Element = Prioritized<T>>> Iterator for Iter<'a, L> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|Prioritized(x)| x)
+    }
+}
+
+impl<T, L> FromIterator<T> for PriorityQueue<L>
+where
+    L: FingerLink<Element = Prioritized<T>>,
+    T: Ord + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut len = 0;
+        let tree = iter
+            .into_iter()
+            .inspect(|_| len += 1)
+            .map(Prioritized)
+            .collect();
+        PriorityQueue { tree, len }
+    }
+}
+
+impl<T, L> Debug for PriorityQueue<L>
+where
+    L: FingerLink<Element = Prioritized<T>>,
+    T: Ord + Clone + Debug,
+{
+    /// Lists the elements in the order they were pushed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
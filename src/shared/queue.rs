@@ -0,0 +1,408 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+use super::link::Link;
+use super::list::{self, List};
+
+/// A persistent FIFO queue.
+///
+/// This is the Hood-Melville real-time queue: the rear list is reversed
+/// onto the front list a couple of steps at a time, so every operation
+/// is O(1) in the worst case, also when old versions are reused.
+pub struct Queue<L> {
+    front_len: usize,
+    front: List<L>,
+    rotation: Rotation<L>,
+    rear_len: usize,
+    rear: List<L>,
+}
+
+/// State of an incremental `front ++ reverse(rear)`.
+///
+/// `old_rear` is the rear that is being rotated, kept only so that the
+/// queue can be iterated while the rotation is in progress.
+enum Rotation<L> {
+    Idle,
+    Reversing {
+        ok: usize,
+        front: List<L>,
+        front_rev: List<L>,
+        rear: List<L>,
+        rear_rev: List<L>,
+        old_rear: List<L>,
+    },
+    Appending {
+        ok: usize,
+        front_rev: List<L>,
+        rear_rev: List<L>,
+        old_rear: List<L>,
+    },
+    Done(List<L>),
+}
+
+impl<L> Default for Queue<L> {
+    fn default() -> Self {
+        Queue {
+            front_len: 0,
+            front: List::default(),
+            rotation: Rotation::Idle,
+            rear_len: 0,
+            rear: List::default(),
+        }
+    }
+}
+
+impl<L: Link> Clone for Queue<L> {
+    fn clone(&self) -> Self {
+        Queue {
+            front_len: self.front_len,
+            front: self.front.clone(),
+            rotation: self.rotation.clone(),
+            rear_len: self.rear_len,
+            rear: self.rear.clone(),
+        }
+    }
+}
+
+impl<L: Link> Clone for Rotation<L> {
+    fn clone(&self) -> Self {
+        match self {
+            Rotation::Idle => Rotation::Idle,
+            Rotation::Reversing {
+                ok,
+                front,
+                front_rev,
+                rear,
+                rear_rev,
+                old_rear,
+            } => Rotation::Reversing {
+                ok: *ok,
+                front: front.clone(),
+                front_rev: front_rev.clone(),
+                rear: rear.clone(),
+                rear_rev: rear_rev.clone(),
+                old_rear: old_rear.clone(),
+            },
+            Rotation::Appending {
+                ok,
+                front_rev,
+                rear_rev,
+                old_rear,
+            } => Rotation::Appending {
+                ok: *ok,
+                front_rev: front_rev.clone(),
+                rear_rev: rear_rev.clone(),
+                old_rear: old_rear.clone(),
+            },
+            Rotation::Done(front) => Rotation::Done(front.clone()),
+        }
+    }
+}
+
+impl<L: Link> Queue<L> {
+    /// Creates an empty `Queue`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Queue;
+    /// let queue: Queue<u32> = Queue::new();
+    /// ```
+    pub fn new() -> Self {
+        Queue::default()
+    }
+
+    /// Returns `true` if this `Queue` is empty.
+    pub fn is_empty(&self) -> bool {
+        self.front_len == 0
+    }
+
+    /// Returns the number of elements in the queue.
+    ///
+    /// Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.front_len + self.rear_len
+    }
+
+    /// Provides a reference to the oldest element, or
+    /// `None` if the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use persi_ds::unsync::Queue;
+    ///
+    /// let q1 = Queue::new();
+    /// assert_eq!(q1.peek(), None);
+    ///
+    /// let q2 = q1.pushed_back(1).pushed_back(2);
+    /// assert_eq!(q2.peek(), Some(&1));
+    /// ```
+    pub fn peek(&self) -> Option<&L::ValueType> {
+        self.front.front()
+    }
+
+    /// Iterates over the elements, oldest first.
+    pub fn iter(&self) -> Iter<'_, L> {
+        let mut back = Vec::new();
+        back.extend(self.rear.iter());
+        match &self.rotation {
+            Rotation::Reversing { old_rear, .. } | Rotation::Appending { old_rear, .. } => {
+                back.extend(old_rear.iter())
+            }
+            Rotation::Idle | Rotation::Done(_) => {}
+        }
+        Iter {
+            front: self.front.iter(),
+            back,
+        }
+    }
+}
+
+impl<L> Queue<L>
+where
+    L: Link,
+    L::ValueType: Clone,
+{
+    /// Returns a queue with `value` added at the back.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use persi_ds::unsync::Queue;
+    ///
+    /// let q1 = Queue::new().pushed_back(1);
+    /// let q2 = q1.pushed_back(2);
+    ///
+    /// assert_eq!(q1.len(), 1);
+    /// assert_eq!(q2.len(), 2);
+    /// ```
+    pub fn pushed_back(&self, value: L::ValueType) -> Self {
+        Queue {
+            front_len: self.front_len,
+            front: self.front.clone(),
+            rotation: self.rotation.clone(),
+            rear_len: self.rear_len + 1,
+            rear: self.rear.pushed_front(value),
+        }
+        .check()
+    }
+
+    /// Returns the queue without its oldest element.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use persi_ds::unsync::Queue;
+    ///
+    /// let q1 = Queue::new().pushed_back(1).pushed_back(2);
+    /// let q2 = q1.popped_front();
+    ///
+    /// assert_eq!(q2.peek(), Some(&2));
+    /// assert_eq!(q1.peek(), Some(&1));
+    /// ```
+    pub fn popped_front(&self) -> Self {
+        if self.is_empty() {
+            panic!("You can't pop an empty queue!");
+        }
+        Queue {
+            front_len: self.front_len - 1,
+            front: self.front.popped_front(),
+            rotation: self.rotation.clone().invalidate(),
+            rear_len: self.rear_len,
+            rear: self.rear.clone(),
+        }
+        .check()
+    }
+
+    fn check(self) -> Self {
+        if self.rear_len <= self.front_len {
+            return self.exec2();
+        }
+        Queue {
+            front_len: self.front_len + self.rear_len,
+            rotation: Rotation::Reversing {
+                ok: 0,
+                front: self.front.clone(),
+                front_rev: List::new(),
+                rear: self.rear.clone(),
+                rear_rev: List::new(),
+                old_rear: self.rear,
+            },
+            front: self.front,
+            rear_len: 0,
+            rear: List::new(),
+        }
+        .exec2()
+    }
+
+    fn exec2(self) -> Self {
+        match self.rotation.exec().exec() {
+            Rotation::Done(front) => Queue {
+                front,
+                rotation: Rotation::Idle,
+                ..self
+            },
+            rotation => Queue { rotation, ..self },
+        }
+    }
+}
+
+impl<L> Rotation<L>
+where
+    L: Link,
+    L::ValueType: Clone,
+{
+    fn exec(self) -> Self {
+        match self {
+            Rotation::Reversing {
+                ok,
+                front,
+                front_rev,
+                rear,
+                rear_rev,
+                old_rear,
+            } => {
+                // `rear` is always one element longer than `front`
+                let y = rear.front().expect("rear is never empty while reversing");
+                match front.front() {
+                    Some(x) => Rotation::Reversing {
+                        ok: ok + 1,
+                        front: front.popped_front(),
+                        front_rev: front_rev.pushed_front(x.clone()),
+                        rear: rear.popped_front(),
+                        rear_rev: rear_rev.pushed_front(y.clone()),
+                        old_rear,
+                    },
+                    None => Rotation::Appending {
+                        ok,
+                        front_rev,
+                        rear_rev: rear_rev.pushed_front(y.clone()),
+                        old_rear,
+                    },
+                }
+            }
+            Rotation::Appending {
+                ok: 0, rear_rev, ..
+            } => Rotation::Done(rear_rev),
+            Rotation::Appending {
+                ok,
+                front_rev,
+                rear_rev,
+                old_rear,
+            } => {
+                let x = front_rev.front().expect("ok never exceeds front_rev");
+                Rotation::Appending {
+                    ok: ok - 1,
+                    rear_rev: rear_rev.pushed_front(x.clone()),
+                    front_rev: front_rev.popped_front(),
+                    old_rear,
+                }
+            }
+            state => state,
+        }
+    }
+
+    /// Records that the element at the front has been popped, so that
+    /// it is not copied into the new front.
+    fn invalidate(self) -> Self {
+        match self {
+            Rotation::Reversing {
+                ok,
+                front,
+                front_rev,
+                rear,
+                rear_rev,
+                old_rear,
+            } => Rotation::Reversing {
+                ok: ok - 1,
+                front,
+                front_rev,
+                rear,
+                rear_rev,
+                old_rear,
+            },
+            Rotation::Appending {
+                ok: 0, rear_rev, ..
+            } => Rotation::Done(rear_rev.popped_front()),
+            Rotation::Appending {
+                ok,
+                front_rev,
+                rear_rev,
+                old_rear,
+            } => Rotation::Appending {
+                ok: ok - 1,
+                front_rev,
+                rear_rev,
+                old_rear,
+            },
+            state => state,
+        }
+    }
+}
+
+pub struct Iter<'a, L: Link> {
+    front: list::Iter<'a, L>,
+    back: Vec<&'a L::ValueType>,
+}
+
+impl<'a, L: Link> Iterator for Iter<'a, L> {
+    type Item = &'a L::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.front.next().or_else(|| self.back.pop())
+    }
+}
+
+impl<'a, L: Link> IntoIterator for &'a Queue<L> {
+    type Item = &'a L::ValueType;
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<L> FromIterator<L::ValueType> for Queue<L>
+where
+    L: Link,
+    L::ValueType: Clone,
+{
+    fn from_iter<I: IntoIterator<Item = L::ValueType>>(iter: I) -> Self {
+        let mut queue = Queue::new();
+        for x in iter {
+            queue = queue.pushed_back(x);
+        }
+        queue
+    }
+}
+
+impl<L> PartialEq for Queue<L>
+where
+    L: Link,
+    L::ValueType: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<L> Debug for Queue<L>
+where
+    L: Link,
+    L::ValueType: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
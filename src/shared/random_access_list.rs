@@ -0,0 +1,384 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+    marker::PhantomData,
+};
+
+use super::link::{BinaryLink, Link};
+use super::list::{self, List};
+
+/// A persistent list with O(log n) indexing.
+///
+/// This is Okasaki's skew-binary random-access list: a list of complete
+/// binary trees whose sizes are the digits of a skew-binary number.
+/// `cons`, `front` and `popped_front` are O(1), `get` and `updated`
+/// O(log n). Elements are stored in preorder, so iteration yields them
+/// in the same order as `List`.
+pub struct RandomAccessList<L, B> {
+    spine: List<L>,
+    tree: PhantomData<B>,
+}
+
+/// A complete binary tree holding `weight` elements.
+pub struct Digit<B> {
+    weight: usize,
+    tree: B,
+}
+
+impl<B: BinaryLink> Digit<B> {
+    fn new(weight: usize, tree: B) -> Self {
+        Digit { weight, tree }
+    }
+}
+
+impl<L, B> Default for RandomAccessList<L, B> {
+    fn default() -> Self {
+        RandomAccessList {
+            spine: List::default(),
+            tree: PhantomData,
+        }
+    }
+}
+
+impl<L: Link, B> Clone for RandomAccessList<L, B> {
+    fn clone(&self) -> Self {
+        RandomAccessList {
+            spine: self.spine.clone(),
+            tree: PhantomData,
+        }
+    }
+}
+
+impl<L, B> RandomAccessList<L, B>
+where
+    L: Link<ValueType = Digit<B>>,
+    B: BinaryLink,
+{
+    /// Creates an empty `RandomAccessList`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::RandomAccessList;
+    /// let list: RandomAccessList<u32> = RandomAccessList::new();
+    /// ```
+    pub fn new() -> Self {
+        RandomAccessList::default()
+    }
+
+    /// Creates an empty `RandomAccessList`.
+    pub fn empty() -> Self {
+        RandomAccessList::default()
+    }
+
+    /// Creates a list with the element given as head
+    /// and the provided list as tail.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::RandomAccessList;
+    ///
+    /// let list = RandomAccessList::cons(1, &RandomAccessList::new());
+    /// let list = RandomAccessList::cons(2, list);
+    /// assert_eq!(list.get(1), Some(&1));
+    /// ```
+    pub fn cons<N>(element: B::ValueType, tail: N) -> Self
+    where
+        N: AsRef<Self>,
+    {
+        let spine = &tail.as_ref().spine;
+        if let Some(first) = spine.front() {
+            let rest = spine.popped_front();
+            if let Some(second) = rest.front() {
+                if first.weight == second.weight {
+                    let tree =
+                        B::node(element, Some(first.tree.clone()), Some(second.tree.clone()));
+                    let spine = rest
+                        .popped_front()
+                        .pushed_front(Digit::new(1 + first.weight + second.weight, tree));
+                    return RandomAccessList::from_spine(spine);
+                }
+            }
+        }
+        RandomAccessList::from_spine(
+            spine.pushed_front(Digit::new(1, B::node(element, None, None))),
+        )
+    }
+
+    fn from_spine(spine: List<L>) -> Self {
+        RandomAccessList {
+            spine,
+            tree: PhantomData,
+        }
+    }
+
+    pub fn from_value(element: B::ValueType) -> Self {
+        RandomAccessList::cons(element, RandomAccessList::new())
+    }
+
+    /// Provides a reference to the front element, or
+    /// `None` if the list is empty.
+    pub fn front(&self) -> Option<&B::ValueType> {
+        self.spine.front().map(|digit| digit.tree.get_element())
+    }
+
+    /// Returns `true` if this `RandomAccessList` is empty.
+    pub fn is_empty(&self) -> bool {
+        self.spine.is_empty()
+    }
+
+    /// Returns the number of elements in the list.
+    ///
+    /// Complexity: O(log n)
+    pub fn len(&self) -> usize {
+        self.spine.iter().map(|digit| digit.weight).sum()
+    }
+
+    /// Returns the tail of the list.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty list.
+    pub fn popped_front(&self) -> Self {
+        let first = match self.spine.front() {
+            None => panic!("You can't pop an empty list!"),
+            Some(first) => first,
+        };
+        let rest = self.spine.popped_front();
+        let spine = match (first.tree.left_ref(), first.tree.right_ref()) {
+            (Some(left), Some(right)) => {
+                let weight = first.weight / 2;
+                rest.pushed_front(Digit::new(weight, right.clone()))
+                    .pushed_front(Digit::new(weight, left.clone()))
+            }
+            _ => rest,
+        };
+        RandomAccessList::from_spine(spine)
+    }
+
+    pub fn pushed_front(&self, value: B::ValueType) -> Self {
+        RandomAccessList::cons(value, self)
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if
+    /// `index` is out of bounds.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::RandomAccessList;
+    ///
+    /// let list: RandomAccessList<_> = (0..10).collect();
+    /// assert_eq!(list.get(3), Some(&3));
+    /// assert_eq!(list.get(10), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&B::ValueType> {
+        let mut index = index;
+        for digit in &self.spine {
+            if index < digit.weight {
+                return Some(tree_get(digit.weight, &digit.tree, index));
+            }
+            index -= digit.weight;
+        }
+        None
+    }
+
+    /// Returns `true` if both lists share the same nodes.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.spine.ptr_eq(&other.spine)
+    }
+
+    pub fn iter(&self) -> Iter<'_, L, B> {
+        Iter {
+            spine: self.spine.iter(),
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl<L, B> RandomAccessList<L, B>
+where
+    L: Link<ValueType = Digit<B>>,
+    B: BinaryLink,
+    B::ValueType: Clone,
+{
+    /// Returns a list with the element at `index` replaced by `value`.
+    ///
+    /// Only the path to the element is copied, the rest of the list is
+    /// shared with `self`.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::RandomAccessList;
+    ///
+    /// let l1: RandomAccessList<_> = (0..10).collect();
+    /// let l2 = l1.updated(3, 42);
+    ///
+    /// assert_eq!(l1.get(3), Some(&3));
+    /// assert_eq!(l2.get(3), Some(&42));
+    /// ```
+    pub fn updated(&self, index: usize, value: B::ValueType) -> Self {
+        let mut skipped = Vec::new();
+        let mut rest = self.spine.clone();
+        let mut index = index;
+        loop {
+            let digit = match rest.front() {
+                None => panic!("index out of bounds"),
+                Some(digit) => digit,
+            };
+            if index < digit.weight {
+                let tree = tree_updated(digit.weight, &digit.tree, index, value);
+                let mut spine = rest
+                    .popped_front()
+                    .pushed_front(Digit::new(digit.weight, tree));
+                for digit in skipped.into_iter().rev() {
+                    spine = spine.pushed_front(digit);
+                }
+                return RandomAccessList::from_spine(spine);
+            }
+            index -= digit.weight;
+            skipped.push(Digit::new(digit.weight, digit.tree.clone()));
+            rest = rest.popped_front();
+        }
+    }
+}
+
+fn tree_get<B: BinaryLink>(weight: usize, tree: &B, index: usize) -> &B::ValueType {
+    let mut weight = weight;
+    let mut tree = tree;
+    let mut index = index;
+    while index > 0 {
+        weight /= 2;
+        tree = if index <= weight {
+            index -= 1;
+            tree.left_ref().expect("complete tree")
+        } else {
+            index -= 1 + weight;
+            tree.right_ref().expect("complete tree")
+        };
+    }
+    tree.get_element()
+}
+
+fn tree_updated<B>(weight: usize, tree: &B, index: usize, value: B::ValueType) -> B
+where
+    B: BinaryLink,
+    B::ValueType: Clone,
+{
+    let left = tree.left_ref().map(B::clone);
+    let right = tree.right_ref().map(B::clone);
+    if index == 0 {
+        return B::node(value, left, right);
+    }
+    let weight = weight / 2;
+    let element = tree.get_element().clone();
+    if index <= weight {
+        let left = tree_updated(weight, &left.expect("complete tree"), index - 1, value);
+        B::node(element, Some(left), right)
+    } else {
+        let right = tree_updated(
+            weight,
+            &right.expect("complete tree"),
+            index - 1 - weight,
+            value,
+        );
+        B::node(element, left, Some(right))
+    }
+}
+
+impl<L, B> AsRef<RandomAccessList<L, B>> for RandomAccessList<L, B> {
+    fn as_ref(&self) -> &RandomAccessList<L, B> {
+        self
+    }
+}
+
+pub struct Iter<'a, L, B> {
+    spine: list::Iter<'a, L>,
+    stack: Vec<&'a B>,
+}
+
+impl<'a, L, B> Iterator for Iter<'a, L, B>
+where
+    L: Link<ValueType = Digit<B>>,
+    B: BinaryLink,
+{
+    type Item = &'a B::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.stack.is_empty() {
+            let digit = self.spine.next()?;
+            self.stack.push(&digit.tree);
+        }
+        let node = self.stack.pop()?;
+        if let Some(right) = node.right_ref() {
+            self.stack.push(right);
+        }
+        if let Some(left) = node.left_ref() {
+            self.stack.push(left);
+        }
+        Some(node.get_element())
+    }
+}
+
+impl<'a, L, B> IntoIterator for &'a RandomAccessList<L, B>
+where
+    L: Link<ValueType = Digit<B>>,
+    B: BinaryLink,
+{
+    type Item = &'a B::ValueType;
+    type IntoIter = Iter<'a, L, B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<L, B> FromIterator<B::ValueType> for RandomAccessList<L, B>
+where
+    L: Link<ValueType = Digit<B>>,
+    B: BinaryLink,
+{
+    /// Builds a list with the elements in iteration order, so that the
+    /// first element yielded ends up at the front.
+    fn from_iter<I: IntoIterator<Item = B::ValueType>>(iter: I) -> Self {
+        let elements: Vec<_> = iter.into_iter().collect();
+        let mut list = RandomAccessList::new();
+        for x in elements.into_iter().rev() {
+            list = list.pushed_front(x);
+        }
+        list
+    }
+}
+
+impl<L, B> PartialEq for RandomAccessList<L, B>
+where
+    L: Link<ValueType = Digit<B>>,
+    B: BinaryLink,
+    B::ValueType: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.iter().eq(other.iter())
+    }
+}
+
+impl<L, B> Debug for RandomAccessList<L, B>
+where
+    L: Link<ValueType = Digit<B>>,
+    B: BinaryLink,
+    B::ValueType: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,207 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::fmt::{self, Debug};
+
+use super::tree::{Tree, TreeLink};
+
+/// A query for nodes of a `Tree`, run with `Tree::select`.
+///
+/// A selector starts out with a set of nodes, either every node of the
+/// tree or only the root, and each step replaces the set: `child` with
+/// the children of its nodes, `descendant` with all their descendants,
+/// and `filter` with those of its nodes whose element satisfies a
+/// predicate. So the nodes labelled `x` whose parent is labelled `y` are
+///
+/// ```
+/// use persi_ds::unsync::tree::Selector;
+///
+/// let selector = Selector::new()
+///     .filter(|label: &&str| *label == "y")
+///     .child()
+///     .filter(|label| *label == "x");
+/// ```
+pub struct Selector<'a, T> {
+    anchored: bool,
+    steps: Vec<Step<'a, T>>,
+}
+
+enum Step<'a, T> {
+    Child,
+    Descendant,
+    Filter(Box<dyn Fn(&T) -> bool + 'a>),
+}
+
+impl<'a, T> Default for Selector<'a, T> {
+    fn default() -> Self {
+        Selector {
+            anchored: false,
+            steps: Vec::new(),
+        }
+    }
+}
+
+impl<'a, T> Selector<'a, T> {
+    /// Creates a selector that starts out with every node of the tree.
+    pub fn new() -> Self {
+        Selector::default()
+    }
+
+    /// Creates a selector that starts out with the root only.
+    pub fn root() -> Self {
+        Selector {
+            anchored: true,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Moves on to the children of the selected nodes.
+    pub fn child(mut self) -> Self {
+        self.steps.push(Step::Child);
+        self
+    }
+
+    /// Moves on to the descendants of the selected nodes, at any depth
+    /// below them.
+    pub fn descendant(mut self) -> Self {
+        self.steps.push(Step::Descendant);
+        self
+    }
+
+    /// Keeps the selected nodes whose element satisfies `p`.
+    pub fn filter<P>(mut self, p: P) -> Self
+    where
+        P: Fn(&T) -> bool + 'a,
+    {
+        self.steps.push(Step::Filter(Box::new(p)));
+        self
+    }
+}
+
+impl<'a, T> Debug for Selector<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        list.entry(&format_args!(
+            "{}",
+            if self.anchored { "Root" } else { "Any" }
+        ));
+        for step in &self.steps {
+            list.entry(&format_args!(
+                "{}",
+                match step {
+                    Step::Child => "Child",
+                    Step::Descendant => "Descendant",
+                    Step::Filter(_) => "Filter",
+                }
+            ));
+        }
+        list.finish()
+    }
+}
+
+impl<N: TreeLink> Tree<N> {
+    /// Returns the nodes chosen by `selector`, as subtrees together with
+    /// their paths, in preorder. The paths can be passed straight to
+    /// `get_at`, `updated_at` or `removed_at`.
+    ///
+    /// Every step keeps an explicit stack, so this works for trees of any
+    /// depth.
+    ///
+    /// Complexity: O(n·d) for each step, where d is the depth of the
+    /// tree
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{
+    ///     tree::{Selector, Tree},
+    ///     List,
+    /// };
+    ///
+    /// let y = Tree::new("y", List::new().pushed_front(Tree::leaf("z")).pushed_front(Tree::leaf("x")));
+    /// let tree = Tree::new("x", List::from_value(y));
+    ///
+    /// let selector = Selector::new()
+    ///     .filter(|label: &&str| *label == "y")
+    ///     .child()
+    ///     .filter(|label| *label == "x");
+    /// let found = tree.select(&selector);
+    ///
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].0, [0, 0]);
+    /// assert_eq!(tree.get_at(&found[0].0), Some(&"x"));
+    /// ```
+    pub fn select(&self, selector: &Selector<'_, N::ValueType>) -> Vec<(Vec<usize>, &Self)> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let mut selected = if selector.anchored {
+            vec![(Vec::new(), self)]
+        } else {
+            let mut all = vec![(Vec::new(), self)];
+            push_descendants(&mut all, &[], self);
+            all
+        };
+        for step in &selector.steps {
+            selected = match step {
+                Step::Filter(p) => selected
+                    .into_iter()
+                    .filter(|(_, tree)| tree.root().map_or(false, p))
+                    .collect(),
+                Step::Child => {
+                    let mut children = Vec::new();
+                    for (path, tree) in &selected {
+                        push_children(&mut children, path, tree);
+                    }
+                    // Children of nested nodes interleave; paths sort in
+                    // preorder.
+                    children.sort_by(|a, b| a.0.cmp(&b.0));
+                    children
+                }
+                Step::Descendant => {
+                    let mut descendants = Vec::new();
+                    let mut covered: Option<&[usize]> = None;
+                    for (path, tree) in &selected {
+                        if covered.map_or(false, |above| path.starts_with(above)) {
+                            continue;
+                        }
+                        push_descendants(&mut descendants, path, tree);
+                        covered = Some(path);
+                    }
+                    descendants
+                }
+            };
+        }
+        selected
+    }
+}
+
+/// Appends the non-empty children of `tree`, which is at `path`.
+fn push_children<'t, N: TreeLink>(
+    out: &mut Vec<(Vec<usize>, &'t Tree<N>)>,
+    path: &[usize],
+    tree: &'t Tree<N>,
+) {
+    let children = tree.children().into_iter().flat_map(|list| list.iter());
+    for (index, child) in children.enumerate() {
+        if !child.is_empty() {
+            let mut child_path = path.to_vec();
+            child_path.push(index);
+            out.push((child_path, child));
+        }
+    }
+}
+
+/// Appends all the nodes below `tree`, which is at `path`, in preorder.
+fn push_descendants<'t, N: TreeLink>(
+    out: &mut Vec<(Vec<usize>, &'t Tree<N>)>,
+    path: &[usize],
+    tree: &'t Tree<N>,
+) {
+    let mut stack = Vec::new();
+    push_children(&mut stack, path, tree);
+    stack.reverse();
+    while let Some((path, tree)) = stack.pop() {
+        let start = stack.len();
+        push_children(&mut stack, &path, tree);
+        stack[start..].reverse();
+        out.push((path, tree));
+    }
+}
@@ -0,0 +1,259 @@
+use core::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+use super::finger_tree::{self, FingerLink, FingerTree, Measured, Monoid};
+
+/// A persistent sequence with cheap access at both ends, indexing,
+/// splitting and concatenation.
+///
+/// This is a `FingerTree` measured by element count. Pushing and popping
+/// at either end are O(1) amortised if old versions are not reused, and
+/// O(log n) in the worst case; `get`, `updated`, `split_at` and
+/// `appended` are O(log n).
+pub struct Seq<L: FingerLink>(FingerTree<L>);
+
+/// A `Seq` element, which counts as one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Counted<T>(T);
+
+/// The number of elements, as a measure.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Size(pub usize);
+
+impl Monoid for Size {
+    fn empty() -> Self {
+        Size(0)
+    }
+
+    fn combine(&self, other: &Self) -> Self {
+        Size(self.0 + other.0)
+    }
+}
+
+impl<T> Measured for Counted<T> {
+    type Measure = Size;
+
+    fn measure(&self) -> Size {
+        Size(1)
+    }
+}
+
+impl<L: FingerLink> Default for Seq<L> {
+    fn default() -> Self {
+        Seq(FingerTree::default())
+    }
+}
+
+impl<T, L> Clone for Seq<L>
+where
+    L: FingerLink<Element = Counted<T>>,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Seq(self.0.clone())
+    }
+}
+
+impl<T, L: FingerLink<Element = Counted<T>>> Seq<L> {
+    /// Creates an empty `Seq`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Seq;
+    ///
+    /// let seq: Seq<u32> = Seq::new();
+    ///
+    /// assert!(seq.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Seq::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of elements.
+    ///
+    /// Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.0.measure().0
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.0.front().map(|Counted(x)| x)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.0.back().map(|Counted(x)| x)
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if it is
+    /// out of bounds.
+    ///
+    /// Complexity: O(log n)
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.0.find(|size| size.0 > index).map(|Counted(x)| x)
+    }
+
+    /// Returns `true` if both sequences share the same root node, or are
+    /// both empty.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter(self.0.iter())
+    }
+}
+
+impl<T, L> Seq<L>
+where
+    L: FingerLink<Element = Counted<T>>,
+    T: Clone,
+{
+    /// Returns a sequence with `value` added at the front.
+    ///
+    /// Complexity: O(log n), O(1) amortised if old versions are not reused
+    pub fn pushed_front(&self, value: T) -> Self {
+        Seq(self.0.pushed_front(Counted(value)))
+    }
+
+    /// Returns a sequence with `value` added at the back.
+    ///
+    /// Complexity: O(log n), O(1) amortised if old versions are not reused
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Seq;
+    ///
+    /// let seq = Seq::new().pushed_back(1).pushed_back(2).pushed_front(0);
+    ///
+    /// assert!(seq.iter().copied().eq(0..3));
+    /// ```
+    pub fn pushed_back(&self, value: T) -> Self {
+        Seq(self.0.pushed_back(Counted(value)))
+    }
+
+    /// Returns the sequence without its front element.
+    ///
+    /// Complexity: O(log n), O(1) amortised if old versions are not reused
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty sequence.
+    pub fn popped_front(&self) -> Self {
+        if self.is_empty() {
+            panic!("You can't pop an empty sequence!");
+        }
+        Seq(self.0.popped_front())
+    }
+
+    /// Returns the sequence without its back element.
+    ///
+    /// Complexity: O(log n), O(1) amortised if old versions are not reused
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty sequence.
+    pub fn popped_back(&self) -> Self {
+        if self.is_empty() {
+            panic!("You can't pop an empty sequence!");
+        }
+        Seq(self.0.popped_back())
+    }
+
+    /// Returns the sequence with the element at `index` replaced by
+    /// `value`.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `index` is out of bounds.
+    pub fn updated(&self, index: usize, value: T) -> Self {
+        if index >= self.len() {
+            panic!("index out of bounds");
+        }
+        let (front, back) = self.split_at(index);
+        front.pushed_back(value).appended(&back.popped_front())
+    }
+
+    /// Splits the sequence into the first `index` elements and the rest.
+    /// An `index` past the end leaves the second sequence empty.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Seq;
+    ///
+    /// let seq: Seq<_> = (0..10).collect();
+    /// let (front, back) = seq.split_at(4);
+    ///
+    /// assert!(front.iter().copied().eq(0..4));
+    /// assert!(back.iter().copied().eq(4..10));
+    /// ```
+    pub fn split_at(&self, index: usize) -> (Self, Self) {
+        let (front, back) = self.0.split(|size| size.0 > index);
+        (Seq(front), Seq(back))
+    }
+
+    /// Returns the elements of `self` followed by those of `other`.
+    ///
+    /// Complexity: O(log min(m, n))
+    pub fn appended(&self, other: &Self) -> Self {
+        Seq(self.0.appended(&other.0))
+    }
+}
+
+pub struct Iter<'a, L: FingerLink>(finger_tree::Iter<'a, L>);
+
+impl<'a, T: 'a, L: FingerLink<Element = Counted<T>>> Iterator for Iter<'a, L> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|Counted(x)| x)
+    }
+}
+
+impl<'a, T: 'a, L: FingerLink<Element = Counted<T>>> IntoIterator for &'a Seq<L> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, L> FromIterator<T> for Seq<L>
+where
+    L: FingerLink<Element = Counted<T>>,
+    T: Clone,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Seq(iter.into_iter().map(Counted).collect())
+    }
+}
+
+impl<T, L> PartialEq for Seq<L>
+where
+    L: FingerLink<Element = Counted<T>>,
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.0 == other.0
+    }
+}
+
+impl<T, L> Debug for Seq<L>
+where
+    L: FingerLink<Element = Counted<T>>,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
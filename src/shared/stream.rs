@@ -0,0 +1,402 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug},
+    mem,
+};
+
+use super::lazy::{Lazy, Suspend};
+use super::link::Link;
+use super::list::List;
+
+/// A reference counted pointer to a node of a `Stream`.
+pub trait StreamLink: Sized {
+    type Element;
+    /// The memoised tail of a node.
+    type Tail: Lazy<Value = Stream<Self>>;
+    /// The link of the lists that `to_list` builds.
+    type ListLink: Link<ValueType = Self::Element>;
+
+    fn new(node: StreamNode<Self>) -> Self;
+
+    fn clone(&self) -> Self;
+
+    fn node(&self) -> &StreamNode<Self>;
+
+    /// Returns `true` if both links point to the same node.
+    fn ptr_eq(&self, other: &Self) -> bool;
+
+    /// Takes the node out if this is its only link.
+    fn try_unwrap(self) -> Result<StreamNode<Self>, Self>;
+}
+
+/// Names the link, in the same flavour, of a stream of `U`, for the
+/// combinators that change the element type.
+pub trait Rebind<U>: StreamLink {
+    type Link: StreamLink<Element = U>;
+}
+
+/// The stream of `U` in the flavour of `L`.
+type Rebound<L, U> = Stream<<L as Rebind<U>>::Link>;
+
+/// The elements of two zipped streams.
+type Pair<L, M> = (<L as StreamLink>::Element, <M as StreamLink>::Element);
+
+/// A node of a `Stream`: a known element and a suspended tail.
+pub struct StreamNode<L: StreamLink> {
+    element: L::Element,
+    tail: L::Tail,
+}
+
+impl<L: StreamLink> StreamNode<L> {
+    fn tail(&self) -> &Stream<L> {
+        self.tail.force()
+    }
+}
+
+/// A persistent lazy list.
+///
+/// The front element of a non-empty stream is known, while the tail is
+/// computed on first use and then memoised, so it is evaluated at most
+/// once even when the stream is shared between versions, or between
+/// threads for `sync::Stream`. How a tail behaves when its thunk panics
+/// or forces the tail itself depends on the flavour's once-cell, see
+/// `unsync::lazy::Memo` and `sync::lazy::Memo`.
+pub struct Stream<L> {
+    root: Option<L>,
+}
+
+impl<L> Default for Stream<L> {
+    fn default() -> Self {
+        Stream { root: None }
+    }
+}
+
+impl<L: StreamLink> Clone for Stream<L> {
+    fn clone(&self) -> Self {
+        Stream {
+            root: self.root.as_ref().map(L::clone),
+        }
+    }
+}
+
+impl<L: StreamLink> Stream<L> {
+    /// Creates an empty `Stream`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Stream;
+    /// let stream: Stream<u32> = Stream::new();
+    /// ```
+    pub fn new() -> Self {
+        Stream::default()
+    }
+
+    /// Creates an empty `Stream`.
+    pub fn empty() -> Self {
+        Stream::default()
+    }
+
+    /// Creates a stream with the element given as head
+    /// and the provided, already evaluated, stream as tail.
+    pub fn cons(element: L::Element, tail: &Self) -> Self {
+        Stream {
+            root: Some(L::new(StreamNode {
+                element,
+                tail: L::Tail::evaluated(tail.clone()),
+            })),
+        }
+    }
+
+    /// Creates a stream with the element given as head and a tail that
+    /// is computed by `tail` when it is first needed.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Stream;
+    ///
+    /// let stream = Stream::cons_lazy(1, || Stream::cons_lazy(2, Stream::new));
+    /// assert!(stream.iter().copied().eq(1..3));
+    /// ```
+    pub fn cons_lazy<F>(element: L::Element, tail: F) -> Self
+    where
+        F: FnOnce() -> Self,
+        L::Tail: Suspend<F>,
+    {
+        Stream::suspended(element, tail, |tail| tail())
+    }
+
+    /// Creates a stream with the element given as head and a tail that
+    /// is computed by `body(captured)` when it is first needed.
+    pub(crate) fn suspended<X>(element: L::Element, captured: X, body: fn(X) -> Self) -> Self
+    where
+        L::Tail: Suspend<X>,
+    {
+        Stream {
+            root: Some(L::new(StreamNode {
+                element,
+                tail: L::Tail::suspended(captured, body),
+            })),
+        }
+    }
+
+    /// Returns `true` if this `Stream` is empty.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Provides a reference to the front element, or
+    /// `None` if the stream is empty.
+    pub fn front(&self) -> Option<&L::Element> {
+        self.root.as_ref().map(|link| &link.node().element)
+    }
+
+    /// Returns the tail of the stream, evaluating it if needed.
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty stream.
+    pub fn popped_front(&self) -> Self {
+        match &self.root {
+            None => panic!("You can't pop an empty stream!"),
+            Some(link) => link.node().tail().clone(),
+        }
+    }
+
+    /// Returns `true` if both streams share the same front node.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            _ => false,
+        }
+    }
+
+    /// Iterates over the elements, evaluating the tails as it goes.
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter {
+            next: self.root.as_ref().map(L::node),
+            yielded: None,
+        }
+    }
+
+    /// Creates a stream whose elements are produced by calling `f`
+    /// until it returns `None`.
+    ///
+    /// The first element is produced immediately, the rest on demand.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Stream;
+    ///
+    /// let mut count = 0;
+    /// let stream = Stream::from_fn(move || {
+    ///     count += 1;
+    ///     if count < 4 { Some(count) } else { None }
+    /// });
+    /// assert!(stream.iter().copied().eq(1..4));
+    /// ```
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: FnMut() -> Option<L::Element>,
+        L::Tail: Suspend<F>,
+    {
+        let mut f = f;
+        match f() {
+            None => Stream::new(),
+            Some(x) => Stream::suspended(x, f, Stream::from_fn),
+        }
+    }
+
+    /// Creates the infinite stream `x, f(x), f(f(x)), ...`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Stream;
+    ///
+    /// let powers = Stream::iterate(1, |x| x * 2);
+    /// assert!(powers.take(4).iter().copied().eq([1, 2, 4, 8]));
+    /// ```
+    pub fn iterate<F>(x: L::Element, f: F) -> Self
+    where
+        L::Element: Clone,
+        F: Fn(&L::Element) -> L::Element,
+        L::Tail: Suspend<(L::Element, F)>,
+    {
+        let seed = x.clone();
+        Stream::suspended(x, (seed, f), |(seed, f)| {
+            let next = f(&seed);
+            Stream::iterate(next, f)
+        })
+    }
+
+    /// Returns a stream with `f` applied to every element.
+    pub fn map<U, F>(&self, f: F) -> Rebound<L, U>
+    where
+        L: Rebind<U>,
+        F: Fn(&L::Element) -> U,
+        <<L as Rebind<U>>::Link as StreamLink>::Tail: Suspend<(L, F)>,
+    {
+        match &self.root {
+            None => Stream::new(),
+            Some(link) => {
+                let element = f(&link.node().element);
+                Stream::suspended(element, (link.clone(), f), |(link, f)| {
+                    link.node().tail().map(f)
+                })
+            }
+        }
+    }
+
+    /// Returns a stream of the elements that match `p`.
+    ///
+    /// Finding the front element evaluates the stream up to the first
+    /// match.
+    pub fn filter<P>(&self, p: P) -> Self
+    where
+        L::Element: Clone,
+        P: Fn(&L::Element) -> bool,
+        L::Tail: Suspend<(L, P)>,
+    {
+        let mut stream = self.clone();
+        while let Some(link) = stream.root {
+            if p(&link.node().element) {
+                let element = link.node().element.clone();
+                return Stream::suspended(element, (link, p), |(link, p)| {
+                    link.node().tail().filter(p)
+                });
+            }
+            stream = link.node().tail().clone();
+        }
+        Stream::new()
+    }
+
+    /// Returns a stream of at most the first `n` elements.
+    pub fn take(&self, n: usize) -> Self
+    where
+        L::Element: Clone,
+        L::Tail: Suspend<(L, usize)>,
+    {
+        match &self.root {
+            Some(link) if n == 1 => Stream::cons(link.node().element.clone(), &Stream::new()),
+            Some(link) if n > 1 => {
+                let element = link.node().element.clone();
+                Stream::suspended(element, (link.clone(), n - 1), |(link, n)| {
+                    link.node().tail().take(n)
+                })
+            }
+            _ => Stream::new(),
+        }
+    }
+
+    /// Returns a stream of pairs, as long as the shorter of the streams.
+    pub fn zip<M>(&self, other: &Stream<M>) -> Rebound<L, Pair<L, M>>
+    where
+        M: StreamLink,
+        L: Rebind<Pair<L, M>>,
+        L::Element: Clone,
+        M::Element: Clone,
+        <<L as Rebind<Pair<L, M>>>::Link as StreamLink>::Tail: Suspend<(L, M)>,
+    {
+        match (&self.root, &other.root) {
+            (Some(a), Some(b)) => {
+                let element = (a.node().element.clone(), b.node().element.clone());
+                Stream::suspended(element, (a.clone(), b.clone()), |(a, b)| {
+                    a.node().tail().zip(b.node().tail())
+                })
+            }
+            _ => Stream::new(),
+        }
+    }
+}
+
+impl<L> Stream<L>
+where
+    L: StreamLink,
+    L::Element: Clone,
+{
+    /// Evaluates the whole stream into a `List` with the same order.
+    ///
+    /// This never returns for an infinite stream.
+    pub fn to_list(&self) -> List<L::ListLink> {
+        let elements: Vec<_> = self.iter().collect();
+        let mut list = List::new();
+        for x in elements.into_iter().rev() {
+            list = list.pushed_front(x.clone());
+        }
+        list
+    }
+}
+
+impl<L> From<&Stream<L>> for List<L::ListLink>
+where
+    L: StreamLink,
+    L::Element: Clone,
+{
+    fn from(stream: &Stream<L>) -> Self {
+        stream.to_list()
+    }
+}
+
+impl<L: StreamLink> Drop for StreamNode<L> {
+    fn drop(&mut self) {
+        // unlink evaluated tails one node at a time to not overflow the
+        // stack on long streams
+        let mut next = self.tail.get_mut().map(mem::take);
+        while let Some(Stream { root: Some(link) }) = next {
+            next = match link.try_unwrap() {
+                Ok(mut node) => node.tail.get_mut().map(mem::take),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+pub struct Iter<'a, L: StreamLink> {
+    next: Option<&'a StreamNode<L>>,
+    yielded: Option<&'a StreamNode<L>>,
+}
+
+impl<'a, L: StreamLink> Iterator for Iter<'a, L> {
+    type Item = &'a L::Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the tail of the previous element is only forced when the next
+        // element is asked for
+        if let Some(node) = self.yielded.take() {
+            self.next = node.tail().root.as_ref().map(L::node);
+        }
+        let node = self.next.take()?;
+        self.yielded = Some(node);
+        Some(&node.element)
+    }
+}
+
+impl<'a, L: StreamLink> IntoIterator for &'a Stream<L> {
+    type Item = &'a L::Element;
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<L> Debug for Stream<L>
+where
+    L: StreamLink,
+    L::Element: Debug,
+{
+    /// Prints the elements that have been evaluated so far, followed by
+    /// `..` if the rest of the stream is not yet known.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        let mut next = self.root.as_ref();
+        while let Some(link) = next {
+            list.entry(&link.node().element);
+            match link.node().tail.get() {
+                Some(tail) => next = tail.root.as_ref(),
+                None => return list.entry(&format_args!("..")).finish(),
+            }
+        }
+        list.finish()
+    }
+}
@@ -0,0 +1,1577 @@
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    string::String,
+    vec,
+    vec::Vec,
+};
+use core::{
+    cmp::max,
+    fmt::{self, Debug},
+    iter::Enumerate,
+};
+
+use super::draw::{self, Draw};
+use super::link::Link;
+use super::list::{self, List};
+
+pub trait TreeLink: Sized {
+    type ValueType;
+    type ListLink: Link<ValueType = Tree<Self>>;
+
+    fn node(element: Self::ValueType, children: List<Self::ListLink>) -> Self;
+    fn clone(&self) -> Self;
+    fn get_element(&self) -> &Self::ValueType;
+    fn children(&self) -> &List<Self::ListLink>;
+    /// Returns `true` if both links point to the same node.
+    fn ptr_eq(&self, other: &Self) -> bool;
+    /// Returns the address of the node, which identifies it for as long
+    /// as the link is alive.
+    fn as_ptr(&self) -> *const ();
+    /// Returns the children of the node if this is the only link to it.
+    fn try_unwrap_children(self) -> Option<List<Self::ListLink>>;
+}
+
+/// A persistent rose tree: every node holds an element and a list of
+/// child trees.
+pub struct Tree<N> {
+    root: Option<N>,
+}
+
+impl<N> Default for Tree<N> {
+    fn default() -> Self {
+        Self { root: None }
+    }
+}
+
+impl<N: TreeLink> Tree<N> {
+    pub fn empty() -> Self {
+        Tree::default()
+    }
+
+    pub fn leaf(x: N::ValueType) -> Self {
+        Tree::new(x, List::new())
+    }
+
+    pub fn new(x: N::ValueType, children: List<N::ListLink>) -> Self {
+        Tree {
+            root: Some(N::node(x, children)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn root(&self) -> Option<&N::ValueType> {
+        self.root.as_ref().map(N::get_element)
+    }
+
+    pub fn children(&self) -> Option<&List<N::ListLink>> {
+        self.root.as_ref().map(N::children)
+    }
+
+    /// Returns `true` if both trees share the same root node, or are
+    /// both empty.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            _ => false,
+        }
+    }
+
+    /// Iterates over the elements depth first, parents before their
+    /// children.
+    ///
+    /// The traversal keeps an explicit stack, so it works for trees of
+    /// any depth.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{tree::Tree, List};
+    ///
+    /// let children = List::new().pushed_front(Tree::leaf(3)).pushed_front(Tree::leaf(2));
+    /// let tree = Tree::new(1, children);
+    ///
+    /// assert!(tree.preorder().copied().eq([1, 2, 3]));
+    /// assert!(tree.preorder().with_depth().eq([(0, &1), (1, &2), (1, &3)]));
+    /// ```
+    pub fn preorder(&self) -> Preorder<'_, N> {
+        Preorder(DepthFirst::new(self))
+    }
+
+    /// Iterates over the elements depth first, children before their
+    /// parents.
+    pub fn postorder(&self) -> Postorder<'_, N> {
+        Postorder(DepthFirst::new(self))
+    }
+
+    /// Iterates over the elements breadth first, one level at a time.
+    pub fn level_order(&self) -> LevelOrder<'_, N> {
+        LevelOrder::new(self)
+    }
+
+    /// Iterates over the elements of the nodes without children, from
+    /// left to right.
+    pub fn leaves(&self) -> Leaves<'_, N> {
+        Leaves(DepthFirst::new(self))
+    }
+
+    /// Builds a tree from a seed. `f` turns a seed into an element and
+    /// the seeds of its children.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::tree::Tree;
+    ///
+    /// // a complete binary tree of depth 2
+    /// let tree = Tree::unfold(0, |depth| {
+    ///     let children = if depth < 2 { vec![depth + 1; 2] } else { vec![] };
+    ///     (depth, children)
+    /// });
+    ///
+    /// assert_eq!(tree.preorder().count(), 7);
+    /// ```
+    pub fn unfold<S, I, F>(seed: S, mut f: F) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        F: FnMut(S) -> (N::ValueType, I),
+    {
+        let (element, seeds) = f(seed);
+        let mut stack = vec![(element, seeds.into_iter(), Vec::new())];
+        loop {
+            let top = stack.last_mut().expect("the root is popped last");
+            match top.1.next() {
+                Some(seed) => {
+                    let (element, seeds) = f(seed);
+                    stack.push((element, seeds.into_iter(), Vec::new()));
+                }
+                None => {
+                    let (element, _, children) = stack.pop().expect("not empty");
+                    let tree = Tree::new(element, list_from(children));
+                    match stack.last_mut() {
+                        Some(parent) => parent.2.push(tree),
+                        None => return tree,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns a tree of the same shape, on any kind of link, with `f`
+    /// applied to every element in preorder.
+    pub(crate) fn mapped<M, F>(&self, mut f: F) -> Tree<M>
+    where
+        M: TreeLink,
+        F: FnMut(&N::ValueType) -> M::ValueType,
+    {
+        self.fold_subtrees(
+            |tree| Some(f(tree.element())),
+            |element, children| Tree::new(element, list_from(children)),
+            || Some(Tree::empty()),
+        )
+        .unwrap_or_default()
+    }
+
+    /// Folds the tree bottom up, passing `f` the results for the
+    /// non-empty children in order.
+    pub(crate) fn folded<R, F>(&self, f: F) -> Option<R>
+    where
+        F: FnMut(&N::ValueType, Vec<R>) -> R,
+    {
+        self.fold_subtrees(|tree| Some(tree.element()), f, || None)
+    }
+
+    /// Combines two trees node by node with `f`, keeping the shape they
+    /// have in common.
+    pub(crate) fn zipped_with<U, M, F>(&self, other: &Tree<U>, mut f: F) -> Tree<M>
+    where
+        U: TreeLink,
+        M: TreeLink,
+        F: FnMut(&N::ValueType, &U::ValueType) -> M::ValueType,
+    {
+        let (a, b) = match (&self.root, &other.root) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return Tree::empty(),
+        };
+        let mut stack = vec![(
+            f(a.get_element(), b.get_element()),
+            a.children().iter().zip(b.children().iter()),
+            Vec::new(),
+        )];
+        loop {
+            let top = stack.last_mut().expect("the root is popped last");
+            match top.1.next() {
+                Some((a, b)) => match (&a.root, &b.root) {
+                    (Some(a), Some(b)) => stack.push((
+                        f(a.get_element(), b.get_element()),
+                        a.children().iter().zip(b.children().iter()),
+                        Vec::new(),
+                    )),
+                    _ => top.2.push(Tree::empty()),
+                },
+                None => {
+                    let (element, _, children) = stack.pop().expect("not empty");
+                    let tree = Tree::new(element, list_from(children));
+                    match stack.last_mut() {
+                        Some(parent) => parent.2.push(tree),
+                        None => return tree,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Rebuilds the tree on another kind of link, converting each
+    /// element with `f`.
+    ///
+    /// `memo` maps the nodes already converted to their copies, so a
+    /// subtree or a tail of a child list that is shared in `self` is
+    /// converted once and shared in the result as well.
+    pub(crate) fn converted<M, F>(&self, memo: &mut ConversionMemo<M>, mut f: F) -> Tree<M>
+    where
+        M: TreeLink,
+        F: FnMut(&N::ValueType) -> M::ValueType,
+    {
+        let root = match &self.root {
+            None => return Tree::empty(),
+            Some(root) => root,
+        };
+        let mut stack = Vec::new();
+        if !memo.trees.contains_key(&root.as_ptr()) {
+            stack.push((root, root.children().iter()));
+        }
+        while let Some((node, children)) = stack.last_mut() {
+            let trees = &memo.trees;
+            let next = children.find_map(|child| {
+                child
+                    .root
+                    .as_ref()
+                    .filter(|child| !trees.contains_key(&child.as_ptr()))
+            });
+            match next {
+                Some(child) => stack.push((child, child.children().iter())),
+                None => {
+                    let node = *node;
+                    stack.pop();
+                    let trees = &memo.trees;
+                    let children = node.children().converted(&mut memo.lists, |child| Tree {
+                        root: child
+                            .root
+                            .as_ref()
+                            .map(|node| trees[&node.as_ptr()].clone()),
+                    });
+                    let copy = M::node(f(node.get_element()), children);
+                    memo.trees.insert(node.as_ptr(), copy);
+                }
+            }
+        }
+        Tree {
+            root: Some(memo.trees[&root.as_ptr()].clone()),
+        }
+    }
+
+    /// The element of a tree known not to be empty.
+    fn element(&self) -> &N::ValueType {
+        self.root.as_ref().expect("not empty").get_element()
+    }
+
+    /// Folds the non-empty subtrees bottom up with an explicit stack.
+    ///
+    /// `enter` is called on every subtree in preorder and may return
+    /// `None` to skip it and everything below it. `leave` gets the state
+    /// returned by `enter` and the results for the children, in order;
+    /// `empty` provides the result for an empty child, if any.
+    fn fold_subtrees<'a, S, R>(
+        &'a self,
+        mut enter: impl FnMut(&'a Self) -> Option<S>,
+        mut leave: impl FnMut(S, Vec<R>) -> R,
+        mut empty: impl FnMut() -> Option<R>,
+    ) -> Option<R> {
+        let node = self.root.as_ref()?;
+        let mut stack = vec![(enter(self)?, node.children().iter(), Vec::new())];
+        loop {
+            let top = stack.last_mut().expect("the root is popped last");
+            match top.1.next() {
+                Some(child) => match &child.root {
+                    Some(node) => {
+                        if let Some(state) = enter(child) {
+                            stack.push((state, node.children().iter(), Vec::new()));
+                        }
+                    }
+                    None => top.2.extend(empty()),
+                },
+                None => {
+                    let (state, _, children) = stack.pop().expect("not empty");
+                    let result = leave(state, children);
+                    match stack.last_mut() {
+                        Some(parent) => parent.2.push(result),
+                        None => return Some(result),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the subtree reached by following `path`, the indices of
+    /// the children to descend into from the root, or `None` if there
+    /// is no node there.
+    ///
+    /// Complexity: O(sum of the indices in `path`)
+    pub fn subtree_at(&self, path: &[usize]) -> Option<&Self> {
+        let mut tree = self;
+        for &index in path {
+            tree = tree.children()?.iter().nth(index)?;
+        }
+        if tree.is_empty() {
+            None
+        } else {
+            Some(tree)
+        }
+    }
+
+    /// Provides a reference to the element at `path`, or `None` if there
+    /// is no node there.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{tree::Tree, List};
+    ///
+    /// let child = Tree::new(2, List::from_value(Tree::leaf(3)));
+    /// let tree = Tree::new(1, List::from_value(child));
+    ///
+    /// assert_eq!(tree.get_at(&[]), Some(&1));
+    /// assert_eq!(tree.get_at(&[0, 0]), Some(&3));
+    /// assert_eq!(tree.get_at(&[1]), None);
+    /// ```
+    pub fn get_at(&self, path: &[usize]) -> Option<&N::ValueType> {
+        self.subtree_at(path)?.root()
+    }
+}
+
+impl<N> Tree<N>
+where
+    N: TreeLink,
+    N::ValueType: Clone,
+{
+    /// Returns the tree with the element at `path` replaced by the
+    /// result of `f`.
+    ///
+    /// Only the ancestors of the node are copied, every other subtree is
+    /// shared with `self`, including the children of the node.
+    ///
+    /// Complexity: O(sum of the indices in `path`)
+    ///
+    /// # Panics
+    ///
+    /// This method panics if there is no node at `path`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{tree::Tree, List};
+    ///
+    /// let t1 = Tree::new(1, List::new().pushed_front(Tree::leaf(3)).pushed_front(Tree::leaf(2)));
+    /// let t2 = t1.updated_at(&[1], |x| x * 10);
+    ///
+    /// assert!(t2.preorder().copied().eq([1, 2, 30]));
+    /// assert!(t1.preorder().copied().eq([1, 2, 3]));
+    /// ```
+    pub fn updated_at<F>(&self, path: &[usize], f: F) -> Self
+    where
+        F: FnOnce(&N::ValueType) -> N::ValueType,
+    {
+        self.replaced_at(path, |tree| {
+            let node = node_at(tree);
+            Some(Tree::new(f(node.get_element()), node.children().clone()))
+        })
+    }
+
+    /// Returns the tree with `subtree` inserted as the child at position
+    /// `index` of the node at `path`.
+    ///
+    /// Only the node and its ancestors are copied.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if there is no node at `path`, or if `index`
+    /// is greater than its number of children.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{tree::Tree, List};
+    ///
+    /// let tree = Tree::new(1, List::from_value(Tree::leaf(3)));
+    /// let tree = tree.inserted_child_at(&[], 0, Tree::leaf(2));
+    ///
+    /// assert!(tree.preorder().copied().eq([1, 2, 3]));
+    /// ```
+    pub fn inserted_child_at(&self, path: &[usize], index: usize, subtree: Self) -> Self {
+        self.replaced_at(path, |tree| {
+            let node = node_at(tree);
+            let children = spliced(node.children(), index, 0, Some(subtree));
+            Some(Tree::new(node.get_element().clone(), children))
+        })
+    }
+
+    /// Returns the tree without the subtree at `path`; the following
+    /// siblings move one position to the front. Removing at the empty
+    /// path gives an empty tree.
+    ///
+    /// Only the ancestors of the subtree are copied, every other subtree
+    /// is shared with `self`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if there is no subtree at `path`.
+    pub fn removed_at(&self, path: &[usize]) -> Self {
+        self.replaced_at(path, |_| None)
+    }
+
+    /// Returns the tree without the subtrees whose root doesn't satisfy
+    /// `p`. If the root itself doesn't, the result is empty.
+    ///
+    /// Subtrees that lose no nodes are shared with `self`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{tree::Tree, List};
+    ///
+    /// let child = Tree::new(2, List::from_value(Tree::leaf(4)));
+    /// let tree = Tree::new(1, List::new().pushed_front(Tree::leaf(3)).pushed_front(child));
+    /// let odd = tree.filter_prune(|x| x % 2 == 1);
+    ///
+    /// assert!(odd.preorder().copied().eq([1, 3]));
+    /// ```
+    pub fn filter_prune<P>(&self, mut p: P) -> Self
+    where
+        P: FnMut(&N::ValueType) -> bool,
+    {
+        self.fold_subtrees(
+            |tree| if p(tree.element()) { Some(tree) } else { None },
+            |tree, children| {
+                let original = tree.children().expect("not empty");
+                let unchanged = original.iter().count() == children.len()
+                    && original.iter().zip(&children).all(|(a, b)| a.ptr_eq(b));
+                if unchanged {
+                    tree.clone()
+                } else {
+                    Tree::new(tree.element().clone(), list_from(children))
+                }
+            },
+            || Some(Tree::empty()),
+        )
+        .unwrap_or_default()
+    }
+
+    /// Replaces the subtree at `path` with the result of `f`, which
+    /// removes it when `None`, and copies the ancestors.
+    fn replaced_at<F>(&self, path: &[usize], f: F) -> Self
+    where
+        F: FnOnce(&Self) -> Option<Self>,
+    {
+        let mut ancestors = Vec::with_capacity(path.len());
+        let mut tree = self;
+        for &index in path {
+            let child = tree
+                .children()
+                .and_then(|children| children.iter().nth(index));
+            match child {
+                None => panic!("There is no node at this path!"),
+                Some(child) => {
+                    ancestors.push(tree);
+                    tree = child;
+                }
+            }
+        }
+        let mut result = f(tree);
+        for (ancestor, &index) in ancestors.into_iter().zip(path).rev() {
+            let node = node_at(ancestor);
+            let children = spliced(node.children(), index, 1, result);
+            result = Some(Tree::new(node.get_element().clone(), children));
+        }
+        result.unwrap_or_default()
+    }
+}
+
+impl<N> Tree<N>
+where
+    N: TreeLink,
+    N::ValueType: Clone + PartialEq,
+{
+    /// Computes an edit script that turns `old` into `new`, to be
+    /// replayed with `apply`.
+    ///
+    /// Subtrees that `old` and `new` share are skipped without being
+    /// visited. The children of two compared nodes are aligned with a
+    /// longest common subsequence that prefers shared subtrees over
+    /// subtrees that merely have equal roots. A shared subtree that is out
+    /// of order among its siblings becomes a `Move`, while moves between
+    /// different parents are reported as a `Delete` and an `Insert`.
+    /// Children left over between two aligned ones are paired up in
+    /// order, and all pairs are compared in turn.
+    ///
+    /// Complexity: O(m·n) for every pair of child lists of length m and
+    /// n that are compared
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{
+    ///     tree::{Tree, TreeEdit},
+    ///     List,
+    /// };
+    ///
+    /// let old = Tree::new(1, List::new().pushed_front(Tree::leaf(3)).pushed_front(Tree::leaf(2)));
+    /// let new = old.updated_at(&[1], |_| 4).inserted_child_at(&[], 0, Tree::leaf(0));
+    /// let edits = Tree::diff(&old, &new);
+    ///
+    /// assert_eq!(edits.len(), 2);
+    /// assert!(matches!(&edits[0], TreeEdit::Insert { path, .. } if path == &[0]));
+    /// assert!(matches!(&edits[1], TreeEdit::Relabel { path, element: 4 } if path == &[2]));
+    /// assert_eq!(old.apply(&edits), new);
+    /// ```
+    pub fn diff(old: &Self, new: &Self) -> Vec<TreeEdit<N>> {
+        let mut edits = Vec::new();
+        let mut stack = match (&old.root, &new.root) {
+            (None, None) => return edits,
+            (Some(_), None) => {
+                edits.push(TreeEdit::Delete { path: Vec::new() });
+                return edits;
+            }
+            (None, Some(_)) => {
+                edits.push(TreeEdit::Insert {
+                    path: Vec::new(),
+                    tree: new.clone(),
+                });
+                return edits;
+            }
+            (Some(_), Some(_)) => vec![(old, new, Vec::new())],
+        };
+        while let Some((old, new, path)) = stack.pop() {
+            if old.ptr_eq(new) {
+                continue;
+            }
+            let (a, b) = (node_at(old), node_at(new));
+            if a.get_element() != b.get_element() {
+                edits.push(TreeEdit::Relabel {
+                    path: path.clone(),
+                    element: b.get_element().clone(),
+                });
+            }
+            let olds: Vec<_> = a.children().iter().collect();
+            let news: Vec<_> = b.children().iter().collect();
+            let sources = child_sources(&olds, &news);
+            let at = |index: usize| {
+                let mut child = path.clone();
+                child.push(index);
+                child
+            };
+
+            // What is left of the old children, by their old index.
+            let mut current: Vec<usize> = (0..olds.len()).collect();
+            let mut used = vec![false; olds.len()];
+            for source in &sources {
+                if let Source::Kept(i) | Source::Moved(i) = *source {
+                    used[i] = true;
+                }
+            }
+            for i in (0..olds.len()).rev() {
+                if !used[i] {
+                    edits.push(TreeEdit::Delete { path: at(i) });
+                    current.remove(i);
+                }
+            }
+
+            // Each moved child goes right after its nearest predecessor
+            // among the children that are already in order.
+            let position = |current: &[usize], i| {
+                current
+                    .iter()
+                    .position(|&x| x == i)
+                    .expect("child is present")
+            };
+            let mut predecessor = None;
+            for source in &sources {
+                match *source {
+                    Source::Kept(i) => predecessor = Some(i),
+                    Source::Moved(i) => {
+                        let from = position(&current, i);
+                        current.remove(from);
+                        let to = predecessor.map_or(0, |p| position(&current, p) + 1);
+                        current.insert(to, i);
+                        if from != to {
+                            edits.push(TreeEdit::Move {
+                                from: at(from),
+                                to: at(to),
+                            });
+                        }
+                        predecessor = Some(i);
+                    }
+                    Source::Inserted => {}
+                }
+            }
+
+            for (j, source) in sources.iter().enumerate() {
+                match *source {
+                    Source::Inserted => edits.push(TreeEdit::Insert {
+                        path: at(j),
+                        tree: news[j].clone(),
+                    }),
+                    Source::Kept(i) => stack.push((olds[i], news[j], at(j))),
+                    Source::Moved(_) => {}
+                }
+            }
+        }
+        edits
+    }
+
+    /// Returns the tree with `edits` applied in order, as produced by
+    /// `diff`.
+    ///
+    /// Inserted subtrees are shared with the edits, and every subtree
+    /// that no edit touches is shared with `self`, so applying
+    /// `Tree::diff(&old, &new)` to `old` gives a tree equal to `new` that
+    /// shares as much as possible with both.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if an edit addresses a node that doesn't exist,
+    /// inserts a root into a tree that has one, or moves the root.
+    pub fn apply(&self, edits: &[TreeEdit<N>]) -> Self {
+        let mut tree = self.clone();
+        for edit in edits {
+            tree = match edit {
+                TreeEdit::Insert {
+                    path,
+                    tree: subtree,
+                } => match path.split_last() {
+                    None if tree.is_empty() => subtree.clone(),
+                    None => panic!("There is already a root!"),
+                    Some((&index, parent)) => {
+                        tree.inserted_child_at(parent, index, subtree.clone())
+                    }
+                },
+                TreeEdit::Delete { path } => tree.removed_at(path),
+                TreeEdit::Relabel { path, element } => tree.updated_at(path, |_| element.clone()),
+                TreeEdit::Move { from, to } => {
+                    let (&index, parent) = match to.split_last() {
+                        Some(last) if !from.is_empty() => last,
+                        _ => panic!("The root can't be moved!"),
+                    };
+                    let subtree = match tree.subtree_at(from) {
+                        None => panic!("There is no node at this path!"),
+                        Some(subtree) => subtree.clone(),
+                    };
+                    tree.removed_at(from)
+                        .inserted_child_at(parent, index, subtree)
+                }
+            };
+        }
+        tree
+    }
+}
+
+/// One step of an edit script computed by `Tree::diff`.
+///
+/// A path lists the child indices to descend into from the root, and
+/// addresses the tree as it is when the step is applied, after all the
+/// steps before it.
+pub enum TreeEdit<N: TreeLink> {
+    /// Inserts `tree` so that it ends up at `path`. The empty path
+    /// inserts the root of an empty tree.
+    Insert { path: Vec<usize>, tree: Tree<N> },
+    /// Removes the subtree at `path`.
+    Delete { path: Vec<usize> },
+    /// Replaces the element at `path`, keeping its children.
+    Relabel {
+        path: Vec<usize>,
+        element: N::ValueType,
+    },
+    /// Removes the subtree at `from` and inserts it at `to`, which
+    /// addresses the tree after the removal.
+    Move { from: Vec<usize>, to: Vec<usize> },
+}
+
+impl<N> Clone for TreeEdit<N>
+where
+    N: TreeLink,
+    N::ValueType: Clone,
+{
+    fn clone(&self) -> Self {
+        match self {
+            TreeEdit::Insert { path, tree } => TreeEdit::Insert {
+                path: path.clone(),
+                tree: tree.clone(),
+            },
+            TreeEdit::Delete { path } => TreeEdit::Delete { path: path.clone() },
+            TreeEdit::Relabel { path, element } => TreeEdit::Relabel {
+                path: path.clone(),
+                element: element.clone(),
+            },
+            TreeEdit::Move { from, to } => TreeEdit::Move {
+                from: from.clone(),
+                to: to.clone(),
+            },
+        }
+    }
+}
+
+impl<N> PartialEq for TreeEdit<N>
+where
+    N: TreeLink,
+    N::ValueType: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TreeEdit::Insert { path: p, tree: t }, TreeEdit::Insert { path: q, tree: u }) => {
+                p == q && t == u
+            }
+            (TreeEdit::Delete { path: p }, TreeEdit::Delete { path: q }) => p == q,
+            (
+                TreeEdit::Relabel {
+                    path: p,
+                    element: x,
+                },
+                TreeEdit::Relabel {
+                    path: q,
+                    element: y,
+                },
+            ) => p == q && x == y,
+            (TreeEdit::Move { from: f, to: t }, TreeEdit::Move { from: g, to: u }) => {
+                f == g && t == u
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<N> Debug for TreeEdit<N>
+where
+    N: TreeLink,
+    N::ValueType: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TreeEdit::Insert { path, tree } => f
+                .debug_struct("Insert")
+                .field("path", path)
+                .field("tree", tree)
+                .finish(),
+            TreeEdit::Delete { path } => f.debug_struct("Delete").field("path", path).finish(),
+            TreeEdit::Relabel { path, element } => f
+                .debug_struct("Relabel")
+                .field("path", path)
+                .field("element", element)
+                .finish(),
+            TreeEdit::Move { from, to } => f
+                .debug_struct("Move")
+                .field("from", from)
+                .field("to", to)
+                .finish(),
+        }
+    }
+}
+
+/// Where a child in the new list of children comes from.
+enum Source {
+    /// The old child at this index, compared with the new one.
+    Kept(usize),
+    /// The old child at this index, shared but out of order.
+    Moved(usize),
+    Inserted,
+}
+
+/// Aligns two lists of children with a longest common subsequence, in
+/// which a shared subtree counts twice as much as a subtree with an
+/// equal root. Shared subtrees left over become moves, and the rest are
+/// paired up in order within each gap of the alignment.
+fn child_sources<N>(old: &[&Tree<N>], new: &[&Tree<N>]) -> Vec<Source>
+where
+    N: TreeLink,
+    N::ValueType: PartialEq,
+{
+    let weight = |a: &Tree<N>, b: &Tree<N>| {
+        if a.ptr_eq(b) {
+            2
+        } else if a.root() == b.root() {
+            1
+        } else {
+            0
+        }
+    };
+    let (m, n) = (old.len(), new.len());
+    // best[i * (n + 1) + j] is the best score for old[i..] and new[j..].
+    let mut best = vec![0usize; (m + 1) * (n + 1)];
+    for i in (0..m).rev() {
+        for j in (0..n).rev() {
+            let skip = max(best[(i + 1) * (n + 1) + j], best[i * (n + 1) + j + 1]);
+            let w = weight(old[i], new[j]);
+            best[i * (n + 1) + j] = if w > 0 {
+                max(skip, w + best[(i + 1) * (n + 1) + j + 1])
+            } else {
+                skip
+            };
+        }
+    }
+
+    let mut sources: Vec<Source> = new.iter().map(|_| Source::Inserted).collect();
+    let mut used = vec![false; m];
+    let (mut i, mut j) = (0, 0);
+    while i < m && j < n {
+        let w = weight(old[i], new[j]);
+        if w > 0 && best[i * (n + 1) + j] == w + best[(i + 1) * (n + 1) + j + 1] {
+            sources[j] = Source::Kept(i);
+            used[i] = true;
+            i += 1;
+            j += 1;
+        } else if best[i * (n + 1) + j] == best[(i + 1) * (n + 1) + j] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    for (j, source) in sources.iter_mut().enumerate() {
+        if let Source::Inserted = source {
+            let shared = (0..m).find(|&i| !used[i] && old[i].ptr_eq(new[j]));
+            if let Some(i) = shared {
+                *source = Source::Moved(i);
+                used[i] = true;
+            }
+        }
+    }
+
+    // Pair up what is left between two aligned children, so that a
+    // changed child is compared rather than deleted and inserted.
+    let mut next_old = 0;
+    for j in 0..n {
+        match sources[j] {
+            Source::Kept(i) => next_old = i + 1,
+            Source::Moved(_) => {}
+            Source::Inserted => {
+                let limit = sources[j..]
+                    .iter()
+                    .find_map(|source| match *source {
+                        Source::Kept(i) => Some(i),
+                        _ => None,
+                    })
+                    .unwrap_or(m);
+                if let Some(i) = (next_old..limit).find(|&i| !used[i]) {
+                    sources[j] = Source::Kept(i);
+                    used[i] = true;
+                    next_old = i + 1;
+                }
+            }
+        }
+    }
+    sources
+}
+
+fn node_at<N>(tree: &Tree<N>) -> &N {
+    match &tree.root {
+        None => panic!("There is no node at this path!"),
+        Some(node) => node,
+    }
+}
+
+/// Returns `list` with `remove` elements at `index` replaced by `insert`.
+/// The elements after them are shared.
+fn spliced<L>(list: &List<L>, index: usize, remove: usize, insert: Option<L::ValueType>) -> List<L>
+where
+    L: Link,
+    L::ValueType: Clone,
+{
+    let mut prefix = Vec::with_capacity(index);
+    let mut rest = list.clone();
+    for _ in 0..index {
+        match rest.front() {
+            None => panic!("index out of bounds"),
+            Some(x) => prefix.push(x.clone()),
+        }
+        rest = rest.popped_front();
+    }
+    for _ in 0..remove {
+        if rest.is_empty() {
+            panic!("index out of bounds");
+        }
+        rest = rest.popped_front();
+    }
+    if let Some(x) = insert {
+        rest = rest.pushed_front(x);
+    }
+    for x in prefix.into_iter().rev() {
+        rest = rest.pushed_front(x);
+    }
+    rest
+}
+
+pub(crate) fn list_from<L: Link>(elements: Vec<L::ValueType>) -> List<L> {
+    let mut list = List::new();
+    for x in elements.into_iter().rev() {
+        list = list.pushed_front(x);
+    }
+    list
+}
+
+/// Unlinks uniquely owned descendants one child list at a time, so
+/// that dropping a very deep tree doesn't overflow the stack.
+pub(crate) fn drop_children<N: TreeLink>(children: List<N::ListLink>) {
+    let mut stack = vec![children];
+    while let Some(list) = stack.pop() {
+        let trees: Vec<_> = list.iter().cloned().collect();
+        drop(list);
+        for tree in trees {
+            if let Some(children) = tree.root.and_then(N::try_unwrap_children) {
+                stack.push(children);
+            }
+        }
+    }
+}
+
+/// The copies made while converting trees to another kind of link,
+/// keyed by the address of the original nodes.
+pub(crate) struct ConversionMemo<M: TreeLink> {
+    trees: BTreeMap<*const (), M>,
+    lists: BTreeMap<*const (), M::ListLink>,
+}
+
+impl<M: TreeLink> ConversionMemo<M> {
+    pub(crate) fn new() -> Self {
+        ConversionMemo {
+            trees: BTreeMap::new(),
+            lists: BTreeMap::new(),
+        }
+    }
+}
+
+impl<N: TreeLink> Clone for Tree<N> {
+    fn clone(&self) -> Self {
+        Tree {
+            root: self.root.as_ref().map(N::clone),
+        }
+    }
+}
+
+impl<N> PartialEq for Tree<N>
+where
+    N: TreeLink,
+    N::ValueType: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (None, None) => true,
+            (Some(x), Some(y)) => {
+                x.ptr_eq(y) || (x.get_element() == y.get_element() && x.children() == y.children())
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<N> Tree<N>
+where
+    N: TreeLink,
+    N::ValueType: Debug,
+{
+    /// Draws the tree as an outline, one element per line, with the
+    /// children of each node below it.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{tree::Tree, List};
+    ///
+    /// let tree = Tree::new(1, List::from_value(Tree::leaf(2)));
+    ///
+    /// assert_eq!(tree.pretty(), "1\n└── 2");
+    /// ```
+    pub fn pretty(&self) -> String {
+        draw::pretty(self.root.as_ref().map(DrawNode))
+    }
+
+    /// Draws `versions` as one Graphviz digraph, with a `v<i>` label
+    /// pointing at the root of each. Nodes shared between versions are
+    /// drawn once.
+    pub fn to_dot(versions: &[&Self]) -> String {
+        draw::dot(
+            versions
+                .iter()
+                .map(|tree| tree.root.as_ref().map(DrawNode))
+                .collect(),
+        )
+    }
+}
+
+struct DrawNode<'a, N>(&'a N);
+
+impl<'a, N> Draw for DrawNode<'a, N>
+where
+    N: TreeLink,
+    N::ValueType: Debug,
+{
+    type Element = N::ValueType;
+
+    const BINARY: bool = false;
+
+    fn id(&self) -> *const () {
+        self.0.as_ptr()
+    }
+    fn element(&self) -> &Self::Element {
+        self.0.get_element()
+    }
+    fn children(&self) -> Vec<Option<Self>> {
+        self.0
+            .children()
+            .iter()
+            .filter_map(|child| child.root.as_ref())
+            .map(|node| Some(DrawNode(node)))
+            .collect()
+    }
+}
+
+impl<N> Debug for Tree<N>
+where
+    N: TreeLink,
+    N::ValueType: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.root {
+            None => f.write_str("Tree"),
+            Some(node) => f
+                .debug_struct("Tree")
+                .field("element", node.get_element())
+                .field("children", node.children())
+                .finish(),
+        }
+    }
+}
+
+mod sealed {
+    use alloc::vec::Vec;
+
+    pub trait Position {
+        fn depth(&self) -> usize;
+        fn path(&self) -> Vec<usize>;
+    }
+}
+
+use sealed::Position;
+
+type Children<'a, N> = Enumerate<list::Iter<'a, <N as TreeLink>::ListLink>>;
+
+struct Frame<'a, N: TreeLink> {
+    node: &'a N,
+    index: usize,
+    children: Children<'a, N>,
+}
+
+impl<'a, N: TreeLink> Frame<'a, N> {
+    fn new(node: &'a N, index: usize) -> Self {
+        Frame {
+            node,
+            index,
+            children: node.children().iter().enumerate(),
+        }
+    }
+}
+
+enum Step<'a, N> {
+    Enter(&'a N),
+    Leave(&'a N),
+}
+
+/// Walks a tree depth first, reporting when a node is entered and left.
+///
+/// The frame of a node stays on the stack until the step after it is
+/// left, so `depth` and `path` describe the node of the last step.
+struct DepthFirst<'a, N: TreeLink> {
+    root: Option<&'a N>,
+    stack: Vec<Frame<'a, N>>,
+    leaving: bool,
+}
+
+impl<'a, N: TreeLink> DepthFirst<'a, N> {
+    fn new(tree: &'a Tree<N>) -> Self {
+        DepthFirst {
+            root: tree.root.as_ref(),
+            stack: Vec::new(),
+            leaving: false,
+        }
+    }
+
+    fn step(&mut self) -> Option<Step<'a, N>> {
+        if self.leaving {
+            self.stack.pop();
+            self.leaving = false;
+        }
+        if let Some(node) = self.root.take() {
+            self.stack.push(Frame::new(node, 0));
+            return Some(Step::Enter(node));
+        }
+        let frame = self.stack.last_mut()?;
+        let child = frame
+            .children
+            .find_map(|(index, child)| child.root.as_ref().map(|node| (index, node)));
+        match child {
+            Some((index, node)) => {
+                self.stack.push(Frame::new(node, index));
+                Some(Step::Enter(node))
+            }
+            None => {
+                self.leaving = true;
+                Some(Step::Leave(frame.node))
+            }
+        }
+    }
+}
+
+impl<'a, N: TreeLink> Position for DepthFirst<'a, N> {
+    fn depth(&self) -> usize {
+        self.stack.len().saturating_sub(1)
+    }
+
+    fn path(&self) -> Vec<usize> {
+        self.stack.iter().skip(1).map(|frame| frame.index).collect()
+    }
+}
+
+/// Iterator returned by [`Tree::preorder`].
+pub struct Preorder<'a, N: TreeLink>(DepthFirst<'a, N>);
+
+impl<'a, N: TreeLink> Iterator for Preorder<'a, N> {
+    type Item = &'a N::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Step::Enter(node) = self.0.step()? {
+                return Some(node.get_element());
+            }
+        }
+    }
+}
+
+/// Iterator returned by [`Tree::postorder`].
+pub struct Postorder<'a, N: TreeLink>(DepthFirst<'a, N>);
+
+impl<'a, N: TreeLink> Iterator for Postorder<'a, N> {
+    type Item = &'a N::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Step::Leave(node) = self.0.step()? {
+                return Some(node.get_element());
+            }
+        }
+    }
+}
+
+/// Iterator returned by [`Tree::leaves`].
+pub struct Leaves<'a, N: TreeLink>(DepthFirst<'a, N>);
+
+impl<'a, N: TreeLink> Iterator for Leaves<'a, N> {
+    type Item = &'a N::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Step::Enter(node) = self.0.step()? {
+                if node.children().iter().all(Tree::is_empty) {
+                    return Some(node.get_element());
+                }
+            }
+        }
+    }
+}
+
+macro_rules! depth_first_position {
+    ($($iter:ident),*) => {
+        $(
+            impl<'a, N: TreeLink> Position for $iter<'a, N> {
+                fn depth(&self) -> usize {
+                    self.0.depth()
+                }
+
+                fn path(&self) -> Vec<usize> {
+                    self.0.path()
+                }
+            }
+        )*
+    };
+}
+
+depth_first_position!(Preorder, Postorder, Leaves);
+
+/// Iterator returned by [`Tree::level_order`].
+///
+/// Besides the queue of nodes still to visit, it remembers the parent
+/// and child index of every node it has queued, to be able to report
+/// paths.
+pub struct LevelOrder<'a, N> {
+    queue: VecDeque<(&'a N, usize, usize)>,
+    parents: Vec<(usize, usize)>,
+    current: Option<(usize, usize)>,
+}
+
+impl<'a, N: TreeLink> LevelOrder<'a, N> {
+    fn new(tree: &'a Tree<N>) -> Self {
+        let mut queue = VecDeque::new();
+        let mut parents = Vec::new();
+        if let Some(node) = &tree.root {
+            queue.push_back((node, 0, 0));
+            parents.push((0, 0));
+        }
+        LevelOrder {
+            queue,
+            parents,
+            current: None,
+        }
+    }
+}
+
+impl<'a, N: TreeLink> Iterator for LevelOrder<'a, N> {
+    type Item = &'a N::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, depth, id) = self.queue.pop_front()?;
+        for (index, child) in node.children().iter().enumerate() {
+            if let Some(child) = &child.root {
+                self.queue.push_back((child, depth + 1, self.parents.len()));
+                self.parents.push((id, index));
+            }
+        }
+        self.current = Some((depth, id));
+        Some(node.get_element())
+    }
+}
+
+impl<'a, N: TreeLink> Position for LevelOrder<'a, N> {
+    fn depth(&self) -> usize {
+        self.current.map_or(0, |(depth, _)| depth)
+    }
+
+    fn path(&self) -> Vec<usize> {
+        let (depth, mut id) = match self.current {
+            None => return Vec::new(),
+            Some(current) => current,
+        };
+        let mut path = Vec::with_capacity(depth);
+        for _ in 0..depth {
+            let (parent, index) = self.parents[id];
+            path.push(index);
+            id = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+macro_rules! positioned {
+    ($($iter:ident),*) => {
+        $(
+            impl<'a, N: TreeLink> $iter<'a, N> {
+                /// Pairs every element with its depth, the root being at
+                /// depth 0.
+                pub fn with_depth(self) -> WithDepth<Self> {
+                    WithDepth(self)
+                }
+
+                /// Pairs every element with its path, the indices of the
+                /// children to follow from the root to reach it.
+                pub fn with_paths(self) -> WithPaths<Self> {
+                    WithPaths(self)
+                }
+            }
+        )*
+    };
+}
+
+positioned!(Preorder, Postorder, LevelOrder, Leaves);
+
+/// Iterator adapter yielding `(depth, element)` pairs.
+pub struct WithDepth<I>(I);
+
+impl<I: Iterator + Position> Iterator for WithDepth<I> {
+    type Item = (usize, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.0.next()?;
+        Some((self.0.depth(), x))
+    }
+}
+
+/// Iterator adapter yielding `(path, element)` pairs.
+pub struct WithPaths<I>(I);
+
+impl<I: Iterator + Position> Iterator for WithPaths<I> {
+    type Item = (Vec<usize>, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.0.next()?;
+        Some((self.0.path(), x))
+    }
+}
+
+/// A cursor into a `Tree`, after Huet's zipper.
+///
+/// The zipper holds the subtree in focus and, for every ancestor, the
+/// siblings to the left (nearest first) and to the right of the path.
+/// Moving left and right is O(1). The children of a node are a `List`,
+/// so `down(i)` walks past i siblings, and `up` after an edit conses
+/// the i siblings left of the focus back onto the shared right part:
+/// both are O(i). Levels that weren't edited are not rebuilt when
+/// moving up: the original subtree is returned instead, so `to_tree`
+/// shares everything outside the edited paths with the tree the zipper
+/// was created from.
+pub struct TreeZipper<N, C> {
+    focus: Tree<N>,
+    crumbs: List<C>,
+    /// Whether the focus or its siblings differ from the children of
+    /// the parent in the first crumb.
+    modified: bool,
+}
+
+/// The context of one level of a `TreeZipper`.
+pub struct Crumb<N: TreeLink> {
+    parent: Tree<N>,
+    parent_modified: bool,
+    left: List<N::ListLink>,
+    right: List<N::ListLink>,
+}
+
+impl<N, C> Clone for TreeZipper<N, C>
+where
+    N: TreeLink,
+    C: Link<ValueType = Crumb<N>>,
+{
+    fn clone(&self) -> Self {
+        TreeZipper {
+            focus: self.focus.clone(),
+            crumbs: self.crumbs.clone(),
+            modified: self.modified,
+        }
+    }
+}
+
+impl<N, C> TreeZipper<N, C>
+where
+    N: TreeLink,
+    C: Link<ValueType = Crumb<N>>,
+{
+    /// Creates a zipper focused on the root of `tree`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{tree::{Tree, TreeZipper}, List};
+    ///
+    /// let tree = Tree::new(1, List::new().pushed_front(Tree::leaf(3)).pushed_front(Tree::leaf(2)));
+    /// let zipper = TreeZipper::new(&tree).down(0).unwrap().right().unwrap();
+    ///
+    /// assert_eq!(zipper.focus().root(), Some(&3));
+    /// let edited = zipper.replaced(Tree::leaf(30)).to_tree();
+    /// assert!(edited.preorder().copied().eq([1, 2, 30]));
+    /// ```
+    pub fn new(tree: &Tree<N>) -> Self {
+        TreeZipper {
+            focus: tree.clone(),
+            crumbs: List::new(),
+            modified: false,
+        }
+    }
+
+    /// Returns the subtree in focus.
+    pub fn focus(&self) -> &Tree<N> {
+        &self.focus
+    }
+
+    /// Returns `true` if the focus is the root of the tree.
+    pub fn is_root(&self) -> bool {
+        self.crumbs.is_empty()
+    }
+
+    /// Moves the focus to the child at `index`, or returns `None` if
+    /// there is no such child.
+    ///
+    /// Complexity: O(index)
+    pub fn down(&self, index: usize) -> Option<Self> {
+        let mut left = List::new();
+        let mut right = self.focus.children()?.clone();
+        for _ in 0..index {
+            left = left.pushed_front(right.front()?.clone());
+            right = right.popped_front();
+        }
+        let focus = right.front()?.clone();
+        let crumb = Crumb {
+            parent: self.focus.clone(),
+            parent_modified: self.modified,
+            left,
+            right: right.popped_front(),
+        };
+        Some(TreeZipper {
+            focus,
+            crumbs: self.crumbs.pushed_front(crumb),
+            modified: false,
+        })
+    }
+
+    /// Moves the focus to the sibling on the left, or returns `None` if
+    /// there is none.
+    ///
+    /// Complexity: O(1)
+    pub fn left(&self) -> Option<Self> {
+        let crumb = self.crumbs.front()?;
+        let focus = crumb.left.front()?.clone();
+        let crumb = Crumb {
+            parent: crumb.parent.clone(),
+            parent_modified: crumb.parent_modified,
+            left: crumb.left.popped_front(),
+            right: crumb.right.pushed_front(self.focus.clone()),
+        };
+        Some(TreeZipper {
+            focus,
+            crumbs: self.crumbs.popped_front().pushed_front(crumb),
+            modified: self.modified,
+        })
+    }
+
+    /// Moves the focus to the sibling on the right, or returns `None` if
+    /// there is none.
+    ///
+    /// Complexity: O(1)
+    pub fn right(&self) -> Option<Self> {
+        let crumb = self.crumbs.front()?;
+        let focus = crumb.right.front()?.clone();
+        let crumb = Crumb {
+            parent: crumb.parent.clone(),
+            parent_modified: crumb.parent_modified,
+            left: crumb.left.pushed_front(self.focus.clone()),
+            right: crumb.right.popped_front(),
+        };
+        Some(TreeZipper {
+            focus,
+            crumbs: self.crumbs.popped_front().pushed_front(crumb),
+            modified: self.modified,
+        })
+    }
+
+    /// Returns a zipper with the focus replaced by `tree`.
+    ///
+    /// Complexity: O(1)
+    pub fn replaced(&self, tree: Tree<N>) -> Self {
+        TreeZipper {
+            focus: tree,
+            crumbs: self.crumbs.clone(),
+            modified: true,
+        }
+    }
+
+    /// Returns a zipper with `tree` inserted as the sibling just left of
+    /// the focus. The focus doesn't move.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Panics
+    ///
+    /// This method panics when the focus is the root.
+    pub fn inserted_left(&self, tree: Tree<N>) -> Self {
+        self.with_siblings(|crumb| (crumb.left.pushed_front(tree), crumb.right.clone()))
+    }
+
+    /// Returns a zipper with `tree` inserted as the sibling just right
+    /// of the focus. The focus doesn't move.
+    ///
+    /// Complexity: O(1)
+    ///
+    /// # Panics
+    ///
+    /// This method panics when the focus is the root.
+    pub fn inserted_right(&self, tree: Tree<N>) -> Self {
+        self.with_siblings(|crumb| (crumb.left.clone(), crumb.right.pushed_front(tree)))
+    }
+
+    fn with_siblings<F>(&self, f: F) -> Self
+    where
+        F: FnOnce(&Crumb<N>) -> (List<N::ListLink>, List<N::ListLink>),
+    {
+        let crumb = match self.crumbs.front() {
+            None => panic!("The root has no siblings!"),
+            Some(crumb) => crumb,
+        };
+        let (left, right) = f(crumb);
+        let crumb = Crumb {
+            parent: crumb.parent.clone(),
+            parent_modified: crumb.parent_modified,
+            left,
+            right,
+        };
+        TreeZipper {
+            focus: self.focus.clone(),
+            crumbs: self.crumbs.popped_front().pushed_front(crumb),
+            modified: true,
+        }
+    }
+}
+
+impl<N, C> TreeZipper<N, C>
+where
+    N: TreeLink,
+    N::ValueType: Clone,
+    C: Link<ValueType = Crumb<N>>,
+{
+    /// Moves the focus to the parent, or returns `None` if the focus is
+    /// the root.
+    ///
+    /// Complexity: O(1) if nothing below the parent was changed,
+    /// otherwise O(number of left siblings), which are copied because a
+    /// `List` can't share a prefix
+    pub fn up(&self) -> Option<Self> {
+        let crumb = self.crumbs.front()?;
+        let crumbs = self.crumbs.popped_front();
+        if !self.modified {
+            return Some(TreeZipper {
+                focus: crumb.parent.clone(),
+                crumbs,
+                modified: crumb.parent_modified,
+            });
+        }
+        let element = crumb.parent.element().clone();
+        let mut children = crumb.right.pushed_front(self.focus.clone());
+        for sibling in &crumb.left {
+            children = children.pushed_front(sibling.clone());
+        }
+        Some(TreeZipper {
+            focus: Tree::new(element, children),
+            crumbs,
+            modified: true,
+        })
+    }
+
+    /// Rebuilds the whole tree.
+    ///
+    /// Complexity: O(depth) plus the siblings left of the edited paths
+    pub fn to_tree(&self) -> Tree<N> {
+        let mut zipper = self.clone();
+        while let Some(parent) = zipper.up() {
+            zipper = parent;
+        }
+        zipper.focus
+    }
+}
+
+impl<N, C> From<&Tree<N>> for TreeZipper<N, C>
+where
+    N: TreeLink,
+    C: Link<ValueType = Crumb<N>>,
+{
+    fn from(tree: &Tree<N>) -> Self {
+        TreeZipper::new(tree)
+    }
+}
+
+impl<N, C> Debug for TreeZipper<N, C>
+where
+    N: TreeLink,
+    N::ValueType: Debug,
+    C: Link<ValueType = Crumb<N>>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TreeZipper")
+            .field("focus", &self.focus)
+            .finish()
+    }
+}
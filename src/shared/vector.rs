@@ -0,0 +1,896 @@
+use alloc::{vec, vec::Vec};
+use core::{
+    cmp::{max, min},
+    fmt::{self, Debug},
+    iter::FromIterator,
+    mem, slice,
+};
+
+/// The number of index bits consumed at each level of the trie.
+const BITS: usize = 5;
+const WIDTH: usize = 1 << BITS;
+/// How many more nodes than the minimum a level may keep after a
+/// concatenation. A higher value rebuilds fewer nodes, at the price of
+/// more scanning when indexing relaxed nodes.
+const EXTRA_STEPS: usize = 2;
+
+pub trait VectorLink: Sized {
+    type ValueType;
+
+    fn new(node: VectorNode<Self>) -> Self;
+    fn clone(&self) -> Self;
+    fn node(&self) -> &VectorNode<Self>;
+    /// Returns `true` if both links point to the same node.
+    fn ptr_eq(&self, other: &Self) -> bool;
+}
+
+/// A node of the trie behind `Vector`.
+pub struct VectorNode<L: VectorLink>(Kind<L>);
+
+enum Kind<L: VectorLink> {
+    Leaf(Vec<L::ValueType>),
+    /// `sizes` holds the number of elements up to and including each
+    /// child. It is left out when every child but the last is full, in
+    /// which case the child holding an index can be computed directly.
+    Branch {
+        children: Vec<L>,
+        sizes: Option<Vec<usize>>,
+    },
+}
+
+/// A persistent vector, implemented as a relaxed radix balanced tree.
+///
+/// The elements are stored in the leaves of a 32-way trie, all at the
+/// same depth, followed by a tail of up to 32 elements. Pushing and
+/// popping at the back only copy the tail, except for every 32nd call
+/// which moves a leaf between tail and trie. Indexing and updating take
+/// O(log32 n) steps, which is at most seven for any vector that fits
+/// into memory.
+///
+/// Nodes are normally full, so the child holding an index follows from
+/// its bits. `appended` and `split_at` can leave nodes partly filled;
+/// these nodes record the sizes of their children and are searched
+/// instead. Appending rebalances the nodes along the seam, so the number
+/// of extra nodes stays bounded and so does the search.
+pub struct Vector<L> {
+    root: Option<L>,
+    height: usize,
+    /// The last elements, as a leaf. Only empty if the vector is.
+    tail: Option<L>,
+    len: usize,
+}
+
+impl<L> Default for Vector<L> {
+    fn default() -> Self {
+        Vector {
+            root: None,
+            height: 0,
+            tail: None,
+            len: 0,
+        }
+    }
+}
+
+impl<L: VectorLink> Clone for Vector<L> {
+    fn clone(&self) -> Self {
+        Vector {
+            root: self.root.as_ref().map(L::clone),
+            height: self.height,
+            tail: self.tail.as_ref().map(L::clone),
+            len: self.len,
+        }
+    }
+}
+
+impl<L: VectorLink> Vector<L> {
+    /// Creates an empty `Vector`.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Vector;
+    ///
+    /// let vector: Vector<u32> = Vector::new();
+    ///
+    /// assert!(vector.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Vector::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements.
+    ///
+    /// Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if it is
+    /// out of bounds.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Vector;
+    ///
+    /// let vector: Vector<_> = (0..100).collect();
+    ///
+    /// assert_eq!(vector.get(42), Some(&42));
+    /// assert_eq!(vector.get(100), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&L::ValueType> {
+        if index >= self.len {
+            return None;
+        }
+        let (elements, offset) = self.leaf_at(index);
+        elements.get(offset)
+    }
+
+    pub fn front(&self) -> Option<&L::ValueType> {
+        self.get(0)
+    }
+
+    /// Complexity: O(1)
+    pub fn back(&self) -> Option<&L::ValueType> {
+        self.tail.as_ref().and_then(|tail| elements(tail).last())
+    }
+
+    /// Returns `true` if both vectors share their nodes, or are both
+    /// empty.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        fn same<L: VectorLink>(a: &Option<L>, b: &Option<L>) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => a.ptr_eq(b),
+                (None, None) => true,
+                _ => false,
+            }
+        }
+
+        same(&self.root, &other.root) && same(&self.tail, &other.tail)
+    }
+
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter {
+            vector: self,
+            leaf: [].iter(),
+            next: 0,
+        }
+    }
+
+    /// Returns the elements of the leaf holding `index`, and the position
+    /// of `index` among them. `index` must be in bounds.
+    fn leaf_at(&self, index: usize) -> (&[L::ValueType], usize) {
+        let tail = elements(self.tail.as_ref().expect("only empty vectors have no tail"));
+        let tree_len = self.len - tail.len();
+        if index >= tree_len {
+            return (tail, index - tree_len);
+        }
+        let mut node = self.root.as_ref().expect("the tree holds all but the tail");
+        let mut index = index;
+        for height in (1..=self.height).rev() {
+            let (child, rest) = locate(node, height, index);
+            node = &children(node)[child];
+            index = rest;
+        }
+        (elements(node), index)
+    }
+}
+
+impl<L> Vector<L>
+where
+    L: VectorLink,
+    L::ValueType: Clone,
+{
+    /// Returns a vector with `value` added at the back.
+    ///
+    /// Complexity: O(1) amortised
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Vector;
+    ///
+    /// let v1 = Vector::new().pushed_back(1);
+    /// let v2 = v1.pushed_back(2);
+    ///
+    /// assert_eq!(v2.back(), Some(&2));
+    /// assert_eq!(v1.len(), 1);
+    /// ```
+    pub fn pushed_back(&self, value: L::ValueType) -> Self {
+        let tail = match &self.tail {
+            None => {
+                return Vector {
+                    root: None,
+                    height: 0,
+                    tail: Some(leaf(vec![value])),
+                    len: 1,
+                }
+            }
+            Some(tail) => tail,
+        };
+        if elements(tail).len() < WIDTH {
+            let mut elements = elements(tail).to_vec();
+            elements.push(value);
+            return Vector {
+                root: self.root.as_ref().map(L::clone),
+                height: self.height,
+                tail: Some(leaf(elements)),
+                len: self.len + 1,
+            };
+        }
+        let (root, height) = with_leaf(self.root.as_ref(), self.height, L::clone(tail));
+        Vector {
+            root: Some(root),
+            height,
+            tail: Some(leaf(vec![value])),
+            len: self.len + 1,
+        }
+    }
+
+    /// Returns the vector without its last element.
+    ///
+    /// Complexity: O(1) amortised
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called on an empty vector.
+    pub fn popped_back(&self) -> Self {
+        let tail = match &self.tail {
+            None => panic!("You can't pop an empty vector!"),
+            Some(tail) => elements(tail),
+        };
+        if tail.len() > 1 {
+            return Vector {
+                root: self.root.as_ref().map(L::clone),
+                height: self.height,
+                tail: Some(leaf(tail[..tail.len() - 1].to_vec())),
+                len: self.len - 1,
+            };
+        }
+        match &self.root {
+            None => Vector::new(),
+            Some(root) => Vector::from_tree(root, self.height, self.len - 1),
+        }
+    }
+
+    /// Returns the vector with the element at `index` replaced by
+    /// `value`. The nodes on the path to the element are copied, all
+    /// others are shared.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Vector;
+    ///
+    /// let v1: Vector<_> = (0..100).collect();
+    /// let v2 = v1.updated(42, 0);
+    ///
+    /// assert_eq!(v2.get(42), Some(&0));
+    /// assert_eq!(v1.get(42), Some(&42));
+    /// ```
+    pub fn updated(&self, index: usize, value: L::ValueType) -> Self {
+        if index >= self.len {
+            panic!("index out of bounds");
+        }
+        let tail = self.tail.as_ref().expect("only empty vectors have no tail");
+        let tree_len = self.len - elements(tail).len();
+        let (root, tail) = if index >= tree_len {
+            let mut elements = elements(tail).to_vec();
+            elements[index - tree_len] = value;
+            (self.root.as_ref().map(L::clone), leaf(elements))
+        } else {
+            let root = self.root.as_ref().expect("the tree holds all but the tail");
+            (
+                Some(updated_node(root, self.height, index, value)),
+                L::clone(tail),
+            )
+        };
+        Vector {
+            root,
+            height: self.height,
+            tail: Some(tail),
+            len: self.len,
+        }
+    }
+
+    /// Returns the concatenation of `self` and `other`.
+    ///
+    /// The trees of both vectors are joined along the right edge of the
+    /// first and the left edge of the second; only the nodes on these
+    /// edges are rebuilt.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Vector;
+    ///
+    /// let v1: Vector<_> = (0..100).collect();
+    /// let v2: Vector<_> = (100..250).collect();
+    /// let v3 = v1.appended(&v2);
+    ///
+    /// assert_eq!(v3.len(), 250);
+    /// assert!(v3.iter().copied().eq(0..250));
+    /// ```
+    pub fn appended(&self, other: &Self) -> Self {
+        let (left_tail, right_tail) = match (&self.tail, &other.tail) {
+            (None, _) => return other.clone(),
+            (_, None) => return self.clone(),
+            (Some(left), Some(right)) => (left, right),
+        };
+        let len = self.len + other.len;
+        let right = match &other.root {
+            Some(right) => right,
+            None => {
+                // All of `other` is in its tail, which joins ours.
+                let mut elements = elements(left_tail).to_vec();
+                elements.extend_from_slice(self::elements(right_tail));
+                if elements.len() <= WIDTH {
+                    return Vector {
+                        root: self.root.as_ref().map(L::clone),
+                        height: self.height,
+                        tail: Some(leaf(elements)),
+                        len,
+                    };
+                }
+                let rest = elements.split_off(WIDTH);
+                let (root, height) = with_leaf(self.root.as_ref(), self.height, leaf(elements));
+                return Vector {
+                    root: Some(root),
+                    height,
+                    tail: Some(leaf(rest)),
+                    len,
+                };
+            }
+        };
+        let (left, left_height) = with_leaf(self.root.as_ref(), self.height, L::clone(left_tail));
+        let height = max(left_height, other.height) + 1;
+        let (root, height) = collapsed(
+            concatenated(&left, left_height, right, other.height),
+            height,
+        );
+        Vector {
+            root: Some(root),
+            height,
+            tail: Some(L::clone(right_tail)),
+            len,
+        }
+    }
+
+    /// Splits the vector into the elements before `index` and the rest.
+    ///
+    /// Complexity: O(log n)
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `index` is greater than the length.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::Vector;
+    ///
+    /// let vector: Vector<_> = (0..100).collect();
+    /// let (left, right) = vector.split_at(40);
+    ///
+    /// assert!(left.iter().copied().eq(0..40));
+    /// assert!(right.iter().copied().eq(40..100));
+    /// ```
+    pub fn split_at(&self, index: usize) -> (Self, Self) {
+        if index > self.len {
+            panic!("index out of bounds");
+        }
+        let tail = match &self.tail {
+            Some(tail) if index < self.len => tail,
+            _ => return (self.clone(), Vector::new()),
+        };
+        if index == 0 {
+            return (Vector::new(), self.clone());
+        }
+        let tail_elements = elements(tail);
+        let tree_len = self.len - tail_elements.len();
+        if index >= tree_len {
+            let at = index - tree_len;
+            let right = Vector {
+                root: None,
+                height: 0,
+                tail: Some(leaf(tail_elements[at..].to_vec())),
+                len: self.len - index,
+            };
+            let root = self.root.as_ref();
+            let left = if at > 0 {
+                Vector {
+                    root: root.map(L::clone),
+                    height: self.height,
+                    tail: Some(leaf(tail_elements[..at].to_vec())),
+                    len: index,
+                }
+            } else {
+                let root = root.expect("the tree holds all but the tail");
+                Vector::from_tree(root, self.height, index)
+            };
+            return (left, right);
+        }
+        let root = self.root.as_ref().expect("the tree holds all but the tail");
+        let (prefix, prefix_height) = collapsed(prefix(root, self.height, index), self.height);
+        let (suffix, suffix_height) = collapsed(suffix(root, self.height, index), self.height);
+        let right = Vector {
+            root: Some(suffix),
+            height: suffix_height,
+            tail: Some(L::clone(tail)),
+            len: self.len - index,
+        };
+        (Vector::from_tree(&prefix, prefix_height, index), right)
+    }
+
+    /// Makes a vector of the `len` elements under `root`, moving its last
+    /// leaf into the tail.
+    fn from_tree(root: &L, height: usize, len: usize) -> Self {
+        let (rest, tail) = without_last_leaf(root, height);
+        let (root, height) = match rest {
+            None => (None, 0),
+            Some(rest) => {
+                let (root, height) = collapsed(rest, height);
+                (Some(root), height)
+            }
+        };
+        Vector {
+            root,
+            height,
+            tail: Some(tail),
+            len,
+        }
+    }
+}
+
+fn leaf<L: VectorLink>(elements: Vec<L::ValueType>) -> L {
+    L::new(VectorNode(Kind::Leaf(elements)))
+}
+
+/// Makes a node at `height` over `children`, which are one level lower.
+fn branch<L: VectorLink>(children: Vec<L>, height: usize) -> L {
+    let full = 1 << (BITS * height);
+    let mut sizes = Vec::with_capacity(children.len());
+    let mut total = 0;
+    for child in &children {
+        total += size(child, height - 1);
+        sizes.push(total);
+    }
+    let regular = sizes[..sizes.len() - 1]
+        .iter()
+        .enumerate()
+        .all(|(i, &size)| size == (i + 1) * full);
+    let sizes = if regular { None } else { Some(sizes) };
+    L::new(VectorNode(Kind::Branch { children, sizes }))
+}
+
+fn elements<L: VectorLink>(node: &L) -> &[L::ValueType] {
+    match &node.node().0 {
+        Kind::Leaf(elements) => elements,
+        Kind::Branch { .. } => unreachable!("only nodes at height 0 are leaves"),
+    }
+}
+
+fn children<L: VectorLink>(node: &L) -> &[L] {
+    match &node.node().0 {
+        Kind::Branch { children, .. } => children,
+        Kind::Leaf(_) => unreachable!("only nodes at height 0 are leaves"),
+    }
+}
+
+/// Returns the number of elements or children directly in `node`.
+fn slots<L: VectorLink>(node: &L) -> usize {
+    match &node.node().0 {
+        Kind::Leaf(elements) => elements.len(),
+        Kind::Branch { children, .. } => children.len(),
+    }
+}
+
+fn cloned<L: VectorLink>(links: &[L]) -> Vec<L> {
+    links.iter().map(L::clone).collect()
+}
+
+/// Returns the number of elements under `node`, which is at `height`.
+fn size<L: VectorLink>(node: &L, height: usize) -> usize {
+    match &node.node().0 {
+        Kind::Leaf(elements) => elements.len(),
+        Kind::Branch {
+            sizes: Some(sizes), ..
+        } => sizes[sizes.len() - 1],
+        Kind::Branch {
+            children,
+            sizes: None,
+        } => {
+            let last = &children[children.len() - 1];
+            ((children.len() - 1) << (BITS * height)) + size(last, height - 1)
+        }
+    }
+}
+
+/// Returns the child of `node`, which is at `height`, that holds
+/// `index`, and the position of `index` within the child.
+fn locate<L: VectorLink>(node: &L, height: usize, index: usize) -> (usize, usize) {
+    let sizes = match &node.node().0 {
+        Kind::Branch { sizes, .. } => sizes,
+        Kind::Leaf(_) => unreachable!("only nodes at height 0 are leaves"),
+    };
+    // No child holds more than a full one, so the child a regular node
+    // would use is never past the right one.
+    let mut child = index >> (BITS * height);
+    match sizes {
+        None => (child, index - (child << (BITS * height))),
+        Some(sizes) => {
+            while sizes[child] <= index {
+                child += 1;
+            }
+            let before = if child == 0 { 0 } else { sizes[child - 1] };
+            (child, index - before)
+        }
+    }
+}
+
+/// Wraps `node` into single-child branches until it is at `height`.
+fn path<L: VectorLink>(node: L, height: usize) -> L {
+    (1..=height).fold(node, |node, height| branch(vec![node], height))
+}
+
+/// Strips single-child branches off the top of the tree.
+fn collapsed<L: VectorLink>(mut root: L, mut height: usize) -> (L, usize) {
+    while height > 0 && slots(&root) == 1 {
+        root = L::clone(&children(&root)[0]);
+        height -= 1;
+    }
+    (root, height)
+}
+
+/// Adds `leaf` after the last leaf of the tree, growing it by one level
+/// if it is full.
+fn with_leaf<L: VectorLink>(root: Option<&L>, height: usize, leaf: L) -> (L, usize) {
+    let root = match root {
+        None => return (leaf, 0),
+        Some(root) => root,
+    };
+    match pushed_leaf(root, height, leaf) {
+        Ok(root) => (root, height),
+        Err(leaf) => {
+            let root = branch(vec![L::clone(root), path(leaf, height)], height + 1);
+            (root, height + 1)
+        }
+    }
+}
+
+/// Adds `leaf` as far right as possible under `node`, or hands it back
+/// if there is no room.
+fn pushed_leaf<L: VectorLink>(node: &L, height: usize, leaf: L) -> Result<L, L> {
+    if height == 0 {
+        return Err(leaf);
+    }
+    let children = children(node);
+    let leaf = match pushed_leaf(&children[children.len() - 1], height - 1, leaf) {
+        Ok(last) => {
+            let mut children = cloned(children);
+            *children.last_mut().expect("branches are never empty") = last;
+            return Ok(branch(children, height));
+        }
+        Err(leaf) => leaf,
+    };
+    if children.len() == WIDTH {
+        return Err(leaf);
+    }
+    let mut children = cloned(children);
+    children.push(path(leaf, height - 1));
+    Ok(branch(children, height))
+}
+
+/// Returns the tree without its last leaf, if anything is left, and that
+/// leaf.
+fn without_last_leaf<L: VectorLink>(node: &L, height: usize) -> (Option<L>, L) {
+    if height == 0 {
+        return (None, L::clone(node));
+    }
+    let children = children(node);
+    let (last, leaf) = without_last_leaf(&children[children.len() - 1], height - 1);
+    let mut children = cloned(&children[..children.len() - 1]);
+    children.extend(last);
+    if children.is_empty() {
+        (None, leaf)
+    } else {
+        (Some(branch(children, height)), leaf)
+    }
+}
+
+fn updated_node<L>(node: &L, height: usize, index: usize, value: L::ValueType) -> L
+where
+    L: VectorLink,
+    L::ValueType: Clone,
+{
+    match &node.node().0 {
+        Kind::Leaf(elements) => {
+            let mut elements = elements.clone();
+            elements[index] = value;
+            leaf(elements)
+        }
+        Kind::Branch { children, sizes } => {
+            let (child, rest) = locate(node, height, index);
+            let mut children = cloned(children);
+            children[child] = updated_node(&children[child], height - 1, rest, value);
+            L::new(VectorNode(Kind::Branch {
+                children,
+                sizes: sizes.clone(),
+            }))
+        }
+    }
+}
+
+/// Returns the first `len` elements under `node`, which must be at
+/// least one.
+fn prefix<L>(node: &L, height: usize, len: usize) -> L
+where
+    L: VectorLink,
+    L::ValueType: Clone,
+{
+    if height == 0 {
+        let elements = elements(node);
+        return if len == elements.len() {
+            L::clone(node)
+        } else {
+            leaf(elements[..len].to_vec())
+        };
+    }
+    let (child, rest) = locate(node, height, len - 1);
+    let children = children(node);
+    let mut kept = cloned(&children[..child]);
+    kept.push(prefix(&children[child], height - 1, rest + 1));
+    branch(kept, height)
+}
+
+/// Returns the elements under `node` from `start` on, which must leave at
+/// least one.
+fn suffix<L>(node: &L, height: usize, start: usize) -> L
+where
+    L: VectorLink,
+    L::ValueType: Clone,
+{
+    if height == 0 {
+        return if start == 0 {
+            L::clone(node)
+        } else {
+            leaf(elements(node)[start..].to_vec())
+        };
+    }
+    let (child, rest) = locate(node, height, start);
+    let children = children(node);
+    let mut kept = vec![suffix(&children[child], height - 1, rest)];
+    kept.extend(cloned(&children[child + 1..]));
+    branch(kept, height)
+}
+
+/// Joins two trees into one whose height is one more than the higher of
+/// the two. Its root has one or two children.
+fn concatenated<L>(left: &L, left_height: usize, right: &L, right_height: usize) -> L
+where
+    L: VectorLink,
+    L::ValueType: Clone,
+{
+    if left_height == 0 && right_height == 0 {
+        let leaves = rebalanced(vec![L::clone(left), L::clone(right)], 0);
+        return branch(leaves, 1);
+    }
+    // Join the facing edges first, then rebalance the nodes one level
+    // below the top of the result.
+    let height = max(left_height, right_height);
+    let mut nodes = Vec::new();
+    if left_height == height {
+        let children = children(left);
+        nodes.extend(cloned(&children[..children.len() - 1]));
+    }
+    let middle = if left_height > right_height {
+        let children = children(left);
+        concatenated(
+            &children[children.len() - 1],
+            left_height - 1,
+            right,
+            right_height,
+        )
+    } else if left_height < right_height {
+        concatenated(left, left_height, &children(right)[0], right_height - 1)
+    } else {
+        let last = &children(left)[slots(left) - 1];
+        concatenated(last, left_height - 1, &children(right)[0], right_height - 1)
+    };
+    nodes.extend(cloned(children(&middle)));
+    if right_height == height {
+        nodes.extend(cloned(&children(right)[1..]));
+    }
+    let nodes = rebalanced(nodes, height - 1);
+    let parents = nodes
+        .chunks(WIDTH)
+        .map(|chunk| branch(cloned(chunk), height))
+        .collect();
+    branch(parents, height + 1)
+}
+
+/// Redistributes the contents of `nodes`, which are at `height`, over as
+/// few nodes as `plan` allows. Nodes that keep their contents are
+/// shared.
+fn rebalanced<L>(nodes: Vec<L>, height: usize) -> Vec<L>
+where
+    L: VectorLink,
+    L::ValueType: Clone,
+{
+    let counts: Vec<_> = nodes.iter().map(slots).collect();
+    let plan = plan(&counts);
+    if plan.len() == counts.len() {
+        return nodes;
+    }
+    if height == 0 {
+        repacked(&nodes, &plan, elements::<L>, Clone::clone, leaf)
+    } else {
+        repacked(&nodes, &plan, children::<L>, L::clone, |children| {
+            branch(children, height)
+        })
+    }
+}
+
+/// Decides how many slots each of the rebalanced nodes gets, given the
+/// slot counts of the current ones.
+///
+/// A level may keep up to `EXTRA_STEPS` more nodes than it would need if
+/// all were full. While there are more, the first node that isn't full is
+/// merged into its successors, which shifts their slots to the left
+/// until one of them is used up.
+fn plan(counts: &[usize]) -> Vec<usize> {
+    let mut plan = counts.to_vec();
+    let total: usize = plan.iter().sum();
+    let optimal = (total + WIDTH - 1) / WIDTH;
+    while plan.len() > optimal + EXTRA_STEPS {
+        let mut i = plan
+            .iter()
+            .position(|&count| count < WIDTH)
+            .expect("a level with more nodes than needed has one that isn't full");
+        let mut carry = plan[i];
+        while carry > 0 {
+            let filled = min(carry + plan[i + 1], WIDTH);
+            carry = carry + plan[i + 1] - filled;
+            plan[i] = filled;
+            i += 1;
+        }
+        plan.remove(i);
+    }
+    plan
+}
+
+/// Builds nodes holding the slots of `nodes`, in order, with as many
+/// slots in each as `plan` says.
+fn repacked<L, S>(
+    nodes: &[L],
+    plan: &[usize],
+    slots_of: for<'a> fn(&'a L) -> &'a [S],
+    clone: fn(&S) -> S,
+    build: impl Fn(Vec<S>) -> L,
+) -> Vec<L>
+where
+    L: VectorLink,
+{
+    let mut repacked = Vec::with_capacity(plan.len());
+    let mut node = 0;
+    let mut offset = 0;
+    for &count in plan {
+        if offset == 0 && slots_of(&nodes[node]).len() == count {
+            repacked.push(L::clone(&nodes[node]));
+            node += 1;
+            continue;
+        }
+        let mut slots = Vec::with_capacity(count);
+        while slots.len() < count {
+            let source = slots_of(&nodes[node]);
+            let taken = min(count - slots.len(), source.len() - offset);
+            slots.extend(source[offset..offset + taken].iter().map(clone));
+            offset += taken;
+            if offset == source.len() {
+                node += 1;
+                offset = 0;
+            }
+        }
+        repacked.push(build(slots));
+    }
+    repacked
+}
+
+pub struct Iter<'a, L: VectorLink> {
+    vector: &'a Vector<L>,
+    leaf: slice::Iter<'a, L::ValueType>,
+    /// The index of the first element after `leaf`.
+    next: usize,
+}
+
+impl<'a, L: VectorLink> Iterator for Iter<'a, L> {
+    type Item = &'a L::ValueType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(x) = self.leaf.next() {
+                return Some(x);
+            }
+            if self.next == self.vector.len {
+                return None;
+            }
+            let (elements, offset) = self.vector.leaf_at(self.next);
+            self.leaf = elements[offset..].iter();
+            self.next += elements.len() - offset;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.leaf.len() + self.vector.len - self.next;
+        (len, Some(len))
+    }
+}
+
+impl<'a, L: VectorLink> ExactSizeIterator for Iter<'a, L> {}
+
+impl<'a, L: VectorLink> IntoIterator for &'a Vector<L> {
+    type Item = &'a L::ValueType;
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<L: VectorLink> FromIterator<L::ValueType> for Vector<L> {
+    /// Fills the leaves one at a time, so nothing is copied.
+    fn from_iter<I: IntoIterator<Item = L::ValueType>>(iter: I) -> Self {
+        let mut root = None;
+        let mut height = 0;
+        let mut len = 0;
+        let mut chunk = Vec::with_capacity(WIDTH);
+        for x in iter {
+            if chunk.len() == WIDTH {
+                let full = mem::replace(&mut chunk, Vec::with_capacity(WIDTH));
+                let (new_root, new_height) = with_leaf(root.as_ref(), height, leaf(full));
+                root = Some(new_root);
+                height = new_height;
+            }
+            chunk.push(x);
+            len += 1;
+        }
+        Vector {
+            root,
+            height,
+            tail: if chunk.is_empty() {
+                None
+            } else {
+                Some(leaf(chunk))
+            },
+            len,
+        }
+    }
+}
+
+impl<L> PartialEq for Vector<L>
+where
+    L: VectorLink,
+    L::ValueType: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && (self.ptr_eq(other) || self.iter().eq(other.iter()))
+    }
+}
+
+impl<L> Debug for Vector<L>
+where
+    L: VectorLink,
+    L::ValueType: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
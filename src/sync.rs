@@ -1,7 +1,42 @@
+pub mod binary_tree;
+pub mod catenable_list;
+pub mod deque;
+pub mod finger_tree;
+pub mod hash_map;
+pub mod hash_set;
+pub mod int_map;
+pub mod int_set;
+pub mod lazy;
+pub mod leftist_heap;
 pub mod list;
+pub mod list_zipper;
+pub mod ordered_seq;
+pub mod priority_queue;
+pub mod queue;
+pub mod random_access_list;
 pub mod rb_map;
 pub mod rb_tree;
+pub mod seq;
+pub mod stream;
+pub mod tree;
+pub mod vector;
 
+pub use catenable_list::CatenableList;
+pub use deque::Deque;
+pub use finger_tree::FingerTree;
+pub use hash_map::HashMap;
+pub use hash_set::HashSet;
+pub use int_map::IntMap;
+pub use int_set::IntSet;
+pub use leftist_heap::LeftistHeap;
 pub use list::List;
+pub use list_zipper::ListZipper;
+pub use ordered_seq::OrderedSeq;
+pub use priority_queue::PriorityQueue;
+pub use queue::Queue;
+pub use random_access_list::RandomAccessList;
 pub use rb_map::RBMap;
 pub use rb_tree::RBTree;
+pub use seq::Seq;
+pub use stream::Stream;
+pub use vector::Vector;
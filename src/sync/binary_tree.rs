@@ -0,0 +1,207 @@
+use alloc::sync::Arc;
+
+use crate::shared::{self, binary_tree::drop_children, link::BinaryLink};
+
+pub type BinaryTree<T> = shared::binary_tree::BinaryTree<SyncBinaryLink<T>>;
+
+pub(crate) type SyncBinaryLink<T> = Arc<Node<T>>;
+
+#[derive(Debug)]
+pub struct Node<T> {
+    element: T,
+    left: Option<SyncBinaryLink<T>>,
+    right: Option<SyncBinaryLink<T>>,
+}
+
+impl<T> BinaryLink for Arc<Node<T>> {
+    type ValueType = T;
+
+    fn node(element: Self::ValueType, left: Option<Self>, right: Option<Self>) -> Self {
+        Arc::new(Node {
+            element,
+            left,
+            right,
+        })
+    }
+
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+
+    fn get_element(&self) -> &Self::ValueType {
+        &self.element
+    }
+
+    fn left_ref(&self) -> Option<&Self> {
+        self.left.as_ref()
+    }
+
+    fn right_ref(&self) -> Option<&Self> {
+        self.right.as_ref()
+    }
+
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+
+    fn as_ptr(&self) -> *const () {
+        Arc::as_ptr(self) as *const ()
+    }
+
+    fn try_unwrap_children(self) -> Option<(Option<Self>, Option<Self>)> {
+        Arc::try_unwrap(self)
+            .ok()
+            .map(|mut node| (node.left.take(), node.right.take()))
+    }
+}
+
+impl<T> Drop for Node<T> {
+    fn drop(&mut self) {
+        drop_children(self.left.take(), self.right.take());
+    }
+}
+
+impl<T> BinaryTree<T> {
+    /// Returns a tree of the same shape with `f` applied to every
+    /// element, in postorder.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::sync::binary_tree::BinaryTree;
+    ///
+    /// let tree = BinaryTree::node(2, BinaryTree::leaf(1), BinaryTree::new());
+    ///
+    /// assert!(tree.map(|x| x * 10).preorder().copied().eq([20, 10]));
+    /// ```
+    pub fn map<U, F>(&self, f: F) -> BinaryTree<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        self.mapped(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    fn sample() -> BinaryTree<i32> {
+        //     4
+        //   2   6
+        //  1 3   7
+        [4, 2, 6, 1, 3, 7]
+            .iter()
+            .fold(BinaryTree::new(), |tree, &x| tree.inserted(x))
+    }
+
+    #[test]
+    fn node_and_subtrees() {
+        let tree = BinaryTree::node(2, BinaryTree::leaf(1), BinaryTree::new());
+        assert_eq!(tree.root(), Some(&2));
+        assert_eq!(tree.left().root(), Some(&1));
+        assert!(tree.right().is_empty());
+        assert!(tree.left().left().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn left_of_empty_panics() {
+        BinaryTree::<i32>::new().left();
+    }
+
+    #[test]
+    fn traversals() {
+        let tree = sample();
+        assert_eq!(
+            tree.preorder().copied().collect::<Vec<_>>(),
+            [4, 2, 1, 3, 6, 7]
+        );
+        assert_eq!(
+            tree.inorder().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 6, 7]
+        );
+        assert_eq!(
+            tree.postorder().copied().collect::<Vec<_>>(),
+            [1, 3, 2, 7, 6, 4]
+        );
+        assert_eq!(
+            tree.level_order().copied().collect::<Vec<_>>(),
+            [4, 2, 6, 1, 3, 7]
+        );
+        assert_eq!(BinaryTree::<i32>::new().inorder().next(), None);
+    }
+
+    #[test]
+    fn measures() {
+        let tree = sample();
+        assert_eq!(tree.size(), 6);
+        assert_eq!(tree.height(), 3);
+        assert_eq!(BinaryTree::<i32>::new().height(), 0);
+        assert_eq!(
+            tree.fold(|x, l, r| x + l.unwrap_or(0) + r.unwrap_or(0)),
+            Some(23)
+        );
+    }
+
+    #[test]
+    fn map_and_mirror() {
+        let tree = sample();
+        let strings = tree.map(|x| x.to_string());
+        assert_eq!(strings.root().map(String::as_str), Some("4"));
+        assert_eq!(
+            tree.mirror().inorder().copied().collect::<Vec<_>>(),
+            [7, 6, 4, 3, 2, 1]
+        );
+        assert_eq!(tree.mirror().mirror(), tree);
+    }
+
+    #[test]
+    fn inserted_shares_untouched_subtrees() {
+        let t1 = sample();
+        let t2 = t1.inserted(5);
+        assert!(t2.contains(&5));
+        assert!(!t1.contains(&5));
+        assert!(t2.left().ptr_eq(&t1.left()));
+        assert!(t2.right().right().ptr_eq(&t1.right().right()));
+        assert!(t1.inserted(3).ptr_eq(&t1));
+        assert_ne!(t1, t2);
+    }
+
+    #[test]
+    fn equality() {
+        let a = BinaryTree::node(1, BinaryTree::new(), BinaryTree::leaf(2));
+        let b = BinaryTree::node(1, BinaryTree::leaf(2), BinaryTree::new());
+        assert_ne!(a, b);
+        assert_eq!(a, BinaryTree::new().inserted(1).inserted(2));
+    }
+
+    #[test]
+    fn pretty_shows_empty_sides() {
+        let expected = "4\n├── 2\n│   ├── 1\n│   └── 3\n└── 6\n    ├── ·\n    └── 7";
+        assert_eq!(sample().pretty(), expected);
+    }
+
+    #[test]
+    fn to_dot_draws_shared_nodes_once() {
+        let t1 = sample();
+        let t2 = t1.inserted(5);
+        let dot = BinaryTree::to_dot(&[&t1, &t2]);
+
+        assert_eq!(dot.matches("label=\"2\"").count(), 1);
+        assert_eq!(dot.matches("label=\"6\"").count(), 2);
+        assert_eq!(dot.matches(" -> ").count(), 2 + 5 + 4);
+    }
+
+    #[test]
+    fn drop_degenerate_tree() {
+        let tree = (0..1_000_000).fold(BinaryTree::new(), |tree, x| {
+            BinaryTree::node(x, tree, BinaryTree::new())
+        });
+        assert_eq!(tree.height(), 1_000_000);
+        assert_eq!(tree.inorder().next(), Some(&0));
+    }
+}
@@ -0,0 +1,116 @@
+use alloc::sync::Arc;
+use core::mem;
+
+use crate::shared::{
+    self,
+    catenable_list::{drop_children, CatenableLink},
+    lazy::{Lazy, Reclaim, Suspend},
+};
+use crate::sync::{lazy::Delayed, Queue};
+
+pub type CatenableList<T> = shared::catenable_list::CatenableList<SyncCatenableLink<T>>;
+
+type SyncCatenableLink<T> = Arc<Node<T>>;
+
+/// A list in the children of a node, or the suspended linking of the
+/// lists in a queue, shared by every node that holds it.
+pub struct Suspended<T>(Arc<Delayed<Queue<Suspended<T>>, CatenableList<T>>>);
+
+pub struct Node<T> {
+    element: T,
+    children: Queue<Suspended<T>>,
+}
+
+impl<T> CatenableLink for Arc<Node<T>> {
+    type ValueType = T;
+    type Suspended = Suspended<T>;
+    type QueueLink = crate::sync::list::SyncLink<Suspended<T>>;
+
+    fn node(element: Self::ValueType, children: Queue<Suspended<T>>) -> Self {
+        Arc::new(Node { element, children })
+    }
+
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+
+    fn get_element(&self) -> &Self::ValueType {
+        &self.element
+    }
+
+    fn children(&self) -> &Queue<Suspended<T>> {
+        &self.children
+    }
+
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+
+    fn try_unwrap_children(self) -> Option<Queue<Suspended<T>>> {
+        Arc::try_unwrap(self)
+            .ok()
+            .map(|mut node| mem::take(&mut node.children))
+    }
+}
+
+impl<T> Drop for Node<T> {
+    fn drop(&mut self) {
+        drop_children::<Arc<Node<T>>>(mem::take(&mut self.children));
+    }
+}
+
+impl<T> Clone for Suspended<T> {
+    fn clone(&self) -> Self {
+        Suspended(self.0.clone())
+    }
+}
+
+impl<T> Lazy for Suspended<T> {
+    type Value = CatenableList<T>;
+
+    fn evaluated(list: CatenableList<T>) -> Self {
+        Suspended(Arc::new(Delayed::evaluated(list)))
+    }
+
+    fn force(&self) -> &CatenableList<T> {
+        self.0.force()
+    }
+
+    fn get(&self) -> Option<&CatenableList<T>> {
+        self.0.get()
+    }
+
+    fn get_mut(&mut self) -> Option<&mut CatenableList<T>> {
+        Arc::get_mut(&mut self.0).and_then(Delayed::get_mut)
+    }
+}
+
+impl<T> Suspend<Queue<Suspended<T>>> for Suspended<T> {
+    fn suspended(
+        queue: Queue<Suspended<T>>,
+        body: fn(Queue<Suspended<T>>) -> CatenableList<T>,
+    ) -> Self {
+        Suspended(Arc::new(Delayed::suspended(queue, body)))
+    }
+}
+
+impl<T> Reclaim<Queue<Suspended<T>>> for Suspended<T> {
+    fn reclaim(&mut self) -> Option<Queue<Suspended<T>>> {
+        Arc::get_mut(&mut self.0).and_then(Delayed::reclaim)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appended_and_popped_front() {
+        let a: CatenableList<i32> = (0..5).collect();
+        let b: CatenableList<i32> = (5..10).collect();
+        let ab = a.appended(&b).popped_front();
+
+        assert!(ab.iter().copied().eq(1..10));
+        assert!(a.iter().copied().eq(0..5));
+    }
+}
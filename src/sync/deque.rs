@@ -0,0 +1,20 @@
+use crate::shared;
+use crate::sync::stream::SyncStreamLink;
+
+pub type Deque<T> = shared::deque::Deque<SyncStreamLink<T>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pushed_and_popped_at_both_ends() {
+        let d1: Deque<i32> = (0..10).collect();
+        let d2 = d1.popped_front().popped_back().pushed_front(-1);
+
+        assert!(d1.iter().copied().eq(0..10));
+        assert!(d2.iter().copied().eq((-1..0).chain(1..9)));
+        assert_eq!(d2.front(), Some(&-1));
+        assert_eq!(d2.back(), Some(&8));
+    }
+}
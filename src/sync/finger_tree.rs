@@ -0,0 +1,77 @@
+use alloc::sync::Arc;
+
+use crate::shared::{
+    self,
+    finger_tree::{FingerLink, FingerNode},
+};
+
+pub use crate::shared::finger_tree::{Measured, Monoid};
+
+pub type FingerTree<A> = shared::finger_tree::FingerTree<SyncFingerLink<A>>;
+
+pub(crate) type SyncFingerLink<A> = Arc<Node<A>>;
+
+pub struct Node<A: Measured>(FingerNode<SyncFingerLink<A>>);
+
+impl<A: Measured> FingerLink for Arc<Node<A>> {
+    type Element = A;
+
+    fn new(node: FingerNode<Self>) -> Self {
+        Arc::new(Node(node))
+    }
+
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+
+    fn node(&self) -> &FingerNode<Self> {
+        &self.0
+    }
+
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Word(&'static str);
+
+    /// The total length of the words.
+    #[derive(Clone)]
+    struct Chars(usize);
+
+    impl Monoid for Chars {
+        fn empty() -> Self {
+            Chars(0)
+        }
+
+        fn combine(&self, other: &Self) -> Self {
+            Chars(self.0 + other.0)
+        }
+    }
+
+    impl Measured for Word {
+        type Measure = Chars;
+
+        fn measure(&self) -> Chars {
+            Chars(self.0.len())
+        }
+    }
+
+    #[test]
+    fn find_by_custom_measure() {
+        let words: FingerTree<_> = ["a", "finger", "tree", "of", "words"]
+            .into_iter()
+            .map(Word)
+            .collect();
+
+        assert_eq!(words.measure().0, 18);
+        assert_eq!(words.find(|chars| chars.0 > 7), Some(&Word("tree")));
+        let (front, _) = words.split(|chars| chars.0 > 11);
+        assert_eq!(front.back(), Some(&Word("tree")));
+    }
+}
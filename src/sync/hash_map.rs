@@ -0,0 +1,61 @@
+use alloc::sync::Arc;
+
+use crate::shared::{
+    self,
+    hash_map::{HashLink, HashNode},
+};
+
+pub use crate::shared::hash_map::{DefaultHashBuilder, DefaultHasher};
+
+pub type HashMap<K, V, S = DefaultHashBuilder> = shared::hash_map::HashMap<SyncHashLink<K, V>, S>;
+
+pub(crate) type SyncHashLink<K, V> = Arc<Node<K, V>>;
+
+pub struct Node<K, V>(HashNode<SyncHashLink<K, V>>);
+
+impl<K, V> HashLink for Arc<Node<K, V>> {
+    type Key = K;
+    type Value = V;
+
+    fn new(node: HashNode<Self>) -> Self {
+        Arc::new(Node(node))
+    }
+
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+
+    fn node(&self) -> &HashNode<Self> {
+        &self.0
+    }
+
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_creates_map() {
+        let map = HashMap::<i32, &str>::new();
+
+        assert!(map.is_empty());
+        assert!(!map.contains_key(&5));
+    }
+
+    #[test]
+    fn inserted_and_removed_keep_old_versions() {
+        let m1: HashMap<_, _> = (0..100).map(|x| (x, x)).collect();
+        let m2 = m1.removed(&50).inserted_or_replaced(7, 0);
+
+        assert_eq!(m1.len(), 100);
+        assert_eq!(m1.get(&7), Some(&7));
+        assert_eq!(m2.len(), 99);
+        assert_eq!(m2.get(&7), Some(&0));
+        assert!(!m2.contains_key(&50));
+        assert!(m2.removed(&50).ptr_eq(&m2));
+    }
+}
@@ -0,0 +1,20 @@
+use crate::shared::{self, hash_map::DefaultHashBuilder};
+use crate::sync::hash_map::SyncHashLink;
+
+pub type HashSet<T, S = DefaultHashBuilder> = shared::hash_set::HashSet<SyncHashLink<T, ()>, S>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserted_and_removed() {
+        let s1: HashSet<_> = (0..10).collect();
+        let s2 = s1.removed(&3).inserted(10);
+
+        assert!(s1.contains(&3));
+        assert!(!s2.contains(&3));
+        assert!(s2.contains(&10));
+        assert_eq!(s2.len(), 10);
+    }
+}
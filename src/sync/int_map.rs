@@ -0,0 +1,48 @@
+use alloc::sync::Arc;
+
+use crate::shared::{
+    self,
+    int_map::{IntLink, IntNode},
+};
+
+pub type IntMap<V> = shared::int_map::IntMap<SyncIntLink<V>>;
+
+pub(crate) type SyncIntLink<V> = Arc<Node<V>>;
+
+pub struct Node<V>(IntNode<SyncIntLink<V>>);
+
+impl<V> IntLink for Arc<Node<V>> {
+    type Value = V;
+
+    fn new(node: IntNode<Self>) -> Self {
+        Arc::new(Node(node))
+    }
+
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+
+    fn node(&self) -> &IntNode<Self> {
+        &self.0
+    }
+
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_and_intersection() {
+        let m1: IntMap<_> = (0..10).map(|x| (x, 'a')).collect();
+        let m2: IntMap<_> = (5..15).map(|x| (x, 'b')).collect();
+
+        assert!(m1.union(&m2).keys().copied().eq(0..15));
+        assert_eq!(m1.union(&m2).get(&5), Some(&'a'));
+        assert!(m1.intersection(&m2).keys().copied().eq(5..10));
+        assert!(m1.removed(&3).inserted(3, 'a').keys().eq(m1.keys()));
+    }
+}
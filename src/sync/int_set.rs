@@ -0,0 +1,18 @@
+use crate::shared;
+use crate::sync::int_map::SyncIntLink;
+
+pub type IntSet = shared::int_set::IntSet<SyncIntLink<()>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserted_and_removed() {
+        let s1: IntSet = [5, 1, 3].into_iter().collect();
+        let s2 = s1.inserted(2).removed(&5);
+
+        assert!(s2.iter().copied().eq(1..=3));
+        assert!(s1.iter().copied().eq([1, 3, 5]));
+    }
+}
@@ -0,0 +1,106 @@
+use alloc::boxed::Box;
+
+use spin::{Mutex, Once};
+
+use crate::shared::lazy::{Lazy, Reclaim, Suspend};
+
+type Thunk<T> = Box<dyn FnOnce() -> T + Send>;
+
+/// A memoised suspension over `spin::Once`, which can be shared and
+/// forced from several threads. A thread that forces a suspension while
+/// another thread is evaluating it spins until the value is ready.
+///
+/// A thunk that panics poisons the `Once`, and forcing the suspension
+/// again panics. Forcing a suspension from inside its own thunk spins
+/// forever.
+pub struct Memo<T> {
+    thunk: Mutex<Option<Thunk<T>>>,
+    value: Once<T>,
+}
+
+impl<T> Lazy for Memo<T> {
+    type Value = T;
+
+    fn evaluated(value: T) -> Self {
+        Memo {
+            thunk: Mutex::new(None),
+            value: Once::initialized(value),
+        }
+    }
+
+    fn force(&self) -> &T {
+        self.value.call_once(|| {
+            let thunk = self.thunk.lock().take();
+            thunk.expect("a suspension is forced only once")()
+        })
+    }
+
+    fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    fn get_mut(&mut self) -> Option<&mut T> {
+        self.value.get_mut()
+    }
+}
+
+impl<T: 'static, X: Send + 'static> Suspend<X> for Memo<T> {
+    fn suspended(captured: X, body: fn(X) -> T) -> Self {
+        Memo {
+            thunk: Mutex::new(Some(Box::new(move || body(captured)))),
+            value: Once::new(),
+        }
+    }
+}
+
+/// A function with the values it is called with.
+type Call<X, T> = (X, fn(X) -> T);
+
+/// A memoised suspension like `Memo`, whose captured values keep their
+/// type until it is forced, so that they can be reclaimed.
+pub struct Delayed<X, T> {
+    captured: Mutex<Option<Call<X, T>>>,
+    value: Once<T>,
+}
+
+impl<X, T> Lazy for Delayed<X, T> {
+    type Value = T;
+
+    fn evaluated(value: T) -> Self {
+        Delayed {
+            captured: Mutex::new(None),
+            value: Once::initialized(value),
+        }
+    }
+
+    fn force(&self) -> &T {
+        self.value.call_once(|| {
+            let captured = self.captured.lock().take();
+            let (captured, body) = captured.expect("a suspension is forced only once");
+            body(captured)
+        })
+    }
+
+    fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    fn get_mut(&mut self) -> Option<&mut T> {
+        self.value.get_mut()
+    }
+}
+
+impl<X, T> Suspend<X> for Delayed<X, T> {
+    fn suspended(captured: X, body: fn(X) -> T) -> Self {
+        Delayed {
+            captured: Mutex::new(Some((captured, body))),
+            value: Once::new(),
+        }
+    }
+}
+
+impl<X, T> Reclaim<X> for Delayed<X, T> {
+    fn reclaim(&mut self) -> Option<X> {
+        self.captured.get_mut().take().map(|(captured, _)| captured)
+    }
+}
@@ -0,0 +1,22 @@
+use crate::shared;
+use crate::sync::binary_tree::SyncBinaryLink;
+
+pub use crate::shared::leftist_heap::Ranked;
+
+pub type LeftistHeap<T> = shared::leftist_heap::LeftistHeap<SyncBinaryLink<Ranked<T>>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pushed_merged_and_popped() {
+        let h1: LeftistHeap<_> = [7, 3, 5].into_iter().collect();
+        let h2 = h1.merged(&LeftistHeap::new().pushed(4).pushed(1));
+
+        assert_eq!(h2.peek_min(), Some(&1));
+        assert_eq!(h2.popped_min().peek_min(), Some(&3));
+        assert!(h2.iter_sorted().copied().eq([1, 3, 4, 5, 7]));
+        assert_eq!(h1.len(), 3);
+    }
+}
@@ -1,4 +1,4 @@
-use alloc::sync::Arc;
+use alloc::{collections::BTreeMap, sync::Arc};
 use core::fmt::Debug;
 
 use crate::shared;
@@ -8,7 +8,7 @@ pub type List<T> = shared::list::List<SyncLink<T>>;
 
 pub use crate::shared::list::{filter, fmap, foldl, foldr, mreturn};
 
-type SyncLink<T> = Arc<Node<T>>;
+pub(crate) type SyncLink<T> = Arc<Node<T>>;
 
 #[derive(Debug)]
 pub struct Node<T> {
@@ -31,13 +31,20 @@ impl<T> Link for Arc<Node<T>> {
     }
 
     fn clone(&self) -> Self {
-        <Self as Clone>::clone(&self)
+        <Self as Clone>::clone(self)
     }
 
     fn link_ref(&self) -> &Self {
         self
     }
 
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+    fn as_ptr(&self) -> *const () {
+        Arc::as_ptr(self) as *const ()
+    }
+
     fn next_ref(&self) -> Option<&Self> {
         self.next.as_ref()
     }
@@ -47,7 +54,7 @@ impl<T> Link for Arc<Node<T>> {
     }
 
     fn next_cloned(&self) -> Option<Self> {
-        self.next.as_ref().map(|node| <Self as Clone>::clone(node))
+        self.next.clone()
     }
 }
 
@@ -77,6 +84,31 @@ impl<T> Drop for Node<T> {
     }
 }
 
+impl<T: Clone> List<T> {
+    /// Rebuilds the list on `Rc`s.
+    ///
+    /// Complexity: O(n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::{sync, unsync};
+    ///
+    /// let list = sync::List::new().pushed_front(2).pushed_front(1);
+    /// let converted: unsync::List<_> = list.to_unsync();
+    ///
+    /// assert!(converted.iter().eq(list.iter()));
+    /// ```
+    pub fn to_unsync(&self) -> crate::unsync::List<T> {
+        self.converted(&mut BTreeMap::new(), T::clone)
+    }
+}
+
+impl<T: Clone> From<&List<T>> for crate::unsync::List<T> {
+    fn from(list: &List<T>) -> Self {
+        list.to_unsync()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,7 +123,7 @@ mod tests {
 
     #[test]
     fn create_cons() {
-        let list = List::cons(3, &List::empty());
+        let list = List::cons(3, List::empty());
 
         assert_eq!(list.front(), Some(&3));
 
@@ -186,6 +218,39 @@ mod tests {
         assert_eq!(list, synced_list!(3));
     }
 
+    #[test]
+    fn debug_and_display_print_elements_front_first() {
+        use alloc::format;
+
+        assert_eq!(format!("{:?}", synced_list!(2, 1)), "[1, 2]");
+        assert_eq!(format!("{}", synced_list!("b", "a")), "[a, b]");
+    }
+
+    #[test]
+    fn sorted_and_merge_sorted() {
+        let list = synced_list!(2, 5, 1, 4);
+
+        assert_eq!(list.sorted(), synced_list!(5, 4, 2, 1));
+        assert_eq!(
+            List::merge_sorted(&synced_list!(3, 1), &synced_list!(4, 2)),
+            synced_list!(4, 3, 2, 1)
+        );
+        assert_eq!(synced_list!(3, 1).insert_sorted(2), synced_list!(3, 2, 1));
+    }
+
+    #[test]
+    fn lists_are_ordered_and_hashable() {
+        extern crate std;
+        use std::collections::HashSet;
+
+        assert!(synced_list!(1) < synced_list!(2, 1));
+
+        let set: HashSet<_> = [synced_list!(1), synced_list!(1), synced_list!(2)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+
     mod no_copy {
 
         use shared::list::concat_all;
@@ -210,7 +275,7 @@ mod tests {
 
             assert_eq!(
                 concat_all(&lst_all),
-                List::cons(NoCopy(1), &List::from_value(NoCopy(2)))
+                List::cons(NoCopy(1), List::from_value(NoCopy(2)))
             )
         }
 
@@ -240,4 +305,13 @@ mod tests {
             assert_eq!(foldr(|a, b| NoCopy(a.0 - b.0), NoCopy(0), &list), NoCopy(1));
         }
     }
+
+    #[test]
+    fn to_unsync_keeps_elements_in_order() {
+        let list = List::new().pushed_front(3).pushed_front(2).pushed_front(1);
+        let converted = list.to_unsync();
+
+        assert!(converted.iter().copied().eq([1, 2, 3]));
+        assert_eq!(crate::unsync::List::from(&list), converted);
+    }
 }
@@ -0,0 +1,25 @@
+use crate::shared;
+use crate::sync::list::SyncLink;
+
+pub type ListZipper<T> = shared::list_zipper::ListZipper<SyncLink<T>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::List;
+
+    #[test]
+    fn edits_are_visible_after_rebuilding() {
+        let list: List<_> = List::new().pushed_front(3).pushed_front(1);
+        let zipper = ListZipper::new(&list)
+            .move_right()
+            .unwrap()
+            .inserted(2)
+            .move_right()
+            .unwrap()
+            .replaced(4);
+
+        assert!(zipper.to_list().iter().copied().eq([1, 2, 4]));
+        assert!(list.iter().copied().eq([1, 3]));
+    }
+}
@@ -0,0 +1,20 @@
+use crate::shared;
+use crate::sync::finger_tree::SyncFingerLink;
+
+pub use crate::shared::ordered_seq::{Keyed, Last};
+
+pub type OrderedSeq<T> = shared::ordered_seq::OrderedSeq<SyncFingerLink<Keyed<T>>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserted_and_removed() {
+        let seq: OrderedSeq<_> = [4, 2, 6].into_iter().collect();
+
+        assert!(seq.inserted(5).iter().copied().eq([2, 4, 5, 6]));
+        assert!(seq.removed(&4).iter().copied().eq([2, 6]));
+        assert!(seq.contains(&6));
+    }
+}
@@ -0,0 +1,20 @@
+use crate::shared;
+use crate::sync::finger_tree::SyncFingerLink;
+
+pub use crate::shared::priority_queue::{Max, Prioritized};
+
+pub type PriorityQueue<T> = shared::priority_queue::PriorityQueue<SyncFingerLink<Prioritized<T>>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pushed_and_popped_max() {
+        let queue = PriorityQueue::new().pushed(3).pushed(7).pushed(5);
+
+        assert_eq!(queue.peek_max(), Some(&7));
+        assert_eq!(queue.popped_max().peek_max(), Some(&5));
+        assert_eq!(queue.len(), 3);
+    }
+}
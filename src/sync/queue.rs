@@ -0,0 +1,20 @@
+use crate::shared;
+use crate::sync::list::SyncLink;
+
+pub type Queue<T> = shared::queue::Queue<SyncLink<T>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elements_come_out_in_fifo_order() {
+        let q1: Queue<i32> = (0..10).collect();
+        let q2 = q1.popped_front().pushed_back(10);
+
+        assert!(q1.iter().copied().eq(0..10));
+        assert!(q2.iter().copied().eq(1..11));
+        assert_eq!(q2.peek(), Some(&1));
+        assert_eq!(q2.len(), 10);
+    }
+}
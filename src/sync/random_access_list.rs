@@ -0,0 +1,25 @@
+use crate::shared;
+use crate::sync::{binary_tree::SyncBinaryLink, list::SyncLink};
+
+pub use crate::shared::random_access_list::Digit;
+
+pub type RandomAccessList<T> = shared::random_access_list::RandomAccessList<
+    SyncLink<Digit<SyncBinaryLink<T>>>,
+    SyncBinaryLink<T>,
+>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_and_updated() {
+        let l1: RandomAccessList<usize> = (0..20).collect();
+        let l2 = l1.updated(13, 0).pushed_front(99);
+
+        assert_eq!(l1.get(13), Some(&13));
+        assert_eq!(l2.get(14), Some(&0));
+        assert_eq!(l2.front(), Some(&99));
+        assert_eq!(l2.len(), 21);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{shared::KeyValue, sync::rb_tree::RBTree};
 
-pub struct RBMap<K, V>(RBTree<KeyValue<K, V>>);
+pub struct RBMap<K, V>(pub(crate) RBTree<KeyValue<K, V>>);
 
 //pub struct RBMap<K, V> {
 //    root: Rc<RBNode<(K, V)>>,
@@ -19,6 +19,11 @@ where
         self.0.is_empty()
     }
 
+    /// Returns `true` if both maps share the same root node.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+
     pub fn contains_key(&self, k: &K) -> bool {
         self.0.contains(k)
     }
@@ -59,6 +64,12 @@ where
     }
 }
 
+impl<K, V> Default for RBMap<K, V> {
+    fn default() -> Self {
+        RBMap(RBTree::default())
+    }
+}
+
 impl<K, V> Clone for RBMap<K, V>
 where
     K: Clone,
@@ -69,6 +80,21 @@ where
     }
 }
 
+impl<K: Clone, V: Clone> RBMap<K, V> {
+    /// Rebuilds the map on `Rc`s.
+    ///
+    /// Complexity: O(n)
+    pub fn to_unsync(&self) -> crate::unsync::rb_map::RBMap<K, V> {
+        crate::unsync::rb_map::RBMap(self.0.to_unsync())
+    }
+}
+
+impl<K: Clone, V: Clone> From<&RBMap<K, V>> for crate::unsync::rb_map::RBMap<K, V> {
+    fn from(map: &RBMap<K, V>) -> Self {
+        map.to_unsync()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,7 @@
-use alloc::sync::Arc;
+use alloc::{collections::BTreeMap, string::String, sync::Arc, vec, vec::Vec};
+use core::fmt::Debug;
+
+use crate::shared::draw::{self, Draw};
 
 #[derive(Debug)]
 pub struct RBTree<T> {
@@ -21,6 +24,12 @@ enum Colour {
     Black,
 }
 
+impl<T> Default for RBTree<T> {
+    fn default() -> Self {
+        RBTree { root: None }
+    }
+}
+
 impl<T> Clone for RBTree<T> {
     fn clone(&self) -> Self {
         RBTree {
@@ -45,6 +54,13 @@ impl<T> RBTree<T> {
         }
     }
 
+    /// Returns `true` if both trees share the same root node, or are
+    /// both empty.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        link_ptr_eq(&self.root, &other.root)
+    }
+
+    #[cfg(test)]
     fn tree(colour: Colour, value: T, left: &Self, right: &Self) -> Self {
         RBTree {
             root: Some(Arc::new(RBNode {
@@ -55,12 +71,6 @@ impl<T> RBTree<T> {
             })),
         }
     }
-
-    fn from_node(node: &Arc<RBNode<T>>) -> Self {
-        RBTree {
-            root: Some(node.clone()),
-        }
-    }
 }
 impl<T> RBTree<T> {
     pub fn is_empty(&self) -> bool {
@@ -71,6 +81,7 @@ impl<T> RBTree<T> {
         self.root.as_ref().map(|node| &node.element)
     }
 
+    #[cfg(test)]
     fn root_colour(&self) -> Colour {
         self.root.as_ref().map_or(Colour::Black, |node| node.colour)
     }
@@ -155,170 +166,9 @@ where
         // let t = self.ins_or_rep(x);
         // RBTree::tree(Colour::Black, t.root().unwrap().clone(), &t.left(), &t.right())
     }
-
-    fn ins(&self, x: T) -> Self
-    where
-        T: PartialOrd,
-    {
-        match &self.root {
-            None => RBTree::leaf(x),
-            Some(node) => {
-                if x < node.element {
-                    balance(
-                        node.colour,
-                        node.element.clone(),
-                        &RBTree {
-                            root: node.left.clone(),
-                        }
-                        .ins(x),
-                        &RBTree {
-                            root: node.right.clone(),
-                        },
-                    )
-                } else if x > node.element {
-                    balance(
-                        node.colour,
-                        node.element.clone(),
-                        &RBTree {
-                            root: node.left.clone(),
-                        },
-                        &RBTree {
-                            root: node.right.clone(),
-                        }
-                        .ins(x),
-                    )
-                } else {
-                    RBTree {
-                        root: self.root.clone(),
-                    }
-                }
-            }
-        }
-    }
-
-    fn ins_or_rep(&self, x: T) -> Self
-    where
-        T: PartialOrd,
-    {
-        match &self.root {
-            None => RBTree::leaf(x),
-            Some(node) => {
-                if x < node.element {
-                    balance(
-                        node.colour,
-                        node.element.clone(),
-                        &RBTree {
-                            root: node.left.clone(),
-                        }
-                        .ins_or_rep(x),
-                        &RBTree {
-                            root: node.right.clone(),
-                        },
-                    )
-                } else if x > node.element {
-                    balance(
-                        node.colour,
-                        node.element.clone(),
-                        &RBTree {
-                            root: node.left.clone(),
-                        },
-                        &RBTree {
-                            root: node.right.clone(),
-                        }
-                        .ins_or_rep(x),
-                    )
-                } else {
-                    RBTree {
-                        root: Some(Arc::new(RBNode {
-                            colour: node.colour,
-                            element: x,
-                            left: node.left.clone(),
-                            right: node.right.clone(),
-                        })),
-                    }
-                }
-            }
-        }
-    }
-
-    fn paint(&self, c: Colour) -> Self {
-        assert!(!self.is_empty());
-        RBTree::tree(c, self.root().unwrap().clone(), &self.left(), &self.right())
-    }
-}
-impl<T> RBTree<T> {
-    fn doubled_left(&self) -> bool {
-        !self.is_empty()
-            && self.root_colour() == Colour::Red
-            && !self.left().is_empty()
-            && self.left().root_colour() == Colour::Red
-    }
-
-    fn doubled_right(&self) -> bool {
-        !self.is_empty()
-            && self.root_colour() == Colour::Red
-            && !self.right().is_empty()
-            && self.right().root_colour() == Colour::Red
-    }
-}
-
-fn balance<T>(c: Colour, x: T, left: &RBTree<T>, right: &RBTree<T>) -> RBTree<T>
-where
-    T: Clone,
-{
-    use Colour::*;
-    if c == Black && left.doubled_left() {
-        RBTree::tree(
-            Red,
-            left.root().unwrap().clone(),
-            &left.left().paint(Black),
-            &RBTree::tree(Black, x, &left.right(), right),
-        )
-    } else if c == Black && left.doubled_right() {
-        RBTree::tree(
-            Red,
-            left.right().root().unwrap().clone(),
-            &RBTree::tree(
-                Black,
-                left.root().unwrap().clone(),
-                &left.left(),
-                &left.right().left(),
-            ),
-            &RBTree::tree(Black, x, &left.right().right(), right),
-        )
-    } else if c == Black && right.doubled_left() {
-        RBTree::tree(
-            Red,
-            right.left().root().unwrap().clone(),
-            &RBTree::tree(Black, x, left, &right.left().left()),
-            &RBTree::tree(
-                Black,
-                right.root().unwrap().clone(),
-                &right.left().right(),
-                &right.right(),
-            ),
-        )
-    } else if c == Black && right.doubled_right() {
-        RBTree::tree(
-            Red,
-            right.root().unwrap().clone(),
-            &RBTree::tree(Black, x, left, &right.left()),
-            &right.right().paint(Black),
-        )
-    } else {
-        RBTree::tree(c, x, left, right)
-    }
 }
 
 impl<T> RBNode<T> {
-    fn left(&self) -> Option<&Arc<Self>> {
-        self.left.as_ref().map(|node| node)
-    }
-
-    fn right(&self) -> Option<&Arc<Self>> {
-        self.right.as_ref()
-    }
-
     fn contains<U>(&self, x: &U) -> bool
     where
         T: PartialOrd<U>,
@@ -344,21 +194,6 @@ impl<T> RBNode<T> {
             Some(&self.element)
         }
     }
-    fn doubled_left(&self) -> bool {
-        self.colour == Colour::Red
-            && self
-                .left
-                .as_ref()
-                .map_or(false, |node| node.colour == Colour::Red)
-    }
-
-    fn doubled_right(&self) -> bool {
-        self.colour == Colour::Red
-            && self
-                .right
-                .as_ref()
-                .map_or(false, |node| node.colour == Colour::Red)
-    }
 }
 fn link_inserted<T>(link: &Link<T>, x: T) -> Link<T>
 where
@@ -384,19 +219,18 @@ where
         None => make_leaf_link(x),
         Some(node) => {
             if x < node.element {
-                balance_link(
-                    node.colour,
-                    node.element.clone(),
-                    sorted_insert(&node.left.clone(), x),
-                    node.right.clone(),
-                )
+                let left = sorted_insert(&node.left, x);
+                if link_ptr_eq(&left, &node.left) {
+                    // x was already present, keep the old version
+                    return link.clone();
+                }
+                balance_link(node.colour, node.element.clone(), left, node.right.clone())
             } else if x > node.element {
-                balance_link(
-                    node.colour,
-                    node.element.clone(),
-                    node.left.clone(),
-                    sorted_insert(&node.right.clone(), x),
-                )
+                let right = sorted_insert(&node.right, x);
+                if link_ptr_eq(&right, &node.right) {
+                    return link.clone();
+                }
+                balance_link(node.colour, node.element.clone(), node.left.clone(), right)
             } else {
                 link.clone()
             }
@@ -539,6 +373,9 @@ where
     T: Clone,
 {
     link.as_ref().map(|node| {
+        if node.colour == colour {
+            return node.clone();
+        }
         Arc::new(RBNode {
             colour,
             element: node.element.clone(),
@@ -548,8 +385,130 @@ where
     })
 }
 
+fn link_ptr_eq<T>(a: &Link<T>, b: &Link<T>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+        _ => false,
+    }
+}
+
+impl<T> RBTree<T> {
+    /// Builds a tree from a root and two subtrees without rebalancing.
+    /// Used when converting a tree from the other flavour.
+    pub(crate) fn from_parts(red: bool, element: T, left: Self, right: Self) -> Self {
+        let colour = if red { Colour::Red } else { Colour::Black };
+        RBTree {
+            root: make_link(colour, element, left.root, right.root),
+        }
+    }
+}
+
+impl<T: Clone> RBTree<T> {
+    /// Rebuilds the tree on `Rc`s.
+    ///
+    /// Colours are kept and nodes shared within the tree stay shared.
+    ///
+    /// Complexity: O(n)
+    pub fn to_unsync(&self) -> crate::unsync::rb_tree::RBTree<T> {
+        link_converted(&self.root, &mut BTreeMap::new())
+    }
+}
+
+impl<T: Clone> From<&RBTree<T>> for crate::unsync::rb_tree::RBTree<T> {
+    fn from(tree: &RBTree<T>) -> Self {
+        tree.to_unsync()
+    }
+}
+
+fn link_converted<T: Clone>(
+    link: &Link<T>,
+    memo: &mut BTreeMap<*const RBNode<T>, crate::unsync::rb_tree::RBTree<T>>,
+) -> crate::unsync::rb_tree::RBTree<T> {
+    let node = match link {
+        None => return crate::unsync::rb_tree::RBTree::new(),
+        Some(node) => node,
+    };
+    if let Some(copy) = memo.get(&Arc::as_ptr(node)) {
+        return copy.clone();
+    }
+    let left = link_converted(&node.left, memo);
+    let right = link_converted(&node.right, memo);
+    let copy = crate::unsync::rb_tree::RBTree::from_parts(
+        node.colour == Colour::Red,
+        node.element.clone(),
+        left,
+        right,
+    );
+    memo.insert(Arc::as_ptr(node), copy.clone());
+    copy
+}
+
+impl<T: Debug> RBTree<T> {
+    /// Draws the tree as an outline, one element per line, marked `[R]`
+    /// or `[B]` for its colour. The left subtree comes above the right
+    /// one, and an empty subtree is shown as `·` when its sibling is not
+    /// empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::sync::RBTree;
+    ///
+    /// let tree = RBTree::new().inserted(1).inserted(2);
+    ///
+    /// assert_eq!(tree.pretty(), "1 [B]\n├── ·\n└── 2 [R]");
+    /// ```
+    pub fn pretty(&self) -> String {
+        draw::pretty(self.root.as_deref())
+    }
+
+    /// Draws `versions` as one Graphviz digraph, with a `v<i>` label
+    /// pointing at the root of each. Nodes shared between versions are
+    /// drawn once, filled with their colour.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::sync::RBTree;
+    ///
+    /// let t1 = RBTree::new().inserted(2).inserted(1);
+    /// let t2 = t1.inserted(3);
+    /// let dot = RBTree::to_dot(&[&t1, &t2]);
+    ///
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert!(dot.contains("fillcolor=red"));
+    /// ```
+    pub fn to_dot(versions: &[&Self]) -> String {
+        draw::dot(versions.iter().map(|tree| tree.root.as_deref()).collect())
+    }
+}
+
+impl<T: Debug> Draw for &RBNode<T> {
+    type Element = T;
+
+    const BINARY: bool = true;
+
+    fn id(&self) -> *const () {
+        *self as *const RBNode<T> as *const ()
+    }
+    fn element(&self) -> &T {
+        &self.element
+    }
+    fn red(&self) -> Option<bool> {
+        Some(self.colour == Colour::Red)
+    }
+    fn children(&self) -> Vec<Option<Self>> {
+        match (self.left.as_deref(), self.right.as_deref()) {
+            (None, None) => Vec::new(),
+            (left, right) => vec![left, right],
+        }
+    }
+}
+
 impl<T: PartialEq + Clone> PartialEq for RBTree<T> {
     fn eq(&self, other: &Self) -> bool {
+        if self.ptr_eq(other) {
+            return true;
+        }
         if self.is_empty() && other.is_empty() {
             return true;
         } else if self.is_empty() || other.is_empty() {
@@ -570,12 +529,6 @@ impl<T: PartialEq + Clone> PartialEq for RBTree<T> {
     }
 }
 
-impl<T: PartialEq> PartialEq for RBNode<T> {
-    fn eq(&self, other: &Self) -> bool {
-        false
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -689,10 +642,52 @@ mod tests {
         assert_eq!(t4.root(), Some(&KV(4, "d")));
     }
 
+    #[test]
+    fn ptr_eq_detects_shared_root() {
+        let t1 = RBTree::new().inserted(1).inserted(2);
+        let t2 = RBTree::new().inserted(1).inserted(2);
+
+        assert!(t1.ptr_eq(&t1.clone()));
+        assert!(!t1.ptr_eq(&t2));
+        assert!(t1.inserted(2).ptr_eq(&t1));
+        assert!(RBTree::<i32>::new().ptr_eq(&RBTree::new()));
+    }
+
     #[test]
     fn get_or_default() {
         let t1 = RBTree::new();
 
         assert_eq!(t1.get_or_default(&5, &7), &7);
     }
+
+    fn assert_same_shape<T: Clone + PartialEq + core::fmt::Debug>(a: &RBTree<T>, b: &RBTree<T>) {
+        assert_eq!(a.root(), b.root());
+        assert_eq!(a.root_colour(), b.root_colour());
+        if !a.is_empty() {
+            assert_same_shape(&a.left(), &b.left());
+            assert_same_shape(&a.right(), &b.right());
+        }
+    }
+
+    #[test]
+    fn round_trip_keeps_shape_and_colours() {
+        let mut tree = RBTree::new();
+        for x in 0..100 {
+            tree = tree.inserted(x * 7 % 101);
+        }
+
+        let converted = tree.to_unsync();
+        assert!((0..100).all(|x| converted.contains(&(x * 7 % 101))));
+        assert_same_shape(&tree, &converted.to_sync());
+    }
+
+    #[test]
+    fn conversion_keeps_shared_subtrees_shared() {
+        let shared = RBTree::leaf(1);
+        let tree = RBTree::tree(Colour::Black, 2, &shared, &shared);
+
+        let converted = tree.to_unsync().to_sync();
+        assert!(converted.left().ptr_eq(&converted.right()));
+        assert!(!converted.left().ptr_eq(&shared));
+    }
 }
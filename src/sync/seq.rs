@@ -0,0 +1,21 @@
+use crate::shared;
+use crate::sync::finger_tree::SyncFingerLink;
+
+pub use crate::shared::seq::{Counted, Size};
+
+pub type Seq<T> = shared::seq::Seq<SyncFingerLink<Counted<T>>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_at_and_appended() {
+        let seq: Seq<_> = (0..300).collect();
+        let (front, back) = seq.split_at(120);
+
+        assert_eq!(front.len(), 120);
+        assert_eq!(back.get(0), Some(&120));
+        assert_eq!(front.appended(&back), seq);
+    }
+}
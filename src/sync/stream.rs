@@ -0,0 +1,77 @@
+use alloc::sync::Arc;
+
+use crate::shared::{
+    self,
+    stream::{Rebind, StreamLink, StreamNode},
+};
+use crate::sync::{lazy::Memo, list::SyncLink};
+
+pub type Stream<T> = shared::stream::Stream<SyncStreamLink<T>>;
+
+pub(crate) type SyncStreamLink<T> = Arc<Node<T>>;
+
+pub struct Node<T>(StreamNode<SyncStreamLink<T>>);
+
+impl<T> StreamLink for Arc<Node<T>> {
+    type Element = T;
+    type Tail = Memo<Stream<T>>;
+    type ListLink = SyncLink<T>;
+
+    fn new(node: StreamNode<Self>) -> Self {
+        Arc::new(Node(node))
+    }
+
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+
+    fn node(&self) -> &StreamNode<Self> {
+        &self.0
+    }
+
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+
+    fn try_unwrap(self) -> Result<StreamNode<Self>, Self> {
+        Arc::try_unwrap(self).map(|node| node.0)
+    }
+}
+
+impl<T, U> Rebind<U> for Arc<Node<T>> {
+    type Link = Arc<Node<U>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::List;
+    use crate::synced_list;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn tail_is_evaluated_at_most_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let stream = Stream::cons_lazy(1, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Stream::cons(2, &Stream::new())
+        });
+        let shared = stream.clone();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(stream.popped_front().front(), Some(&2));
+        assert_eq!(shared.popped_front().front(), Some(&2));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn iterate_map_filter_take() {
+        let odd_squares = Stream::iterate(0, |x| x + 1)
+            .map(|x| x * x)
+            .filter(|x| x % 2 == 1)
+            .take(3);
+
+        assert_eq!(odd_squares.to_list(), synced_list!(25, 9, 1));
+    }
+}
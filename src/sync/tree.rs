@@ -0,0 +1,235 @@
+use alloc::sync::Arc;
+use core::mem;
+
+use crate::shared::{
+    self,
+    tree::{drop_children, list_from, ConversionMemo, Crumb, TreeLink},
+};
+use crate::sync::list::{List, SyncLink};
+
+pub use crate::shared::selector::Selector;
+
+pub type Tree<T> = shared::tree::Tree<SyncTreeLink<T>>;
+pub type TreeEdit<T> = shared::tree::TreeEdit<SyncTreeLink<T>>;
+pub type TreeZipper<T> =
+    shared::tree::TreeZipper<SyncTreeLink<T>, SyncLink<Crumb<SyncTreeLink<T>>>>;
+
+pub(crate) type SyncTreeLink<T> = Arc<Node<T>>;
+
+pub struct Node<T> {
+    element: T,
+    children: List<Tree<T>>,
+}
+
+impl<T> TreeLink for Arc<Node<T>> {
+    type ValueType = T;
+    type ListLink = SyncLink<Tree<T>>;
+
+    fn node(element: Self::ValueType, children: List<Tree<T>>) -> Self {
+        Arc::new(Node { element, children })
+    }
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+    fn get_element(&self) -> &Self::ValueType {
+        &self.element
+    }
+    fn children(&self) -> &List<Tree<T>> {
+        &self.children
+    }
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+    fn as_ptr(&self) -> *const () {
+        Arc::as_ptr(self) as *const ()
+    }
+    fn try_unwrap_children(self) -> Option<List<Tree<T>>> {
+        Arc::try_unwrap(self)
+            .ok()
+            .map(|mut node| mem::take(&mut node.children))
+    }
+}
+
+impl<T> Drop for Node<T> {
+    fn drop(&mut self) {
+        drop_children::<Arc<Node<T>>>(mem::take(&mut self.children));
+    }
+}
+
+impl<T> Tree<T> {
+    /// Returns a tree of the same shape with `f` applied to every
+    /// element, called in preorder.
+    pub fn map<U, F>(&self, f: F) -> Tree<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        self.mapped(f)
+    }
+
+    /// Folds the tree bottom up: `f` gets every element together with
+    /// the results for its children, in order. Returns `None` for an
+    /// empty tree.
+    pub fn fold<R, F>(&self, mut f: F) -> Option<R>
+    where
+        F: FnMut(&T, List<R>) -> R,
+    {
+        self.folded(|x, children| f(x, list_from(children)))
+    }
+
+    /// Combines two trees node by node with `f`, keeping the shape they
+    /// have in common.
+    pub fn zip_with<U, V, F>(&self, other: &Tree<U>, f: F) -> Tree<V>
+    where
+        F: FnMut(&T, &U) -> V,
+    {
+        self.zipped_with(other, f)
+    }
+}
+
+impl<T: Clone> Tree<T> {
+    /// Rebuilds the tree on `Rc`s.
+    ///
+    /// Subtrees and tails of child lists that are shared within the tree
+    /// are converted once and stay shared.
+    ///
+    /// Complexity: O(n)
+    pub fn to_unsync(&self) -> crate::unsync::tree::Tree<T> {
+        self.converted(&mut ConversionMemo::new(), T::clone)
+    }
+}
+
+impl<T: Clone> From<&Tree<T>> for crate::unsync::tree::Tree<T> {
+    fn from(tree: &Tree<T>) -> Self {
+        tree.to_unsync()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn sample() -> Tree<i32> {
+        let t3 = Tree::new(3, List::from_value(Tree::leaf(4)));
+        let children = List::new()
+            .pushed_front(Tree::leaf(5))
+            .pushed_front(t3)
+            .pushed_front(Tree::leaf(2));
+        Tree::new(1, children)
+    }
+
+    #[test]
+    fn leaf_creates_tree_w_no_children() {
+        let tree = Tree::leaf(5);
+
+        assert_eq!(tree.root(), Some(&5));
+        assert!(tree.children().unwrap().is_empty());
+        assert!(Tree::<i32>::empty().is_empty());
+    }
+
+    #[test]
+    fn traversals_visit_all_nodes() {
+        let tree = sample();
+
+        assert!(tree.preorder().copied().eq([1, 2, 3, 4, 5]));
+        assert!(tree.postorder().copied().eq([2, 4, 3, 5, 1]));
+        assert!(tree.level_order().copied().eq([1, 2, 3, 5, 4]));
+        assert!(tree.leaves().copied().eq([2, 4, 5]));
+    }
+
+    #[test]
+    fn transformations_keep_child_order() {
+        let tree = sample();
+
+        assert!(tree.map(|x| x * 2).preorder().copied().eq([2, 4, 6, 8, 10]));
+        assert_eq!(tree.fold(|x, sums| x + sums.iter().sum::<i32>()), Some(15));
+        assert!(tree
+            .filter_prune(|&x| x != 3)
+            .preorder()
+            .copied()
+            .eq([1, 2, 5]));
+        assert!(tree
+            .zip_with(&tree, |a, b| a + b)
+            .preorder()
+            .copied()
+            .eq([2, 4, 6, 8, 10]));
+    }
+
+    #[test]
+    fn updated_at_shares_siblings() {
+        let t1 = sample();
+        let t2 = t1.updated_at(&[1, 0], |x| x * 10);
+        let c1: Vec<_> = t1.children().unwrap().iter().collect();
+        let c2: Vec<_> = t2.children().unwrap().iter().collect();
+
+        assert_eq!(t2.get_at(&[1, 0]), Some(&40));
+        assert!(c1[0].ptr_eq(c2[0]));
+        assert!(c1[2].ptr_eq(c2[2]));
+    }
+
+    #[test]
+    fn diff_and_apply_round_trip() {
+        let old = sample();
+        let new = old
+            .removed_at(&[0])
+            .inserted_child_at(&[0], 0, Tree::leaf(6));
+        let edits = Tree::diff(&old, &new);
+
+        assert_eq!(old.apply(&edits), new);
+        assert!(matches!(&edits[0], TreeEdit::Delete { path } if path == &[0]));
+    }
+
+    #[test]
+    fn select_returns_paths() {
+        let tree = sample();
+        let found = tree.select(&Selector::new().filter(|x| *x == 3).child());
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, [1, 0]);
+        assert_eq!(found[0].1.root(), Some(&4));
+    }
+
+    #[test]
+    fn zipper_edits_and_rebuilds() {
+        let tree = sample();
+        let edited = TreeZipper::new(&tree)
+            .down(1)
+            .unwrap()
+            .replaced(Tree::leaf(30))
+            .to_tree();
+
+        assert!(edited.preorder().copied().eq([1, 2, 30, 5]));
+        assert!(TreeZipper::new(&tree)
+            .down(2)
+            .unwrap()
+            .to_tree()
+            .ptr_eq(&tree));
+    }
+
+    #[test]
+    fn to_unsync_keeps_shared_subtrees_shared() {
+        let shared = Tree::new(2, List::from_value(Tree::leaf(3)));
+        let tree = Tree::new(
+            1,
+            List::new()
+                .pushed_front(shared.clone())
+                .pushed_front(shared),
+        );
+
+        let converted = tree.to_unsync();
+        assert!(converted.preorder().copied().eq([1, 2, 3, 2, 3]));
+        let children: Vec<_> = converted.children().unwrap().iter().collect();
+        assert!(children[0].ptr_eq(children[1]));
+        assert_eq!(crate::unsync::tree::Tree::from(&tree), converted);
+    }
+
+    #[test]
+    fn dropping_deep_chain_does_not_overflow() {
+        let mut tree = Tree::leaf(0);
+        for i in 1..1_000_000 {
+            tree = Tree::new(i, List::from_value(tree));
+        }
+
+        drop(tree);
+    }
+}
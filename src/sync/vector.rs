@@ -0,0 +1,57 @@
+use alloc::sync::Arc;
+
+use crate::shared::{
+    self,
+    vector::{VectorLink, VectorNode},
+};
+
+pub type Vector<T> = shared::vector::Vector<SyncVectorLink<T>>;
+
+pub(crate) type SyncVectorLink<T> = Arc<Node<T>>;
+
+pub struct Node<T>(VectorNode<SyncVectorLink<T>>);
+
+impl<T> VectorLink for Arc<Node<T>> {
+    type ValueType = T;
+
+    fn new(node: VectorNode<Self>) -> Self {
+        Arc::new(Node(node))
+    }
+
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+
+    fn node(&self) -> &VectorNode<Self> {
+        &self.0
+    }
+
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pushed_back_and_popped_back() {
+        let v1: Vector<_> = (0..100).collect();
+        let v2 = v1.pushed_back(100).popped_back().popped_back();
+
+        assert_eq!(v1.len(), 100);
+        assert_eq!(v2.back(), Some(&98));
+        assert!(v2.iter().copied().eq(0..99));
+    }
+
+    #[test]
+    fn appended_and_split_at_round_trip() {
+        let vector: Vector<_> = (0..2000).collect();
+        let (left, right) = vector.split_at(777);
+
+        assert_eq!(left.len(), 777);
+        assert_eq!(right.get(0), Some(&777));
+        assert_eq!(left.appended(&right), vector);
+    }
+}
@@ -0,0 +1,233 @@
+use alloc::rc::Rc;
+use core::mem;
+
+use crate::shared::{
+    self,
+    catenable_list::{drop_children, CatenableLink},
+    lazy::{Lazy, Reclaim, Suspend},
+};
+use crate::unsync::{lazy::Delayed, Queue};
+
+pub type CatenableList<T> = shared::catenable_list::CatenableList<UnsyncCatenableLink<T>>;
+
+type UnsyncCatenableLink<T> = Rc<Node<T>>;
+
+/// A list in the children of a node, or the suspended linking of the
+/// lists in a queue, shared by every node that holds it.
+pub struct Suspended<T>(Rc<Delayed<Queue<Suspended<T>>, CatenableList<T>>>);
+
+pub struct Node<T> {
+    element: T,
+    children: Queue<Suspended<T>>,
+}
+
+impl<T> CatenableLink for Rc<Node<T>> {
+    type ValueType = T;
+    type Suspended = Suspended<T>;
+    type QueueLink = crate::unsync::list::UnsyncLink<Suspended<T>>;
+
+    fn node(element: Self::ValueType, children: Queue<Suspended<T>>) -> Self {
+        Rc::new(Node { element, children })
+    }
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+    fn get_element(&self) -> &Self::ValueType {
+        &self.element
+    }
+    fn children(&self) -> &Queue<Suspended<T>> {
+        &self.children
+    }
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
+    }
+    fn try_unwrap_children(self) -> Option<Queue<Suspended<T>>> {
+        Rc::try_unwrap(self)
+            .ok()
+            .map(|mut node| mem::take(&mut node.children))
+    }
+}
+
+impl<T> Drop for Node<T> {
+    fn drop(&mut self) {
+        drop_children::<Rc<Node<T>>>(mem::take(&mut self.children));
+    }
+}
+
+impl<T> Clone for Suspended<T> {
+    fn clone(&self) -> Self {
+        Suspended(self.0.clone())
+    }
+}
+
+impl<T> Lazy for Suspended<T> {
+    type Value = CatenableList<T>;
+
+    fn evaluated(list: CatenableList<T>) -> Self {
+        Suspended(Rc::new(Delayed::evaluated(list)))
+    }
+    fn force(&self) -> &CatenableList<T> {
+        self.0.force()
+    }
+    fn get(&self) -> Option<&CatenableList<T>> {
+        self.0.get()
+    }
+    fn get_mut(&mut self) -> Option<&mut CatenableList<T>> {
+        Rc::get_mut(&mut self.0).and_then(Delayed::get_mut)
+    }
+}
+
+impl<T> Suspend<Queue<Suspended<T>>> for Suspended<T> {
+    fn suspended(
+        queue: Queue<Suspended<T>>,
+        body: fn(Queue<Suspended<T>>) -> CatenableList<T>,
+    ) -> Self {
+        Suspended(Rc::new(Delayed::suspended(queue, body)))
+    }
+}
+
+impl<T> Reclaim<Queue<Suspended<T>>> for Suspended<T> {
+    fn reclaim(&mut self) -> Option<Queue<Suspended<T>>> {
+        Rc::get_mut(&mut self.0).and_then(Delayed::reclaim)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn create_empty() {
+        let list = CatenableList::<i32>::new();
+
+        assert!(list.is_empty());
+        assert_eq!(list.front(), None);
+        assert_eq!(list.iter().next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn popped_front_on_empty_list_panics() {
+        CatenableList::<i32>::new().popped_front();
+    }
+
+    #[test]
+    fn pushed_front_and_pushed_back() {
+        let list = CatenableList::new()
+            .pushed_back(2)
+            .pushed_front(1)
+            .pushed_back(3)
+            .pushed_front(0);
+
+        assert!(list.iter().copied().eq(0..4));
+    }
+
+    #[test]
+    fn appended_keeps_both_lists() {
+        let a: CatenableList<i32> = (0..5).collect();
+        let b: CatenableList<i32> = (5..10).collect();
+        let ab = a.appended(&b);
+
+        assert!(ab.iter().copied().eq(0..10));
+        assert!(a.iter().copied().eq(0..5));
+        assert!(b.iter().copied().eq(5..10));
+        assert!(a.appended(&CatenableList::new()).ptr_eq(&a));
+        assert!(CatenableList::new().appended(&b).ptr_eq(&b));
+    }
+
+    #[test]
+    fn popped_front_after_many_appends() {
+        let mut list = CatenableList::new();
+        for i in 0..50 {
+            let fragment: CatenableList<i32> = (i * 10..i * 10 + 10).collect();
+            list = list.appended(&fragment);
+        }
+        for i in 0..500 {
+            assert_eq!(list.front(), Some(&i));
+            list = list.popped_front();
+        }
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn old_versions_are_unchanged() {
+        let base: CatenableList<i32> = (0..10).collect();
+        let l1 = base.popped_front().appended(&base);
+        let l2 = l1.popped_front().pushed_back(-1);
+
+        assert!(base.iter().copied().eq(0..10));
+        assert!(l1.iter().copied().eq((1..10).chain(0..10)));
+        assert!(l2.iter().copied().eq((2..10).chain(0..10).chain([-1])));
+    }
+
+    #[test]
+    fn dropping_long_chain_does_not_overflow() {
+        let mut list = CatenableList::new();
+        for i in 0..100_000 {
+            list = CatenableList::from_value(i).appended(&list);
+        }
+        let popped = list.popped_front();
+        drop(list);
+        drop(popped);
+    }
+
+    #[test]
+    fn matches_vec_when_versions_are_reused() {
+        let mut seed: u64 = 7;
+        let mut versions = vec![(CatenableList::new(), Vec::new())];
+        for i in 0..3000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let (list, model) = versions[(seed >> 16) as usize % versions.len()].clone();
+            let (mut list, mut model): (CatenableList<i32>, Vec<i32>) = (list, model);
+            match (seed >> 8) % 4 {
+                0 => {
+                    list = list.pushed_front(i);
+                    model.insert(0, i);
+                }
+                1 => {
+                    list = list.pushed_back(i);
+                    model.push(i);
+                }
+                2 => {
+                    let (other, rest) = &versions[(seed >> 24) as usize % versions.len()];
+                    list = list.appended(other);
+                    model.extend(rest);
+                }
+                3 if !model.is_empty() => {
+                    list = list.popped_front();
+                    model.remove(0);
+                }
+                _ => {}
+            }
+            assert_eq!(list.front(), model.first());
+            if i % 50 == 0 {
+                assert!(list.iter().eq(model.iter()));
+            }
+            versions.push((list, model));
+        }
+    }
+
+    #[test]
+    fn dropping_long_chain_of_suspensions_does_not_overflow() {
+        let mut list = CatenableList::new();
+        for i in 0..100_000 {
+            list = CatenableList::from_value(i).appended(&list).pushed_back(-i);
+        }
+        for _ in 0..100_000 {
+            list = list.popped_front();
+        }
+        assert!(list.iter().copied().eq((0..100_000).map(|i| -i)));
+        drop(list);
+    }
+
+    #[test]
+    fn test_partial_eq() {
+        let a: CatenableList<i32> = (0..4).collect();
+        let b = CatenableList::from_value(0).appended(&(1..4).collect());
+
+        assert_eq!(a, b);
+        assert_ne!(a, b.popped_front());
+        assert_eq!(alloc::format!("{:?}", a), "[0, 1, 2, 3]");
+    }
+}
@@ -0,0 +1,191 @@
+use crate::shared;
+use crate::unsync::stream::UnsyncStreamLink;
+
+pub type Deque<T> = shared::deque::Deque<UnsyncStreamLink<T>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{collections::VecDeque, vec, vec::Vec};
+
+    #[test]
+    fn new_creates_empty_deque() {
+        let deque = Deque::<i32>::new();
+
+        assert!(deque.is_empty());
+        assert_eq!(deque.len(), 0);
+        assert_eq!(deque.front(), None);
+        assert_eq!(deque.back(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn popped_front_on_empty_deque_panics() {
+        Deque::<i32>::new().popped_front();
+    }
+
+    #[test]
+    #[should_panic]
+    fn popped_back_on_empty_deque_panics() {
+        Deque::<i32>::new().popped_back();
+    }
+
+    #[test]
+    fn single_element_is_front_and_back() {
+        let d1 = Deque::new().pushed_front(1);
+        let d2 = Deque::new().pushed_back(1);
+
+        for d in [d1, d2] {
+            assert_eq!(d.front(), Some(&1));
+            assert_eq!(d.back(), Some(&1));
+            assert!(d.popped_front().is_empty());
+            assert!(d.popped_back().is_empty());
+        }
+    }
+
+    #[test]
+    fn pushed_back_then_popped_front_is_fifo() {
+        let mut deque: Deque<i32> = (0..50).collect();
+        for i in 0..50 {
+            assert_eq!(deque.front(), Some(&i));
+            deque = deque.popped_front();
+        }
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn pushed_back_then_popped_back_is_lifo() {
+        let mut deque: Deque<i32> = (0..50).collect();
+        for i in (0..50).rev() {
+            assert_eq!(deque.back(), Some(&i));
+            deque = deque.popped_back();
+        }
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn pushed_front_then_popped_front_is_lifo() {
+        let mut deque = Deque::new();
+        for i in 0..50 {
+            deque = deque.pushed_front(i);
+        }
+        assert!(deque.iter().copied().eq((0..50).rev()));
+        for i in (0..50).rev() {
+            assert_eq!(deque.front(), Some(&i));
+            deque = deque.popped_front();
+        }
+    }
+
+    #[test]
+    fn iter_is_double_ended() {
+        let deque: Deque<i32> = (0..10).collect();
+        let deque = deque.pushed_front(-1).popped_back();
+
+        assert!(deque.iter().copied().eq(-1..9));
+        assert!(deque.iter().rev().copied().eq((-1..9).rev()));
+        assert_eq!(deque.iter().len(), 10);
+
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), Some(&-1));
+        assert_eq!(iter.next_back(), Some(&8));
+        assert_eq!(iter.next(), Some(&0));
+    }
+
+    #[test]
+    fn sliding_window() {
+        let mut window = Deque::new();
+        let mut sums = Vec::new();
+        for i in 0..20 {
+            window = window.pushed_back(i);
+            if window.len() > 3 {
+                window = window.popped_front();
+            }
+            sums.push(window.iter().sum::<i32>());
+        }
+
+        assert_eq!(&sums[..4], &[0, 1, 3, 6]);
+        assert_eq!(sums[19], 17 + 18 + 19);
+    }
+
+    #[test]
+    fn old_versions_are_unchanged() {
+        let base: Deque<i32> = (0..10).collect();
+        let mut versions = Vec::new();
+        let mut deque = base.clone();
+        for i in 0..100 {
+            deque = if i % 2 == 0 {
+                deque.popped_front().pushed_back(i)
+            } else {
+                deque.popped_back().pushed_front(i)
+            };
+            versions.push(deque.clone());
+        }
+
+        assert!(base.iter().copied().eq(0..10));
+        assert!(base.popped_back().iter().copied().eq(0..9));
+        assert!(base.popped_front().iter().copied().eq(1..10));
+        assert_eq!(
+            versions[0].iter().copied().collect::<Vec<_>>(),
+            (1..10).chain([0]).collect::<Vec<_>>()
+        );
+        assert_eq!(versions[1].front(), Some(&1));
+        assert_eq!(versions[1].back(), Some(&9));
+    }
+
+    #[test]
+    fn test_partial_eq() {
+        let d1: Deque<i32> = (1..4).collect();
+        let d2 = Deque::new().pushed_front(2).pushed_front(1).pushed_back(3);
+
+        assert_eq!(d1, d2);
+        assert_ne!(d1, d2.popped_back());
+        assert_eq!(alloc::format!("{:?}", d1), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn matches_vec_deque_when_versions_are_reused() {
+        let mut seed: u64 = 11;
+        let mut versions = vec![(Deque::new(), VecDeque::new())];
+        for i in 0..3000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let (deque, model) = versions[(seed >> 16) as usize % versions.len()].clone();
+            let (mut deque, mut model): (Deque<i32>, VecDeque<i32>) = (deque, model);
+            match (seed >> 8) % 4 {
+                0 => {
+                    deque = deque.pushed_front(i);
+                    model.push_front(i);
+                }
+                1 => {
+                    deque = deque.pushed_back(i);
+                    model.push_back(i);
+                }
+                2 if !model.is_empty() => {
+                    deque = deque.popped_front();
+                    model.pop_front();
+                }
+                3 if !model.is_empty() => {
+                    deque = deque.popped_back();
+                    model.pop_back();
+                }
+                _ => {}
+            }
+            assert_eq!(deque.len(), model.len());
+            assert_eq!(deque.front(), model.front());
+            assert_eq!(deque.back(), model.back());
+            if i % 50 == 0 {
+                assert!(deque.iter().eq(model.iter()));
+                assert!(deque.iter().rev().eq(model.iter().rev()));
+                let (mut iter, mut expected) = (deque.iter(), model.iter());
+                for step in 0..model.len() {
+                    if step % 3 == 0 {
+                        assert_eq!(iter.next_back(), expected.next_back());
+                    } else {
+                        assert_eq!(iter.next(), expected.next());
+                    }
+                    assert_eq!(iter.len(), expected.len());
+                }
+            }
+            versions.push((deque, model));
+        }
+    }
+}
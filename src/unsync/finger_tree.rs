@@ -0,0 +1,208 @@
+use alloc::rc::Rc;
+
+use crate::shared::{
+    self,
+    finger_tree::{FingerLink, FingerNode},
+};
+
+pub use crate::shared::finger_tree::{Measured, Monoid};
+
+pub type FingerTree<A> = shared::finger_tree::FingerTree<UnsyncFingerLink<A>>;
+
+pub(crate) type UnsyncFingerLink<A> = Rc<Node<A>>;
+
+pub struct Node<A: Measured>(FingerNode<UnsyncFingerLink<A>>);
+
+impl<A: Measured> FingerLink for Rc<Node<A>> {
+    type Element = A;
+
+    fn new(node: FingerNode<Self>) -> Self {
+        Rc::new(Node(node))
+    }
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+    fn node(&self) -> &FingerNode<Self> {
+        &self.0
+    }
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{collections::VecDeque, format, vec::Vec};
+
+    /// Counts the elements and sums them, so a tree can be split by
+    /// either.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Summed(u32);
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct CountSum(usize, u32);
+
+    impl Monoid for CountSum {
+        fn empty() -> Self {
+            CountSum(0, 0)
+        }
+
+        fn combine(&self, other: &Self) -> Self {
+            CountSum(self.0 + other.0, self.1 + other.1)
+        }
+    }
+
+    impl Measured for Summed {
+        type Measure = CountSum;
+
+        fn measure(&self) -> CountSum {
+            CountSum(1, self.0)
+        }
+    }
+
+    fn tree(range: core::ops::Range<u32>) -> FingerTree<Summed> {
+        range.map(Summed).collect()
+    }
+
+    fn values(tree: &FingerTree<Summed>) -> Vec<u32> {
+        tree.iter().map(|x| x.0).collect()
+    }
+
+    #[test]
+    fn empty_creates_tree() {
+        let tree = FingerTree::<Summed>::new();
+
+        assert!(tree.is_empty());
+        assert_eq!(tree.measure(), CountSum(0, 0));
+        assert_eq!(tree.front(), None);
+        assert_eq!(tree.back(), None);
+        assert_eq!(tree.find(|_| true), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn popped_front_on_empty_tree_panics() {
+        FingerTree::<Summed>::new().popped_front();
+    }
+
+    #[test]
+    fn measure_covers_all_elements() {
+        for n in [1, 2, 8, 9, 100, 1000] {
+            let tree = tree(0..n);
+
+            assert_eq!(tree.measure(), CountSum(n as usize, (0..n).sum()));
+            assert_eq!(tree.front(), Some(&Summed(0)));
+            assert_eq!(tree.back(), Some(&Summed(n - 1)));
+        }
+    }
+
+    #[test]
+    fn ends_behave_like_vec_deque() {
+        let mut seed = 11u32;
+        let mut next = |bound: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 8) % bound
+        };
+        let mut tree = FingerTree::new();
+        let mut model = VecDeque::new();
+        for step in 0..5000 {
+            match next(5) {
+                0 | 1 => {
+                    tree = tree.pushed_front(Summed(step));
+                    model.push_front(step);
+                }
+                2 | 3 => {
+                    tree = tree.pushed_back(Summed(step));
+                    model.push_back(step);
+                }
+                _ if model.is_empty() => {}
+                _ if next(2) == 0 => {
+                    tree = tree.popped_front();
+                    model.pop_front();
+                }
+                _ => {
+                    tree = tree.popped_back();
+                    model.pop_back();
+                }
+            }
+            assert_eq!(tree.front().map(|x| x.0), model.front().copied());
+            assert_eq!(tree.back().map(|x| x.0), model.back().copied());
+        }
+        assert!(tree.iter().map(|x| x.0).eq(model.into_iter()));
+    }
+
+    #[test]
+    fn split_by_count_and_by_sum() {
+        let tree = tree(0..200);
+        for at in [0, 1, 5, 99, 150, 199, 200, 300] {
+            let (front, back) = tree.split(|m| m.0 > at);
+            let at = at.min(200);
+
+            assert_eq!(values(&front), (0..at as u32).collect::<Vec<_>>());
+            assert_eq!(values(&back), (at as u32..200).collect::<Vec<_>>());
+            assert_eq!(front.measure().0 + back.measure().0, 200);
+        }
+
+        // 0 + 1 + ... + 44 = 990, the first prefix summing past 1000 ends
+        // with 45.
+        let (front, back) = tree.split(|m| m.1 > 1000);
+        assert_eq!(front.back(), Some(&Summed(44)));
+        assert_eq!(back.front(), Some(&Summed(45)));
+        assert_eq!(tree.find(|m| m.1 > 1000), Some(&Summed(45)));
+    }
+
+    #[test]
+    fn appended_joins_in_order() {
+        for (m, n) in [
+            (0, 3),
+            (3, 0),
+            (1, 1),
+            (1, 50),
+            (50, 1),
+            (9, 9),
+            (300, 1000),
+        ] {
+            let joined = tree(0..m).appended(&tree(m..m + n));
+
+            assert_eq!(values(&joined), (0..m + n).collect::<Vec<_>>());
+            assert_eq!(joined.measure().0, (m + n) as usize);
+            assert!((0..m + n).all(|i| joined.find(|c| c.0 > i as usize) == Some(&Summed(i))));
+        }
+    }
+
+    #[test]
+    fn split_and_append_round_trip_everywhere() {
+        let tree = tree(0..500);
+        for at in 0..=500 {
+            let (front, back) = tree.split(|m| m.0 > at);
+            assert_eq!(front.appended(&back), tree);
+            let swapped = back.appended(&front);
+            assert_eq!(
+                swapped.front().map(|x| x.0),
+                Some(if at == 500 { 0 } else { at as u32 })
+            );
+        }
+    }
+
+    #[test]
+    fn old_versions_are_kept() {
+        let t1 = tree(0..100);
+        let t2 = t1.pushed_front(Summed(1000)).popped_back();
+        let (t3, _) = t1.split(|m| m.0 > 10);
+
+        assert_eq!(t1.measure().0, 100);
+        assert_eq!(values(&t1), (0..100).collect::<Vec<_>>());
+        assert_eq!(t2.front(), Some(&Summed(1000)));
+        assert_eq!(t3.measure().0, 10);
+    }
+
+    #[test]
+    fn ptr_eq_and_debug() {
+        let t1 = tree(0..10);
+
+        assert!(t1.ptr_eq(&t1.clone()));
+        assert!(!t1.ptr_eq(&t1.pushed_back(Summed(1))));
+        assert_eq!(format!("{:?}", tree(0..2)), "[Summed(0), Summed(1)]");
+    }
+}
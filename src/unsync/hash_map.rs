@@ -0,0 +1,256 @@
+use alloc::rc::Rc;
+
+use crate::shared::{
+    self,
+    hash_map::{HashLink, HashNode},
+};
+
+pub use crate::shared::hash_map::{DefaultHashBuilder, DefaultHasher};
+
+pub type HashMap<K, V, S = DefaultHashBuilder> = shared::hash_map::HashMap<UnsyncHashLink<K, V>, S>;
+
+pub(crate) type UnsyncHashLink<K, V> = Rc<Node<K, V>>;
+
+pub struct Node<K, V>(HashNode<UnsyncHashLink<K, V>>);
+
+impl<K, V> HashLink for Rc<Node<K, V>> {
+    type Key = K;
+    type Value = V;
+
+    fn new(node: HashNode<Self>) -> Self {
+        Rc::new(Node(node))
+    }
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+    fn node(&self) -> &HashNode<Self> {
+        &self.0
+    }
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{collections::BTreeMap, format, vec::Vec};
+    use core::hash::{BuildHasherDefault, Hasher};
+
+    /// Hashes a `u64` to itself, so tests can place keys in the trie.
+    #[derive(Default)]
+    struct IdentityHasher(u64);
+
+    impl Hasher for IdentityHasher {
+        fn write(&mut self, bytes: &[u8]) {
+            for &byte in bytes {
+                self.0 = self.0 << 8 | u64::from(byte);
+            }
+        }
+        fn write_u64(&mut self, n: u64) {
+            self.0 = n;
+        }
+        fn finish(&self) -> u64 {
+            self.0
+        }
+    }
+
+    type Identity = BuildHasherDefault<IdentityHasher>;
+
+    /// Hashes every key to the same value.
+    #[derive(Default)]
+    struct ConstantHasher;
+
+    impl Hasher for ConstantHasher {
+        fn write(&mut self, _: &[u8]) {}
+        fn finish(&self) -> u64 {
+            42
+        }
+    }
+
+    type Constant = BuildHasherDefault<ConstantHasher>;
+
+    #[test]
+    fn empty_creates_map() {
+        let map = HashMap::<i32, &str>::new();
+
+        assert!(map.is_empty());
+        assert_eq!(map.len(), 0);
+        assert!(!map.contains_key(&5));
+        assert_eq!(map.iter().next(), None);
+    }
+
+    #[test]
+    fn inserted_returns_larger_map() {
+        let m1 = HashMap::new();
+        let m2 = m1.inserted("a", 1).inserted("b", 2);
+
+        assert!(m1.is_empty());
+        assert_eq!(m2.len(), 2);
+        assert_eq!(m2.get("a"), Some(&1));
+        assert_eq!(m2.get_key_value("b"), Some((&"b", &2)));
+        assert_eq!(m2.get("c"), None);
+        assert_eq!(m2.get_or_default("c", &0), &0);
+    }
+
+    #[test]
+    fn ptr_eq_detects_unchanged_version() {
+        let m1 = HashMap::new().inserted(1, "a");
+        let m2 = m1.inserted(1, "b");
+        let m3 = m1.inserted_or_replaced(1, "b");
+
+        assert!(m1.ptr_eq(&m2));
+        assert!(!m1.ptr_eq(&m3));
+        assert_eq!(m3.get(&1), Some(&"b"));
+        assert_eq!(m3.len(), 1);
+        assert!(m1.removed(&2).ptr_eq(&m1));
+    }
+
+    #[test]
+    fn removed_returns_smaller_map() {
+        let m1: HashMap<_, _> = (0..100).map(|x| (x, x * 2)).collect();
+        let m2 = (0..100).step_by(2).fold(m1.clone(), |m, x| m.removed(&x));
+
+        assert_eq!(m1.len(), 100);
+        assert_eq!(m2.len(), 50);
+        assert_eq!(m2.get(&3), Some(&6));
+        assert_eq!(m2.get(&4), None);
+        assert!((0..100).fold(m2, |m, x| m.removed(&x)).is_empty());
+    }
+
+    #[test]
+    fn keys_sharing_hash_prefixes_split_deep() {
+        let keys = [0u64, 32, 1 << 60, 1 << 59, 31, 1 << 5 | 1 << 60];
+        let map = keys
+            .iter()
+            .fold(HashMap::with_hasher(Identity::default()), |m, &k| {
+                m.inserted(k, k)
+            });
+
+        for k in keys {
+            assert_eq!(map.get(&k), Some(&k));
+        }
+        assert_eq!(map.get(&(1 << 58)), None);
+        let removed = keys[1..].iter().fold(map, |m, k| m.removed(k));
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed.get(&0), Some(&0));
+    }
+
+    #[test]
+    fn colliding_keys_share_a_node() {
+        let map = (0..10).fold(HashMap::with_hasher(Constant::default()), |m, x| {
+            m.inserted(x, x)
+        });
+
+        assert_eq!(map.len(), 10);
+        assert!((0..10).all(|x| map.get(&x) == Some(&x)));
+        assert_eq!(map.get(&10), None);
+        assert!(map.inserted(3, 0).ptr_eq(&map));
+        assert_eq!(map.inserted_or_replaced(3, 0).get(&3), Some(&0));
+
+        let removed = (1..10).fold(map.clone(), |m, x| m.removed(&x));
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed.iter().collect::<Vec<_>>(), [(&0, &0)]);
+        assert!(removed.removed(&0).is_empty());
+    }
+
+    /// A key that hashes only its upper bits, so keys that differ in the
+    /// low four collide.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct Coarse(u64);
+
+    impl core::hash::Hash for Coarse {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            state.write_u64(self.0 >> 4);
+        }
+    }
+
+    #[test]
+    fn collision_node_splits_on_other_hash() {
+        let map = HashMap::with_hasher(Identity::default())
+            .inserted(Coarse(0x10), 'a')
+            .inserted(Coarse(0x11), 'b')
+            .inserted(Coarse(0x30), 'c')
+            .inserted(Coarse(0x12), 'd');
+
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.get(&Coarse(0x11)), Some(&'b'));
+        assert_eq!(map.get(&Coarse(0x30)), Some(&'c'));
+        assert_eq!(map.get(&Coarse(0x13)), None);
+
+        let removed = map.removed(&Coarse(0x10)).removed(&Coarse(0x12));
+        assert_eq!(removed.len(), 2);
+        assert_eq!(removed.get(&Coarse(0x11)), Some(&'b'));
+        assert!(removed.removed(&Coarse(0x13)).ptr_eq(&removed));
+    }
+
+    #[test]
+    fn matches_btree_map_model() {
+        let mut seed = 17u32;
+        let mut next = |bound: u64| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            u64::from(seed >> 8) % bound
+        };
+        let mut map = HashMap::with_hasher(Identity::default());
+        let mut model = BTreeMap::new();
+        for _ in 0..5000 {
+            // Few distinct low bits and a few high ones, so keys share
+            // long prefixes and deep nodes get merged and split.
+            let key = next(64) | next(4) << 58;
+            let value = next(1000);
+            if next(3) == 0 {
+                map = map.removed(&key);
+                model.remove(&key);
+            } else {
+                map = map.inserted_or_replaced(key, value);
+                model.insert(key, value);
+            }
+            assert_eq!(map.len(), model.len());
+        }
+        let mut entries: Vec<_> = map.iter().map(|(&k, &v)| (k, v)).collect();
+        entries.sort_unstable();
+        assert_eq!(entries, model.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn iter_visits_every_entry_once() {
+        let map: HashMap<_, _> = (0..1000).map(|x| (x, ())).collect();
+        let mut keys: Vec<_> = map.keys().copied().collect();
+        keys.sort_unstable();
+
+        assert_eq!(map.iter().len(), 1000);
+        assert!(keys.into_iter().eq(0..1000));
+        assert_eq!((&map).into_iter().count(), 1000);
+    }
+
+    #[test]
+    fn equality_ignores_insertion_order() {
+        let m1 = HashMap::new().inserted(1, 'a').inserted(2, 'b');
+        let m2 = HashMap::new().inserted(2, 'b').inserted(1, 'a');
+
+        assert_eq!(m1, m2);
+        assert_ne!(m1, m2.inserted_or_replaced(1, 'c'));
+        assert_ne!(m1, m2.removed(&1));
+    }
+
+    #[test]
+    fn debug_formats_as_map() {
+        let map = HashMap::new().inserted(1, "a");
+        assert_eq!(format!("{:?}", map), r#"{1: "a"}"#);
+    }
+
+    #[test]
+    fn default_hasher_spreads_small_keys() {
+        let map: HashMap<u32, ()> = (0..32).map(|x| (x, ())).collect();
+        let slots: Vec<_> = (0..32u32)
+            .map(|x| {
+                let mut hasher = DefaultHasher::default();
+                hasher.write_u32(x);
+                hasher.finish() & 31
+            })
+            .collect();
+
+        assert_eq!(map.len(), 32);
+        assert!(slots.iter().filter(|&&s| s == slots[0]).count() < 8);
+    }
+}
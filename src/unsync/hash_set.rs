@@ -0,0 +1,46 @@
+use crate::shared::{self, hash_map::DefaultHashBuilder};
+use crate::unsync::hash_map::UnsyncHashLink;
+
+pub type HashSet<T, S = DefaultHashBuilder> = shared::hash_set::HashSet<UnsyncHashLink<T, ()>, S>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn inserted_returns_larger_set() {
+        let s1 = HashSet::new().inserted("a");
+        let s2 = s1.inserted("b");
+
+        assert_eq!(s1.len(), 1);
+        assert_eq!(s2.len(), 2);
+        assert!(s2.contains("b"));
+        assert!(!s1.contains("b"));
+        assert_eq!(s2.get("a"), Some(&"a"));
+        assert!(s2.inserted("a").ptr_eq(&s2));
+    }
+
+    #[test]
+    fn removed_returns_smaller_set() {
+        let s1: HashSet<_> = (0..64).collect();
+        let s2 = (0..64)
+            .filter(|x| x % 3 != 0)
+            .fold(s1.clone(), |s, x| s.removed(&x));
+
+        assert_eq!(s2.len(), 22);
+        assert!(s2.iter().all(|x| x % 3 == 0));
+        assert_eq!(s2.iter().len(), 22);
+        assert!(s2.removed(&1).ptr_eq(&s2));
+    }
+
+    #[test]
+    fn equality_and_debug() {
+        let s1: HashSet<_> = [1, 2, 3].into_iter().collect();
+        let s2 = HashSet::new().inserted(3).inserted(1).inserted(2);
+
+        assert_eq!(s1, s2);
+        assert_ne!(s1, s2.removed(&2));
+        assert_eq!(format!("{:?}", HashSet::new().inserted(1)), "{1}");
+    }
+}
@@ -0,0 +1,173 @@
+use alloc::rc::Rc;
+
+use crate::shared::{
+    self,
+    int_map::{IntLink, IntNode},
+};
+
+pub type IntMap<V> = shared::int_map::IntMap<UnsyncIntLink<V>>;
+
+pub(crate) type UnsyncIntLink<V> = Rc<Node<V>>;
+
+pub struct Node<V>(IntNode<UnsyncIntLink<V>>);
+
+impl<V> IntLink for Rc<Node<V>> {
+    type Value = V;
+
+    fn new(node: IntNode<Self>) -> Self {
+        Rc::new(Node(node))
+    }
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+    fn node(&self) -> &IntNode<Self> {
+        &self.0
+    }
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{collections::BTreeMap, format, vec::Vec};
+
+    #[test]
+    fn inserted_returns_larger_map() {
+        let m1 = IntMap::new().inserted(5, "a");
+        let m2 = m1.inserted(7, "b");
+
+        assert_eq!(m1.len(), 1);
+        assert_eq!(m2.len(), 2);
+        assert_eq!(m2.get(&7), Some(&"b"));
+        assert_eq!(m1.get(&7), None);
+        assert_eq!(m2.get_or_default(&9, &"z"), &"z");
+        assert_eq!(m2.inserted_or_replaced(5, "c").get(&5), Some(&"c"));
+        assert_eq!(m2.get(&5), Some(&"a"));
+    }
+
+    #[test]
+    fn extreme_keys() {
+        let map: IntMap<_> = [(u64::MAX, 'c'), (0, 'a'), (1 << 63, 'b')]
+            .into_iter()
+            .collect();
+
+        assert!(map.keys().copied().eq([0, 1 << 63, u64::MAX]));
+        assert!(map.contains_key(&u64::MAX));
+        assert!(!map.contains_key(&(u64::MAX - 1)));
+        assert!(map.removed(&(1 << 63)).keys().copied().eq([0, u64::MAX]));
+    }
+
+    #[test]
+    fn removed_returns_smaller_map() {
+        let m1: IntMap<_> = (0..100).map(|x| (x, x)).collect();
+        let m2 = (0..100)
+            .filter(|x| x % 3 != 0)
+            .fold(m1.clone(), |m, x| m.removed(&x));
+
+        assert_eq!(m1.len(), 100);
+        assert_eq!(m2.len(), 34);
+        assert!(m2.keys().copied().eq((0..100).step_by(3)));
+        assert!(m2.removed(&1).ptr_eq(&m2));
+        assert!((0..100).fold(m1, |m, x| m.removed(&x)).is_empty());
+    }
+
+    #[test]
+    fn shape_does_not_depend_on_insertion_order() {
+        let keys: Vec<u64> = (0..200).map(|x| x * 7919 % 1000).collect();
+        let forward: IntMap<_> = keys.iter().map(|&k| (k, ())).collect();
+        let backward: IntMap<_> = keys.iter().rev().map(|&k| (k, ())).collect();
+
+        assert_eq!(forward, backward);
+        assert_eq!(format!("{:?}", forward), format!("{:?}", backward));
+    }
+
+    #[test]
+    fn union_prefers_left_values() {
+        let m1: IntMap<_> = (0..10).map(|x| (x, "left")).collect();
+        let m2: IntMap<_> = (5..15).map(|x| (x, "right")).collect();
+        let union = m1.union(&m2);
+
+        assert_eq!(union.len(), 15);
+        assert_eq!(union.get(&7), Some(&"left"));
+        assert_eq!(union.get(&12), Some(&"right"));
+        assert!(m1.union(&m1).ptr_eq(&m1));
+        assert!(m1.union(&IntMap::new()).ptr_eq(&m1));
+    }
+
+    #[test]
+    fn intersection_keeps_left_values() {
+        let m1: IntMap<_> = (0..10).map(|x| (x, "left")).collect();
+        let m2: IntMap<_> = (5..15).map(|x| (x, "right")).collect();
+        let intersection = m1.intersection(&m2);
+
+        assert!(intersection.keys().copied().eq(5..10));
+        assert!(intersection.values().all(|v| *v == "left"));
+        assert!(m1.intersection(&IntMap::new()).is_empty());
+        assert!(m1.intersection(&m1).ptr_eq(&m1));
+    }
+
+    #[test]
+    fn union_shares_disjoint_halves() {
+        let low: IntMap<_> = (0..64).map(|x| (x, x)).collect();
+        let high: IntMap<_> = (1 << 40..(1 << 40) + 64).map(|x| (x, x)).collect();
+        let union = low.union(&high);
+
+        assert_eq!(union.len(), 128);
+        assert!(union.intersection(&low).keys().eq(low.keys()));
+        assert!(union.removed(&3).intersection(&high).keys().eq(high.keys()));
+    }
+
+    #[test]
+    fn matches_btree_map() {
+        let mut seed: u64 = 7;
+        let mut next = || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            // Spread the keys over all 64 bits, with many shared prefixes.
+            ((seed >> 16) % 512).wrapping_mul(0x0081_0204_0810_2041)
+        };
+        let mut maps = Vec::new();
+        for round in 0..20 {
+            let mut map = IntMap::new();
+            let mut model = BTreeMap::new();
+            for _ in 0..200 {
+                let key = next();
+                if key % 5 == 0 {
+                    map = map.removed(&key);
+                    model.remove(&key);
+                } else {
+                    map = map.inserted_or_replaced(key, round);
+                    model.insert(key, round);
+                }
+            }
+            assert_eq!(map.len(), model.len());
+            assert!(map.iter().eq(model.iter()));
+            maps.push((map, model));
+        }
+        for pair in maps.windows(2) {
+            let ((a, model_a), (b, model_b)) = (&pair[0], &pair[1]);
+            let mut union = model_b.clone();
+            union.extend(model_a.iter());
+            let intersection: BTreeMap<_, _> = model_a
+                .iter()
+                .filter(|(k, _)| model_b.contains_key(k))
+                .collect();
+
+            assert!(a.union(b).iter().eq(union.iter()));
+            assert_eq!(a.union(b).len(), union.len());
+            assert!(a.intersection(b).iter().eq(intersection.into_iter()));
+            assert_eq!(
+                a.intersection(b).len(),
+                a.iter().filter(|(k, _)| b.contains_key(k)).count()
+            );
+        }
+    }
+
+    #[test]
+    fn debug_lists_entries_in_order() {
+        let map = IntMap::new().inserted(2, 'b').inserted(1, 'a');
+
+        assert_eq!(format!("{:?}", map), "{1: 'a', 2: 'b'}");
+    }
+}
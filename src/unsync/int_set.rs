@@ -0,0 +1,43 @@
+use crate::shared;
+use crate::unsync::int_map::UnsyncIntLink;
+
+pub type IntSet = shared::int_set::IntSet<UnsyncIntLink<()>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn inserted_and_removed() {
+        let s1 = IntSet::new().inserted(3).inserted(1);
+        let s2 = s1.inserted(2);
+
+        assert!(s2.iter().copied().eq(1..=3));
+        assert!(!s1.contains(&2));
+        assert!(s2.inserted(1).ptr_eq(&s2));
+        assert_eq!(s2.removed(&1), [2, 3].into_iter().collect());
+        assert!(s2.removed(&4).ptr_eq(&s2));
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let evens: IntSet = (0..100).step_by(2).collect();
+        let threes: IntSet = (0..100).step_by(3).collect();
+
+        assert!(evens
+            .intersection(&threes)
+            .iter()
+            .copied()
+            .eq((0..100).step_by(6)));
+        assert_eq!(evens.union(&threes).len(), 67);
+        assert!(evens.union(&evens).ptr_eq(&evens));
+    }
+
+    #[test]
+    fn debug_lists_elements_in_order() {
+        let set: IntSet = [20, 10].into_iter().collect();
+
+        assert_eq!(format!("{:?}", set), "{10, 20}");
+    }
+}
@@ -0,0 +1,105 @@
+use alloc::boxed::Box;
+use core::cell::Cell;
+
+use once_cell::unsync::OnceCell;
+
+use crate::shared::lazy::{Lazy, Reclaim, Suspend};
+
+type Thunk<T> = Box<dyn FnOnce() -> T>;
+
+/// A memoised suspension over `once_cell::unsync::OnceCell`.
+///
+/// The thunk is taken out before it runs, so if it panics the value
+/// stays unset and forcing the suspension again panics as well. Forcing
+/// a suspension from inside its own thunk panics.
+pub struct Memo<T> {
+    thunk: Cell<Option<Thunk<T>>>,
+    value: OnceCell<T>,
+}
+
+impl<T> Lazy for Memo<T> {
+    type Value = T;
+
+    fn evaluated(value: T) -> Self {
+        Memo {
+            thunk: Cell::new(None),
+            value: OnceCell::with_value(value),
+        }
+    }
+
+    fn force(&self) -> &T {
+        self.value.get_or_init(|| {
+            let thunk = self.thunk.take().expect("a suspension is forced only once");
+            thunk()
+        })
+    }
+
+    fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    fn get_mut(&mut self) -> Option<&mut T> {
+        self.value.get_mut()
+    }
+}
+
+impl<T: 'static, X: 'static> Suspend<X> for Memo<T> {
+    fn suspended(captured: X, body: fn(X) -> T) -> Self {
+        Memo {
+            thunk: Cell::new(Some(Box::new(move || body(captured)))),
+            value: OnceCell::new(),
+        }
+    }
+}
+
+/// A function with the values it is called with.
+type Call<X, T> = (X, fn(X) -> T);
+
+/// A memoised suspension like `Memo`, whose captured values keep their
+/// type until it is forced, so that they can be reclaimed.
+pub struct Delayed<X, T> {
+    captured: Cell<Option<Call<X, T>>>,
+    value: OnceCell<T>,
+}
+
+impl<X, T> Lazy for Delayed<X, T> {
+    type Value = T;
+
+    fn evaluated(value: T) -> Self {
+        Delayed {
+            captured: Cell::new(None),
+            value: OnceCell::with_value(value),
+        }
+    }
+
+    fn force(&self) -> &T {
+        self.value.get_or_init(|| {
+            let captured = self.captured.take();
+            let (captured, body) = captured.expect("a suspension is forced only once");
+            body(captured)
+        })
+    }
+
+    fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    fn get_mut(&mut self) -> Option<&mut T> {
+        self.value.get_mut()
+    }
+}
+
+impl<X, T> Suspend<X> for Delayed<X, T> {
+    fn suspended(captured: X, body: fn(X) -> T) -> Self {
+        Delayed {
+            captured: Cell::new(Some((captured, body))),
+            value: OnceCell::new(),
+        }
+    }
+}
+
+impl<X, T> Reclaim<X> for Delayed<X, T> {
+    fn reclaim(&mut self) -> Option<X> {
+        self.captured.get_mut().take().map(|(captured, _)| captured)
+    }
+}
@@ -0,0 +1,135 @@
+use crate::shared;
+use crate::unsync::binary_tree::UnsyncBinaryLink;
+
+pub use crate::shared::leftist_heap::Ranked;
+
+pub type LeftistHeap<T> = shared::leftist_heap::LeftistHeap<UnsyncBinaryLink<Ranked<T>>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{collections::BinaryHeap, format, vec::Vec};
+    use core::cmp::Reverse;
+
+    #[test]
+    fn empty_creates_heap() {
+        let heap = LeftistHeap::<i32>::new();
+
+        assert!(heap.is_empty());
+        assert_eq!(heap.len(), 0);
+        assert_eq!(heap.peek_min(), None);
+        assert_eq!(heap.iter().next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn popped_min_on_empty_heap_panics() {
+        LeftistHeap::<i32>::new().popped_min();
+    }
+
+    #[test]
+    fn popped_min_yields_sorted_elements() {
+        let mut heap: LeftistHeap<_> = [5, 3, 8, 1, 9, 2, 7, 3].into_iter().collect();
+        let mut popped = Vec::new();
+        while let Some(&min) = heap.peek_min() {
+            popped.push(min);
+            heap = heap.popped_min();
+        }
+
+        assert_eq!(popped, [1, 2, 3, 3, 5, 7, 8, 9]);
+    }
+
+    #[test]
+    fn pushed_keeps_old_versions() {
+        let h1 = LeftistHeap::new().pushed(4).pushed(2);
+        let h2 = h1.pushed(1);
+        let h3 = h1.pushed(3);
+
+        assert_eq!(h1.peek_min(), Some(&2));
+        assert!(h2.iter_sorted().copied().eq([1, 2, 4]));
+        assert!(h3.iter_sorted().copied().eq([2, 3, 4]));
+        assert_eq!(h1.len(), 2);
+    }
+
+    #[test]
+    fn merged_combines_both_heaps() {
+        let evens: LeftistHeap<_> = (0..100).step_by(2).collect();
+        let odds: LeftistHeap<_> = (1..100).step_by(2).collect();
+        let all = evens.merged(&odds);
+
+        assert_eq!(all.len(), 100);
+        assert!(all.iter_sorted().copied().eq(0..100));
+        assert_eq!(evens.len(), 50);
+        assert!(evens.merged(&LeftistHeap::new()).ptr_eq(&evens));
+        assert!(LeftistHeap::new().merged(&odds).ptr_eq(&odds));
+    }
+
+    #[test]
+    fn iter_visits_parents_first() {
+        let heap: LeftistHeap<_> = (0..50).rev().collect();
+        let elements: Vec<_> = heap.iter().copied().collect();
+
+        assert_eq!(elements.len(), 50);
+        assert_eq!(elements[0], 0);
+        let mut sorted = elements.clone();
+        sorted.sort_unstable();
+        assert!(sorted.into_iter().eq(0..50));
+    }
+
+    #[test]
+    fn matches_binary_heap_model() {
+        let mut seed = 3u32;
+        let mut next = |bound: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 8) % bound
+        };
+        let mut heap = LeftistHeap::new();
+        let mut model = BinaryHeap::new();
+        for _ in 0..5000 {
+            match next(4) {
+                0 if !model.is_empty() => {
+                    heap = heap.popped_min();
+                    model.pop();
+                }
+                1 => {
+                    let other: Vec<_> = (0..next(20)).map(|_| next(1000)).collect();
+                    heap = heap.merged(&other.iter().copied().collect());
+                    model.extend(other.into_iter().map(Reverse));
+                }
+                _ => {
+                    let x = next(1000);
+                    heap = heap.pushed(x);
+                    model.push(Reverse(x));
+                }
+            }
+            assert_eq!(heap.len(), model.len());
+            assert_eq!(heap.peek_min(), model.peek().map(|Reverse(x)| x));
+        }
+        let mut sorted: Vec<_> = model.into_iter().map(|Reverse(x)| x).collect();
+        sorted.sort_unstable();
+        assert!(heap.iter_sorted().eq(sorted.iter()));
+    }
+
+    #[test]
+    fn equality_ignores_shape() {
+        let h1: LeftistHeap<_> = [3, 1, 2].into_iter().collect();
+        let h2 = LeftistHeap::new().pushed(1).pushed(2).pushed(3);
+
+        assert_eq!(h1, h2);
+        assert_ne!(h1, h2.popped_min());
+        assert_eq!(format!("{:?}", h1), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn dropping_long_left_spine_does_not_overflow() {
+        // Pushing ever smaller elements puts each old heap left of the
+        // new root.
+        let mut heap = LeftistHeap::new();
+        for i in (0..1_000_000).rev() {
+            heap = heap.pushed(i);
+        }
+
+        assert_eq!(heap.peek_min(), Some(&0));
+        drop(heap);
+    }
+}
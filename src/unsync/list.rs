@@ -1,4 +1,4 @@
-use alloc::rc::Rc;
+use alloc::{collections::BTreeMap, rc::Rc};
 use core::fmt::Debug;
 
 use crate::shared::{self, link::Link};
@@ -6,7 +6,7 @@ use crate::shared::{self, link::Link};
 pub type List<T> = shared::list::List<UnsyncLink<T>>;
 pub use crate::shared::list::{filter, fmap, foldl, foldr, mreturn};
 
-type UnsyncLink<T> = Rc<Node<T>>;
+pub(crate) type UnsyncLink<T> = Rc<Node<T>>;
 
 #[derive(Debug)]
 pub struct Node<T> {
@@ -26,20 +26,26 @@ impl<T> Link for Rc<Node<T>> {
         Rc::new(Node { element, next })
     }
     fn clone(&self) -> Self {
-        <Self as Clone>::clone(&self)
+        <Self as Clone>::clone(self)
     }
     fn get_element(&self) -> &Self::ValueType {
         &self.element
     }
     fn next_cloned(&self) -> Option<Self> {
-        self.next.as_ref().map(|node| <Self as Clone>::clone(node))
+        self.next.clone()
     }
     fn next_ref(&self) -> Option<&Self> {
-        self.next.as_ref().map(|node| node)
+        self.next.as_ref()
     }
     fn link_ref(&self) -> &Self {
         self
     }
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
+    }
+    fn as_ptr(&self) -> *const () {
+        Rc::as_ptr(self) as *const ()
+    }
 }
 
 #[macro_export]
@@ -68,6 +74,31 @@ impl<T> Drop for Node<T> {
     }
 }
 
+impl<T: Clone> List<T> {
+    /// Rebuilds the list on `Arc`s so it can be shared between threads.
+    ///
+    /// Complexity: O(n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::{sync, unsync};
+    ///
+    /// let list = unsync::List::new().pushed_front(2).pushed_front(1);
+    /// let converted: sync::List<_> = list.to_sync();
+    ///
+    /// assert!(converted.iter().eq(list.iter()));
+    /// ```
+    pub fn to_sync(&self) -> crate::sync::List<T> {
+        self.converted(&mut BTreeMap::new(), T::clone)
+    }
+}
+
+impl<T: Clone> From<&List<T>> for crate::sync::List<T> {
+    fn from(list: &List<T>) -> Self {
+        list.to_sync()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,7 +113,7 @@ mod tests {
 
     #[test]
     fn create_cons() {
-        let list: List<i32> = List::cons(3, &List::new());
+        let list: List<i32> = List::cons(3, List::new());
 
         assert_eq!(list.front(), Some(&3));
 
@@ -199,4 +230,265 @@ mod tests {
 
         assert_eq!(list, unsynced_list!(3));
     }
+
+    mod ptr_eq {
+        use super::*;
+
+        #[test]
+        fn clones_are_ptr_eq() {
+            let l1 = unsynced_list!(1, 2);
+            let l2 = unsynced_list!(1, 2);
+
+            assert!(l1.ptr_eq(&l1.clone()));
+            assert!(!l1.ptr_eq(&l2));
+            assert!(List::<i32>::new().ptr_eq(&List::new()));
+            assert!(!l1.ptr_eq(&List::new()));
+        }
+
+        #[test]
+        fn common_suffix_finds_shared_tail() {
+            let tail = unsynced_list!(1, 2);
+            let a = tail.pushed_front(3);
+            let b = tail.pushed_front(4).pushed_front(5);
+
+            assert!(List::common_suffix(&a, &b).ptr_eq(&tail));
+            assert!(List::common_suffix(&b, &a).ptr_eq(&tail));
+            assert!(List::common_suffix(&a, &tail).ptr_eq(&tail));
+            assert!(List::common_suffix(&a, &a).ptr_eq(&a));
+        }
+
+        #[test]
+        fn common_suffix_of_equal_but_unshared_lists_is_empty() {
+            let a = unsynced_list!(1, 2, 3);
+            let b = unsynced_list!(1, 2, 3);
+
+            assert!(List::common_suffix(&a, &b).is_empty());
+            assert!(List::common_suffix(&a, &List::new()).is_empty());
+        }
+
+        #[test]
+        fn eq_on_shared_tail_with_nan() {
+            let tail = unsynced_list!(f64::NAN);
+            let a = tail.pushed_front(1.0);
+            let b = tail.pushed_front(1.0);
+
+            assert_eq!(a, b);
+            assert_ne!(unsynced_list!(f64::NAN), unsynced_list!(f64::NAN));
+        }
+    }
+
+    mod sort {
+        use super::*;
+        use alloc::vec::Vec;
+
+        #[test]
+        fn sorted_sorts_ascending() {
+            let list = unsynced_list!(3, 9, 1, 4, 1, 5, 9, 2, 6);
+            let sorted = list.sorted();
+
+            assert_eq!(sorted, unsynced_list!(9, 9, 6, 5, 4, 3, 2, 1, 1));
+            assert_eq!(list, unsynced_list!(3, 9, 1, 4, 1, 5, 9, 2, 6));
+            assert!(List::<i32>::new().sorted().is_empty());
+        }
+
+        #[test]
+        fn sorted_matches_slice_sort() {
+            let mut seed = 17u32;
+            for len in 0..60 {
+                let mut values = Vec::new();
+                let mut list = List::new();
+                for _ in 0..len {
+                    seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                    let x = seed >> 28;
+                    values.push(x);
+                    list = list.pushed_front(x);
+                }
+                values.reverse();
+                values.sort();
+
+                assert!(list.sorted().iter().copied().eq(values));
+            }
+        }
+
+        #[test]
+        fn sorted_shares_already_sorted_tail() {
+            let tail = unsynced_list!(8, 7, 6);
+            let list = tail.pushed_front(2).pushed_front(1).pushed_front(5);
+            let sorted = list.sorted();
+
+            assert!(sorted.iter().copied().eq([1, 2, 5, 6, 7, 8]));
+            assert!(List::common_suffix(&sorted, &list).ptr_eq(&tail));
+            assert!(tail.sorted().ptr_eq(&tail));
+        }
+
+        #[test]
+        fn sorted_by_key_is_stable() {
+            let list = unsynced_list!((2, 'd'), (1, 'c'), (2, 'b'), (1, 'a'));
+            let sorted = list.sorted_by_key(|x| x.0);
+
+            assert!(sorted.iter().map(|x| x.1).eq(['a', 'c', 'b', 'd']));
+        }
+
+        #[test]
+        fn sorted_by_descending() {
+            let list = unsynced_list!(1, 3, 2);
+
+            assert_eq!(list.sorted_by(|a, b| b.cmp(a)), unsynced_list!(1, 2, 3));
+        }
+
+        #[test]
+        fn merge_sorted_shares_rest_of_longer_list() {
+            let a = unsynced_list!(9, 7, 3, 1);
+            let b = unsynced_list!(4, 2);
+            let merged = List::merge_sorted(&a, &b);
+
+            assert_eq!(merged, unsynced_list!(9, 7, 4, 3, 2, 1));
+            assert!(List::common_suffix(&merged, &a).ptr_eq(&a.popped_front().popped_front()));
+            assert!(List::merge_sorted(&a, &List::new()).ptr_eq(&a));
+            assert!(List::merge_sorted(&List::new(), &b).ptr_eq(&b));
+        }
+
+        #[test]
+        fn insert_sorted_shares_tail() {
+            let list = unsynced_list!(5, 3, 1);
+            let inserted = list.insert_sorted(2);
+
+            assert_eq!(inserted, unsynced_list!(5, 3, 2, 1));
+            assert!(inserted
+                .popped_front()
+                .popped_front()
+                .ptr_eq(&list.popped_front()));
+            assert_eq!(list.insert_sorted(9), unsynced_list!(9, 5, 3, 1));
+            assert_eq!(list.insert_sorted(0), list.pushed_front(0));
+        }
+    }
+
+    mod fmt {
+        use super::*;
+        use alloc::format;
+
+        #[test]
+        fn debug_prints_elements_front_first() {
+            assert_eq!(format!("{:?}", List::<i32>::new()), "[]");
+            assert_eq!(format!("{:?}", unsynced_list!(3, 2, 1)), "[1, 2, 3]");
+            assert_eq!(format!("{:?}", unsynced_list!("a")), "[\"a\"]");
+        }
+
+        #[test]
+        fn display_prints_elements_front_first() {
+            assert_eq!(format!("{}", List::<i32>::new()), "[]");
+            assert_eq!(format!("{}", unsynced_list!("c", "b", "a")), "[a, b, c]");
+        }
+    }
+
+    mod ord {
+        use super::*;
+        use core::cmp::Ordering;
+
+        #[test]
+        fn lists_are_compared_lexicographically() {
+            let empty = List::<i32>::new();
+
+            assert_eq!(empty.cmp(&empty), Ordering::Equal);
+            assert!(empty < unsynced_list!(1));
+            assert!(unsynced_list!(1) < unsynced_list!(2));
+            assert!(unsynced_list!(9, 1) < unsynced_list!(2));
+            assert!(unsynced_list!(3, 1) > unsynced_list!(2, 1));
+            assert_eq!(
+                unsynced_list!(2, 1).cmp(&unsynced_list!(2, 1)),
+                Ordering::Equal
+            );
+        }
+
+        #[test]
+        fn partial_cmp_on_incomparable_elements() {
+            let l1 = unsynced_list!(f64::NAN);
+            let l2 = unsynced_list!(1.0);
+
+            assert_eq!(l1.partial_cmp(&l2), None);
+        }
+    }
+
+    mod hash {
+        extern crate std;
+
+        use super::*;
+        use crate::unsync::rb_map::RBMap;
+        use std::collections::HashSet;
+
+        #[test]
+        fn equal_lists_are_one_set_member() {
+            let mut set = HashSet::new();
+
+            assert!(set.insert(unsynced_list!(1, 2)));
+            assert!(!set.insert(unsynced_list!(1, 2)));
+            assert!(set.insert(unsynced_list!(2, 1)));
+            assert!(set.insert(List::new()));
+            assert_eq!(set.len(), 3);
+        }
+
+        #[test]
+        fn nested_lists_with_same_elements_differ() {
+            let mut set = HashSet::new();
+            let a: List<List<i32>> = unsynced_list!(unsynced_list!(1), List::new());
+            let b: List<List<i32>> = unsynced_list!(List::new(), unsynced_list!(1));
+
+            set.insert(a.clone());
+
+            assert!(set.contains(&a));
+            assert!(!set.contains(&b));
+        }
+
+        #[test]
+        fn list_as_rbmap_key() {
+            let map = RBMap::new()
+                .inserted(unsynced_list!(1, 2), "a")
+                .inserted(unsynced_list!(1), "b");
+
+            assert_eq!(map.get(&unsynced_list!(1, 2)), Some(&"a"));
+            assert_eq!(map.get(&unsynced_list!(1)), Some(&"b"));
+            assert_eq!(map.get(&unsynced_list!(2)), None);
+        }
+    }
+
+    mod convert {
+        use super::*;
+        use alloc::collections::BTreeMap;
+
+        #[test]
+        fn to_sync_keeps_elements_in_order() {
+            let list = List::new().pushed_front(3).pushed_front(2).pushed_front(1);
+            let converted = list.to_sync();
+
+            assert!(converted.iter().copied().eq([1, 2, 3]));
+            assert_eq!(crate::sync::List::from(&list), converted);
+        }
+
+        #[test]
+        fn to_sync_of_empty_list_is_empty() {
+            assert!(List::<i32>::new().to_sync().is_empty());
+        }
+
+        #[test]
+        fn round_trip_gives_equal_list() {
+            let list = List::new().pushed_front(2).pushed_front(1);
+
+            assert_eq!(list.to_sync().to_unsync(), list);
+        }
+
+        #[test]
+        fn converted_with_shared_memo_keeps_shared_suffix() {
+            let tail = List::new().pushed_front(2).pushed_front(1);
+            let a = tail.pushed_front(3);
+            let b = tail.pushed_front(4).pushed_front(5);
+            let mut memo = BTreeMap::new();
+
+            let a: crate::sync::List<i32> = a.converted(&mut memo, Clone::clone);
+            let b: crate::sync::List<i32> = b.converted(&mut memo, Clone::clone);
+
+            let suffix = crate::sync::List::common_suffix(&a, &b);
+            assert!(suffix.iter().copied().eq([1, 2]));
+            assert!(suffix.ptr_eq(&a.popped_front()));
+        }
+    }
 }
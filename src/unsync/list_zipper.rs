@@ -0,0 +1,125 @@
+use crate::shared;
+use crate::unsync::list::UnsyncLink;
+
+pub type ListZipper<T> = shared::list_zipper::ListZipper<UnsyncLink<T>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unsync::List;
+
+    fn list_of(n: i32) -> List<i32> {
+        let mut list = List::new();
+        for x in (0..n).rev() {
+            list = list.pushed_front(x);
+        }
+        list
+    }
+
+    #[test]
+    fn new_focuses_on_front() {
+        let zipper = ListZipper::new(&list_of(3));
+
+        assert_eq!(zipper.focus(), Some(&0));
+        assert!(zipper.is_at_start());
+        assert!(!zipper.is_at_end());
+        assert!(zipper.left().is_empty());
+    }
+
+    #[test]
+    fn zipper_of_empty_list_is_at_both_ends() {
+        let zipper = ListZipper::<i32>::new(&List::new());
+
+        assert_eq!(zipper.focus(), None);
+        assert!(zipper.is_at_start());
+        assert!(zipper.is_at_end());
+        assert!(zipper.move_left().is_none());
+        assert!(zipper.move_right().is_none());
+    }
+
+    #[test]
+    fn moving_right_then_left_returns_to_same_position() {
+        let zipper = ListZipper::new(&list_of(3));
+        let moved = zipper.move_right().unwrap().move_right().unwrap();
+
+        assert_eq!(moved.focus(), Some(&2));
+        assert!(moved.left().iter().copied().eq([1, 0]));
+        assert_eq!(moved.move_left().unwrap().focus(), Some(&1));
+        assert_eq!(moved.move_left().unwrap().move_left().unwrap(), zipper);
+    }
+
+    #[test]
+    fn moving_past_the_end_allows_inserting_at_end() {
+        let mut zipper = ListZipper::new(&list_of(2));
+        while let Some(next) = zipper.move_right() {
+            zipper = next;
+        }
+
+        assert!(zipper.is_at_end());
+        assert!(zipper.inserted(2).to_list().iter().copied().eq(0..3));
+    }
+
+    #[test]
+    fn inserted_becomes_focus() {
+        let zipper = ListZipper::new(&list_of(3)).move_right().unwrap();
+        let inserted = zipper.inserted(42);
+
+        assert_eq!(inserted.focus(), Some(&42));
+        assert!(inserted.to_list().iter().copied().eq([0, 42, 1, 2]));
+        assert!(zipper.to_list().iter().copied().eq(0..3));
+    }
+
+    #[test]
+    fn replaced_changes_only_focus() {
+        let zipper = ListZipper::new(&list_of(3)).move_right().unwrap();
+
+        assert!(zipper.replaced(7).to_list().iter().copied().eq([0, 7, 2]));
+    }
+
+    #[test]
+    fn removed_focuses_on_next_element() {
+        let zipper = ListZipper::new(&list_of(3)).move_right().unwrap();
+        let removed = zipper.removed();
+
+        assert_eq!(removed.focus(), Some(&2));
+        assert!(removed.to_list().iter().copied().eq([0, 2]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn replaced_at_end_panics() {
+        ListZipper::<i32>::new(&List::new()).replaced(1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn removed_at_end_panics() {
+        ListZipper::<i32>::new(&List::new()).removed();
+    }
+
+    #[test]
+    fn to_list_shares_untouched_suffix() {
+        let list = list_of(5);
+        let zipper = ListZipper::new(&list)
+            .move_right()
+            .unwrap()
+            .move_right()
+            .unwrap()
+            .replaced(42)
+            .move_right()
+            .unwrap();
+        let rebuilt = zipper.to_list();
+
+        assert!(rebuilt.iter().copied().eq([0, 1, 42, 3, 4]));
+        let suffix = List::common_suffix(&list, &rebuilt);
+        assert!(suffix.iter().copied().eq([3, 4]));
+        assert!(suffix.ptr_eq(&list.popped_front().popped_front().popped_front()));
+    }
+
+    #[test]
+    fn to_list_without_edits_shares_whole_list() {
+        let list = list_of(3);
+
+        assert!(ListZipper::new(&list).to_list().ptr_eq(&list));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::shared;
+use crate::unsync::finger_tree::UnsyncFingerLink;
+
+pub use crate::shared::ordered_seq::{Keyed, Last};
+
+pub type OrderedSeq<T> = shared::ordered_seq::OrderedSeq<UnsyncFingerLink<Keyed<T>>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec::Vec};
+
+    #[test]
+    fn empty_creates_seq() {
+        let seq = OrderedSeq::<i32>::new();
+
+        assert!(seq.is_empty());
+        assert_eq!(seq.len(), 0);
+        assert_eq!(seq.first(), None);
+        assert!(!seq.contains(&1));
+    }
+
+    #[test]
+    fn inserted_keeps_elements_sorted() {
+        let seq: OrderedSeq<_> = [5, 3, 9, 1, 3, 7].into_iter().collect();
+
+        assert_eq!(seq.len(), 6);
+        assert!(seq.iter().copied().eq([1, 3, 3, 5, 7, 9]));
+        assert_eq!(seq.first(), Some(&1));
+        assert_eq!(seq.last(), Some(&9));
+        assert!(seq.contains(&7));
+        assert!(!seq.contains(&4));
+        assert!(!seq.contains(&10));
+    }
+
+    #[test]
+    fn removed_drops_one_occurrence() {
+        let s1: OrderedSeq<_> = [2, 1, 2, 3].into_iter().collect();
+        let s2 = s1.removed(&2);
+
+        assert!(s2.iter().copied().eq([1, 2, 3]));
+        assert!(s2.removed(&2).iter().copied().eq([1, 3]));
+        assert_eq!(s2.removed(&5), s2);
+        assert_eq!(s1.len(), 4);
+    }
+
+    #[test]
+    fn split_divides_at_key() {
+        let seq: OrderedSeq<_> = (0..100).step_by(10).collect();
+        let (smaller, rest) = seq.split(&35);
+
+        assert!(smaller.iter().copied().eq([0, 10, 20, 30]));
+        assert!(rest.iter().copied().eq([40, 50, 60, 70, 80, 90]));
+        assert_eq!(smaller.len() + rest.len(), 10);
+        assert_eq!(seq.split(&40).1.first(), Some(&40));
+    }
+
+    #[test]
+    fn merged_interleaves_runs() {
+        let mut seed = 13u32;
+        let mut next = |bound: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 8) % bound
+        };
+        for _ in 0..20 {
+            let a: Vec<_> = (0..next(200)).map(|_| next(300)).collect();
+            let b: Vec<_> = (0..next(200)).map(|_| next(300)).collect();
+            let merged = a
+                .iter()
+                .copied()
+                .collect::<OrderedSeq<_>>()
+                .merged(&b.iter().copied().collect());
+            let mut model: Vec<_> = a.into_iter().chain(b).collect();
+            model.sort_unstable();
+
+            assert_eq!(merged.len(), model.len());
+            assert!(merged.iter().eq(model.iter()));
+        }
+    }
+
+    #[test]
+    fn equality_and_debug() {
+        let s1: OrderedSeq<_> = [3, 1, 2].into_iter().collect();
+        let s2 = OrderedSeq::new().inserted(1).inserted(2).inserted(3);
+
+        assert_eq!(s1, s2);
+        assert_ne!(s1, s2.removed(&1));
+        assert_eq!(format!("{:?}", s1), "[1, 2, 3]");
+    }
+}
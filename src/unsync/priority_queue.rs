@@ -0,0 +1,126 @@
+use crate::shared;
+use crate::unsync::finger_tree::UnsyncFingerLink;
+
+pub use crate::shared::priority_queue::{Max, Prioritized};
+
+pub type PriorityQueue<T> = shared::priority_queue::PriorityQueue<UnsyncFingerLink<Prioritized<T>>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{collections::BinaryHeap, format, vec::Vec};
+    use core::cmp::Ordering;
+
+    /// Ordered by priority only, so equal priorities can be told apart.
+    #[derive(Clone, Debug)]
+    struct Event(u32, &'static str);
+
+    impl PartialEq for Event {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Event {}
+
+    impl PartialOrd for Event {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Event {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn empty_creates_queue() {
+        let queue = PriorityQueue::<i32>::new();
+
+        assert!(queue.is_empty());
+        assert_eq!(queue.peek_max(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn popped_max_on_empty_queue_panics() {
+        PriorityQueue::<i32>::new().popped_max();
+    }
+
+    #[test]
+    fn popped_max_yields_descending_elements() {
+        let mut queue: PriorityQueue<_> = [4, 8, 1, 8, 3, 9, 2].into_iter().collect();
+        let mut popped = Vec::new();
+        while let Some(&max) = queue.peek_max() {
+            popped.push(max);
+            queue = queue.popped_max();
+        }
+
+        assert_eq!(popped, [9, 8, 8, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn equal_priorities_pop_first_in_first_out() {
+        let queue = PriorityQueue::new()
+            .pushed(Event(1, "a"))
+            .pushed(Event(2, "b"))
+            .pushed(Event(1, "c"))
+            .pushed(Event(2, "d"));
+        let mut names = Vec::new();
+        let mut rest = queue;
+        while let Some(event) = rest.peek_max() {
+            names.push(event.1);
+            rest = rest.popped_max();
+        }
+
+        assert_eq!(names, ["b", "d", "a", "c"]);
+    }
+
+    #[test]
+    fn merged_combines_both_queues() {
+        let q1: PriorityQueue<_> = (0..50).collect();
+        let q2: PriorityQueue<_> = (50..100).rev().collect();
+        let merged = q1.merged(&q2);
+
+        assert_eq!(merged.len(), 100);
+        assert_eq!(merged.peek_max(), Some(&99));
+        assert_eq!(merged.popped_max().peek_max(), Some(&98));
+        assert_eq!(q1.peek_max(), Some(&49));
+    }
+
+    #[test]
+    fn matches_binary_heap_model() {
+        let mut seed = 9u32;
+        let mut next = |bound: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 8) % bound
+        };
+        let mut queue = PriorityQueue::new();
+        let mut model = BinaryHeap::new();
+        for _ in 0..3000 {
+            match next(3) {
+                0 if !model.is_empty() => {
+                    queue = queue.popped_max();
+                    model.pop();
+                }
+                _ => {
+                    let x = next(500);
+                    queue = queue.pushed(x);
+                    model.push(x);
+                }
+            }
+            assert_eq!(queue.len(), model.len());
+            assert_eq!(queue.peek_max(), model.peek());
+        }
+    }
+
+    #[test]
+    fn iter_and_debug_keep_insertion_order() {
+        let queue: PriorityQueue<_> = [2, 3, 1].into_iter().collect();
+
+        assert!(queue.iter().copied().eq([2, 3, 1]));
+        assert_eq!(format!("{:?}", queue), "[2, 3, 1]");
+    }
+}
@@ -0,0 +1,126 @@
+use crate::shared;
+use crate::unsync::list::UnsyncLink;
+
+pub type Queue<T> = shared::queue::Queue<UnsyncLink<T>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn new_creates_empty_queue() {
+        let queue = Queue::<i32>::new();
+
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+        assert_eq!(queue.peek(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn popped_front_on_empty_queue_panics() {
+        let queue = Queue::<i32>::new();
+        queue.popped_front();
+    }
+
+    #[test]
+    fn elements_come_out_in_fifo_order() {
+        let mut queue = Queue::new();
+        for i in 0..100 {
+            queue = queue.pushed_back(i);
+        }
+
+        assert_eq!(queue.len(), 100);
+        for i in 0..100 {
+            assert_eq!(queue.peek(), Some(&i));
+            queue = queue.popped_front();
+        }
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn interleaved_pushes_and_pops() {
+        let mut queue = Queue::new();
+        let mut next_in = 0;
+        let mut next_out = 0;
+        for round in 1..50 {
+            for _ in 0..round {
+                queue = queue.pushed_back(next_in);
+                next_in += 1;
+            }
+            for _ in 0..round / 2 {
+                assert_eq!(queue.peek(), Some(&next_out));
+                queue = queue.popped_front();
+                next_out += 1;
+            }
+            assert_eq!(queue.len(), next_in - next_out);
+            assert!(queue.iter().copied().eq(next_out..next_in));
+        }
+    }
+
+    #[test]
+    fn iter_yields_fifo_order_during_rotation() {
+        let mut queue = Queue::new();
+        for i in 0..37 {
+            queue = queue.pushed_back(i);
+            assert!(queue.iter().copied().eq(0..=i));
+        }
+        for i in 1..37 {
+            queue = queue.popped_front();
+            assert!(queue.iter().copied().eq(i..37));
+        }
+    }
+
+    #[test]
+    fn old_versions_are_unchanged() {
+        let base: Queue<i32> = (0..10).collect();
+        let mut versions = Vec::new();
+        let mut queue = base.clone();
+        for i in 10..200 {
+            queue = queue.pushed_back(i).popped_front();
+            versions.push(queue.clone());
+        }
+
+        assert!(base.iter().copied().eq(0..10));
+        for (i, version) in versions.iter().enumerate() {
+            let start = i as i32 + 1;
+            assert!(version.iter().copied().eq(start..start + 10));
+        }
+
+        let branch = base.popped_front().pushed_back(-1);
+        assert!(branch.iter().copied().eq((1..10).chain([-1])));
+        assert!(base.iter().copied().eq(0..10));
+    }
+
+    #[test]
+    fn reused_version_pops_in_order() {
+        let base: Queue<i32> = (0..64).collect();
+        for _ in 0..3 {
+            let mut queue = base.clone();
+            for i in 0..64 {
+                assert_eq!(queue.peek(), Some(&i));
+                queue = queue.popped_front();
+            }
+            assert!(queue.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_partial_eq() {
+        let q1: Queue<i32> = (1..4).collect();
+        let q2 = Queue::new().pushed_back(0).pushed_back(1);
+        let q2 = q2.pushed_back(2).pushed_back(3).popped_front();
+
+        assert_eq!(q1, q2);
+        assert_ne!(q1, q2.popped_front());
+        assert_eq!(Queue::<i32>::new(), Queue::new());
+    }
+
+    #[test]
+    fn debug_prints_fifo_order() {
+        let queue: Queue<i32> = (1..4).collect();
+
+        assert_eq!(alloc::format!("{:?}", queue), "[1, 2, 3]");
+    }
+}
@@ -0,0 +1,98 @@
+use crate::shared;
+use crate::unsync::{binary_tree::UnsyncBinaryLink, list::UnsyncLink};
+
+pub use crate::shared::random_access_list::Digit;
+
+pub type RandomAccessList<T> = shared::random_access_list::RandomAccessList<
+    UnsyncLink<Digit<UnsyncBinaryLink<T>>>,
+    UnsyncBinaryLink<T>,
+>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn create_empty() {
+        let list = RandomAccessList::<i32>::new();
+
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.front(), None);
+        assert_eq!(list.get(0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn popped_front_on_empty_list_panics() {
+        RandomAccessList::<i32>::new().popped_front();
+    }
+
+    #[test]
+    fn pushed_front_and_popped_front_behave_like_list() {
+        let l1 = RandomAccessList::new();
+        let l2 = l1.pushed_front(1);
+        let l3 = l2.pushed_front(2);
+
+        assert_eq!(l3.front(), Some(&2));
+        assert_eq!(l3.popped_front(), l2);
+        assert_eq!(l3.popped_front().popped_front(), l1);
+        assert!(l1.is_empty());
+    }
+
+    #[test]
+    fn get_returns_every_element() {
+        for n in 0..70 {
+            let list: RandomAccessList<usize> = (0..n).collect();
+
+            assert_eq!(list.len(), n);
+            for i in 0..n {
+                assert_eq!(list.get(i), Some(&i));
+            }
+            assert_eq!(list.get(n), None);
+            assert!(list.iter().copied().eq(0..n));
+        }
+    }
+
+    #[test]
+    fn popped_front_keeps_order() {
+        let mut list: RandomAccessList<usize> = (0..100).collect();
+        for i in 0..100 {
+            assert_eq!(list.front(), Some(&i));
+            assert_eq!(list.get(99 - i), Some(&99));
+            list = list.popped_front();
+        }
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn updated_leaves_original_unchanged() {
+        let original: RandomAccessList<usize> = (0..50).collect();
+        let mut updated = original.clone();
+        for i in (0..50).step_by(3) {
+            updated = updated.updated(i, 100 + i);
+        }
+
+        assert!(original.iter().copied().eq(0..50));
+        for i in 0..50 {
+            let expected = if i % 3 == 0 { 100 + i } else { i };
+            assert_eq!(updated.get(i), Some(&expected));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn updated_out_of_bounds_panics() {
+        let list: RandomAccessList<usize> = (0..5).collect();
+        list.updated(5, 0);
+    }
+
+    #[test]
+    fn debug_prints_front_first() {
+        let list: RandomAccessList<i32> = (1..4).collect();
+
+        assert_eq!(alloc::format!("{:?}", list), "[1, 2, 3]");
+        assert_eq!(list.iter().collect::<Vec<_>>(), [&1, &2, &3]);
+    }
+}
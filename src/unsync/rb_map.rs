@@ -1,7 +1,7 @@
 use crate::{shared::KeyValue, unsync::rb_tree::RBTree};
 
 #[derive(Debug)]
-pub struct RBMap<K, V>(RBTree<KeyValue<K, V>>);
+pub struct RBMap<K, V>(pub(crate) RBTree<KeyValue<K, V>>);
 
 //pub struct RBMap<K, V> {
 //    root: Rc<RBNode<(K, V)>>,
@@ -20,6 +20,11 @@ where
         self.0.is_empty()
     }
 
+    /// Returns `true` if both maps share the same root node.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+
     pub fn contains_key(&self, k: &K) -> bool {
         self.0.contains(k)
     }
@@ -54,6 +59,12 @@ where
     }
 }
 
+impl<K, V> Default for RBMap<K, V> {
+    fn default() -> Self {
+        RBMap(RBTree::default())
+    }
+}
+
 impl<K, V> Clone for RBMap<K, V>
 where
     K: Clone,
@@ -64,6 +75,21 @@ where
     }
 }
 
+impl<K: Clone, V: Clone> RBMap<K, V> {
+    /// Rebuilds the map on `Arc`s so it can be shared between threads.
+    ///
+    /// Complexity: O(n)
+    pub fn to_sync(&self) -> crate::sync::RBMap<K, V> {
+        crate::sync::RBMap(self.0.to_sync())
+    }
+}
+
+impl<K: Clone, V: Clone> From<&RBMap<K, V>> for crate::sync::RBMap<K, V> {
+    fn from(map: &RBMap<K, V>) -> Self {
+        map.to_sync()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,6 +118,16 @@ mod tests {
         assert_eq!(m.get(&6), None);
     }
 
+    #[test]
+    fn ptr_eq_detects_unchanged_version() {
+        let m1 = RBMap::new().inserted(1, "a");
+        let m2 = m1.inserted(1, "b");
+        let m3 = m1.inserted_or_replaced(1, "b");
+
+        assert!(m1.ptr_eq(&m2));
+        assert!(!m1.ptr_eq(&m3));
+    }
+
     #[test]
     fn get_or_default() {
         let m1 = RBMap::new();
@@ -101,4 +137,15 @@ mod tests {
         assert_eq!(m1.get_or_default(&"g", &0), &0);
         assert_eq!(m.get_or_default(&"g", &0), &5);
     }
+
+    #[test]
+    fn to_sync_keeps_entries() {
+        let m = RBMap::new().inserted(1, "a").inserted(2, "b");
+        let converted = m.to_sync();
+
+        assert_eq!(converted.get(&1), Some(&"a"));
+        assert_eq!(converted.get(&2), Some(&"b"));
+        assert_eq!(crate::sync::RBMap::from(&m).get(&2), Some(&"b"));
+        assert_eq!(converted.to_unsync().get(&1), Some(&"a"));
+    }
 } // mod tests
@@ -1,4 +1,7 @@
-use alloc::rc::Rc;
+use alloc::{collections::BTreeMap, rc::Rc, string::String, vec, vec::Vec};
+use core::fmt::Debug;
+
+use crate::shared::draw::{self, Draw};
 
 #[derive(Debug)]
 pub struct RBTree<T> {
@@ -51,6 +54,13 @@ impl<T: Clone> RBTree<T> {
         }
     }
 
+    /// Returns `true` if both trees share the same root node, or are
+    /// both empty.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        link_ptr_eq(&self.root, &other.root)
+    }
+
+    #[cfg(test)]
     fn tree(colour: Colour, value: T, left: &Self, right: &Self) -> Self {
         RBTree {
             root: Some(Rc::new(RBNode {
@@ -62,12 +72,6 @@ impl<T: Clone> RBTree<T> {
         }
     }
 
-    fn from_node(node: &Rc<RBNode<T>>) -> Self {
-        RBTree {
-            root: Some(node.clone()),
-        }
-    }
-
     pub fn is_empty(&self) -> bool {
         self.root.is_none()
     }
@@ -76,6 +80,7 @@ impl<T: Clone> RBTree<T> {
         self.root.as_ref().map(|node| &node.element)
     }
 
+    #[cfg(test)]
     fn root_colour(&self) -> Colour {
         self.root.as_ref().map_or(Colour::Black, |node| node.colour)
     }
@@ -156,169 +161,9 @@ impl<T: Clone> RBTree<T> {
             None => default,
         }
     }
-
-    fn ins(&self, x: T) -> Self
-    where
-        T: PartialOrd,
-    {
-        match &self.root {
-            None => RBTree::leaf(x),
-            Some(node) => {
-                if x < node.element {
-                    balance(
-                        node.colour,
-                        node.element.clone(),
-                        &RBTree {
-                            root: node.left.clone(),
-                        }
-                        .ins(x),
-                        &RBTree {
-                            root: node.right.clone(),
-                        },
-                    )
-                } else if x > node.element {
-                    balance(
-                        node.colour,
-                        node.element.clone(),
-                        &RBTree {
-                            root: node.left.clone(),
-                        },
-                        &RBTree {
-                            root: node.right.clone(),
-                        }
-                        .ins(x),
-                    )
-                } else {
-                    RBTree {
-                        root: self.root.clone(),
-                    }
-                }
-            }
-        }
-    }
-
-    fn ins_or_rep(&self, x: T) -> Self
-    where
-        T: PartialOrd,
-    {
-        match &self.root {
-            None => RBTree::leaf(x),
-            Some(node) => {
-                if x < node.element {
-                    balance(
-                        node.colour,
-                        node.element.clone(),
-                        &RBTree {
-                            root: node.left.clone(),
-                        }
-                        .ins_or_rep(x),
-                        &RBTree {
-                            root: node.right.clone(),
-                        },
-                    )
-                } else if x > node.element {
-                    balance(
-                        node.colour,
-                        node.element.clone(),
-                        &RBTree {
-                            root: node.left.clone(),
-                        },
-                        &RBTree {
-                            root: node.right.clone(),
-                        }
-                        .ins_or_rep(x),
-                    )
-                } else {
-                    RBTree {
-                        root: Some(Rc::new(RBNode {
-                            colour: node.colour,
-                            element: x,
-                            left: node.left.clone(),
-                            right: node.right.clone(),
-                        })),
-                    }
-                }
-            }
-        }
-    }
-
-    fn doubled_left(&self) -> bool {
-        !self.is_empty()
-            && self.root_colour() == Colour::Red
-            && !self.left().is_empty()
-            && self.left().root_colour() == Colour::Red
-    }
-
-    fn doubled_right(&self) -> bool {
-        !self.is_empty()
-            && self.root_colour() == Colour::Red
-            && !self.right().is_empty()
-            && self.right().root_colour() == Colour::Red
-    }
-
-    fn paint(&self, c: Colour) -> Self {
-        assert!(!self.is_empty());
-        RBTree::tree(c, self.root().unwrap().clone(), &self.left(), &self.right())
-    }
-}
-
-fn balance<T>(c: Colour, x: T, left: &RBTree<T>, right: &RBTree<T>) -> RBTree<T>
-where
-    T: Clone,
-{
-    use Colour::*;
-    if c == Black && left.doubled_left() {
-        RBTree::tree(
-            Red,
-            left.root().unwrap().clone(),
-            &left.left().paint(Black),
-            &RBTree::tree(Black, x, &left.right(), right),
-        )
-    } else if c == Black && left.doubled_right() {
-        RBTree::tree(
-            Red,
-            left.right().root().unwrap().clone(),
-            &RBTree::tree(
-                Black,
-                left.root().unwrap().clone(),
-                &left.left(),
-                &left.right().left(),
-            ),
-            &RBTree::tree(Black, x, &left.right().right(), right),
-        )
-    } else if c == Black && right.doubled_left() {
-        RBTree::tree(
-            Red,
-            right.left().root().unwrap().clone(),
-            &RBTree::tree(Black, x, left, &right.left().left()),
-            &RBTree::tree(
-                Black,
-                right.root().unwrap().clone(),
-                &right.left().right(),
-                &right.right(),
-            ),
-        )
-    } else if c == Black && right.doubled_right() {
-        RBTree::tree(
-            Red,
-            right.root().unwrap().clone(),
-            &RBTree::tree(Black, x, left, &right.left()),
-            &right.right().paint(Black),
-        )
-    } else {
-        RBTree::tree(c, x, left, right)
-    }
 }
 
 impl<T> RBNode<T> {
-    fn left(&self) -> Option<&Rc<Self>> {
-        self.left.as_ref()
-    }
-
-    fn right(&self) -> Option<&Rc<Self>> {
-        self.right.as_ref()
-    }
-
     fn contains<U>(&self, x: &U) -> bool
     where
         T: PartialOrd<U>,
@@ -344,21 +189,6 @@ impl<T> RBNode<T> {
             Some(&self.element)
         }
     }
-    fn doubled_left(&self) -> bool {
-        self.colour == Colour::Red
-            && self
-                .left
-                .as_ref()
-                .map_or(false, |node| node.colour == Colour::Red)
-    }
-
-    fn doubled_right(&self) -> bool {
-        self.colour == Colour::Red
-            && self
-                .right
-                .as_ref()
-                .map_or(false, |node| node.colour == Colour::Red)
-    }
 }
 fn link_inserted<T>(link: &Link<T>, x: T) -> Link<T>
 where
@@ -384,19 +214,18 @@ where
         None => make_leaf_link(x),
         Some(node) => {
             if x < node.element {
-                balance_link(
-                    node.colour,
-                    node.element.clone(),
-                    sorted_insert(&node.left.clone(), x),
-                    node.right.clone(),
-                )
+                let left = sorted_insert(&node.left, x);
+                if link_ptr_eq(&left, &node.left) {
+                    // x was already present, keep the old version
+                    return link.clone();
+                }
+                balance_link(node.colour, node.element.clone(), left, node.right.clone())
             } else if x > node.element {
-                balance_link(
-                    node.colour,
-                    node.element.clone(),
-                    node.left.clone(),
-                    sorted_insert(&node.right.clone(), x),
-                )
+                let right = sorted_insert(&node.right, x);
+                if link_ptr_eq(&right, &node.right) {
+                    return link.clone();
+                }
+                balance_link(node.colour, node.element.clone(), node.left.clone(), right)
             } else {
                 link.clone()
             }
@@ -539,6 +368,9 @@ where
     T: Clone,
 {
     link.as_ref().map(|node| {
+        if node.colour == colour {
+            return node.clone();
+        }
         Rc::new(RBNode {
             colour,
             element: node.element.clone(),
@@ -548,6 +380,125 @@ where
     })
 }
 
+fn link_ptr_eq<T>(a: &Link<T>, b: &Link<T>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+        _ => false,
+    }
+}
+
+impl<T> RBTree<T> {
+    /// Builds a tree from a root and two subtrees without rebalancing.
+    /// Used when converting a tree from the other flavour.
+    pub(crate) fn from_parts(red: bool, element: T, left: Self, right: Self) -> Self {
+        let colour = if red { Colour::Red } else { Colour::Black };
+        RBTree {
+            root: make_link(colour, element, left.root, right.root),
+        }
+    }
+}
+
+impl<T: Clone> RBTree<T> {
+    /// Rebuilds the tree on `Arc`s so it can be shared between threads.
+    ///
+    /// Colours are kept and nodes shared within the tree stay shared.
+    ///
+    /// Complexity: O(n)
+    pub fn to_sync(&self) -> crate::sync::RBTree<T> {
+        link_converted(&self.root, &mut BTreeMap::new())
+    }
+}
+
+impl<T: Clone> From<&RBTree<T>> for crate::sync::RBTree<T> {
+    fn from(tree: &RBTree<T>) -> Self {
+        tree.to_sync()
+    }
+}
+
+fn link_converted<T: Clone>(
+    link: &Link<T>,
+    memo: &mut BTreeMap<*const RBNode<T>, crate::sync::RBTree<T>>,
+) -> crate::sync::RBTree<T> {
+    let node = match link {
+        None => return crate::sync::RBTree::new(),
+        Some(node) => node,
+    };
+    if let Some(copy) = memo.get(&Rc::as_ptr(node)) {
+        return copy.clone();
+    }
+    let left = link_converted(&node.left, memo);
+    let right = link_converted(&node.right, memo);
+    let copy = crate::sync::RBTree::from_parts(
+        node.colour == Colour::Red,
+        node.element.clone(),
+        left,
+        right,
+    );
+    memo.insert(Rc::as_ptr(node), copy.clone());
+    copy
+}
+
+impl<T: Debug> RBTree<T> {
+    /// Draws the tree as an outline, one element per line, marked `[R]`
+    /// or `[B]` for its colour. The left subtree comes above the right
+    /// one, and an empty subtree is shown as `·` when its sibling is not
+    /// empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::rb_tree::RBTree;
+    ///
+    /// let tree = RBTree::new().inserted(1).inserted(2);
+    ///
+    /// assert_eq!(tree.pretty(), "1 [B]\n├── ·\n└── 2 [R]");
+    /// ```
+    pub fn pretty(&self) -> String {
+        draw::pretty(self.root.as_deref())
+    }
+
+    /// Draws `versions` as one Graphviz digraph, with a `v<i>` label
+    /// pointing at the root of each. Nodes shared between versions are
+    /// drawn once, filled with their colour.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::rb_tree::RBTree;
+    ///
+    /// let t1 = RBTree::new().inserted(2).inserted(1);
+    /// let t2 = t1.inserted(3);
+    /// let dot = RBTree::to_dot(&[&t1, &t2]);
+    ///
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert!(dot.contains("fillcolor=red"));
+    /// ```
+    pub fn to_dot(versions: &[&Self]) -> String {
+        draw::dot(versions.iter().map(|tree| tree.root.as_deref()).collect())
+    }
+}
+
+impl<T: Debug> Draw for &RBNode<T> {
+    type Element = T;
+
+    const BINARY: bool = true;
+
+    fn id(&self) -> *const () {
+        *self as *const RBNode<T> as *const ()
+    }
+    fn element(&self) -> &T {
+        &self.element
+    }
+    fn red(&self) -> Option<bool> {
+        Some(self.colour == Colour::Red)
+    }
+    fn children(&self) -> Vec<Option<Self>> {
+        match (self.left.as_deref(), self.right.as_deref()) {
+            (None, None) => Vec::new(),
+            (left, right) => vec![left, right],
+        }
+    }
+}
+
 // impl<T: PartialEq + Clone> PartialEq for RBTree<T> {
 //     fn eq(&self, other: &Self) -> bool {
 //         self.root == other.root
@@ -679,6 +630,41 @@ mod tests {
         assert_eq!(t4.root(), Some(&KV(4, "d")));
     }
 
+    #[test]
+    fn pretty_marks_colours() {
+        let tree = RBTree::new()
+            .inserted(2)
+            .inserted(1)
+            .inserted(3)
+            .inserted(4);
+        let expected = "3 [B]\n├── 2 [B]\n│   ├── 1 [R]\n│   └── ·\n└── 4 [B]";
+        assert_eq!(tree.pretty(), expected);
+    }
+
+    #[test]
+    fn to_dot_draws_shared_nodes_once() {
+        let t1 = RBTree::new().inserted(2).inserted(1).inserted(3);
+        let t2 = t1.inserted(4);
+        let dot = RBTree::to_dot(&[&t1, &t2, &RBTree::new()]);
+
+        assert_eq!(dot.matches("label=\"1\"").count(), 1);
+        assert_eq!(dot.matches("label=\"3\"").count(), 2);
+        assert_eq!(dot.matches("fillcolor=red").count(), 2);
+        assert!(dot.contains("v2 [shape=plaintext];"));
+        assert!(dot.contains("[tailport=se]"));
+    }
+
+    #[test]
+    fn ptr_eq_detects_shared_root() {
+        let t1 = RBTree::new().inserted(1).inserted(2);
+        let t2 = RBTree::new().inserted(1).inserted(2);
+
+        assert!(t1.ptr_eq(&t1.clone()));
+        assert!(!t1.ptr_eq(&t2));
+        assert!(t1.inserted(2).ptr_eq(&t1));
+        assert!(RBTree::<i32>::new().ptr_eq(&RBTree::new()));
+    }
+
     #[test]
     fn get_or_default() {
         let t1 = RBTree::new();
@@ -0,0 +1,110 @@
+use crate::shared;
+use crate::unsync::finger_tree::UnsyncFingerLink;
+
+pub use crate::shared::seq::{Counted, Size};
+
+pub type Seq<T> = shared::seq::Seq<UnsyncFingerLink<Counted<T>>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec::Vec};
+
+    #[test]
+    fn empty_creates_seq() {
+        let seq = Seq::<i32>::new();
+
+        assert!(seq.is_empty());
+        assert_eq!(seq.len(), 0);
+        assert_eq!(seq.get(0), None);
+        assert_eq!(seq.front(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn popped_back_on_empty_seq_panics() {
+        Seq::<i32>::new().popped_back();
+    }
+
+    #[test]
+    fn get_returns_every_element() {
+        for n in [1, 4, 9, 100, 2000] {
+            let seq: Seq<_> = (0..n).collect();
+
+            assert_eq!(seq.len(), n);
+            assert!((0..n).all(|i| seq.get(i) == Some(&i)));
+            assert_eq!(seq.get(n), None);
+        }
+    }
+
+    #[test]
+    fn pushes_and_pops_at_both_ends() {
+        let seq = Seq::new().pushed_back(2).pushed_front(1).pushed_back(3);
+
+        assert!(seq.iter().copied().eq([1, 2, 3]));
+        assert!(seq.popped_front().iter().copied().eq([2, 3]));
+        assert!(seq.popped_back().iter().copied().eq([1, 2]));
+        assert_eq!(seq.len(), 3);
+    }
+
+    #[test]
+    fn updated_replaces_one_element() {
+        let s1: Seq<_> = (0..100).collect();
+        let s2 = s1.updated(0, -1).updated(57, -2).updated(99, -3);
+
+        assert_eq!(s2.len(), 100);
+        assert_eq!(s2.get(0), Some(&-1));
+        assert_eq!(s2.get(57), Some(&-2));
+        assert_eq!(s2.back(), Some(&-3));
+        assert_eq!(s2.get(58), Some(&58));
+        assert_eq!(s1.get(57), Some(&57));
+    }
+
+    #[test]
+    fn matches_vec_model() {
+        let mut seed = 5u32;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 8) as usize % bound
+        };
+        let mut seq = Seq::new();
+        let mut model = Vec::new();
+        for step in 0..3000 {
+            match next(5) {
+                0 => {
+                    seq = seq.pushed_front(step);
+                    model.insert(0, step);
+                }
+                1 => {
+                    seq = seq.pushed_back(step);
+                    model.push(step);
+                }
+                2 if !model.is_empty() => {
+                    let index = next(model.len());
+                    seq = seq.updated(index, step);
+                    model[index] = step;
+                }
+                _ => {
+                    let index = next(model.len() + 1);
+                    let (front, back) = seq.split_at(index);
+                    assert_eq!(front.len(), index);
+                    seq = back.appended(&front);
+                    model.rotate_left(index);
+                }
+            }
+            assert_eq!(seq.len(), model.len());
+        }
+        assert!(seq.iter().eq(model.iter()));
+        assert!((0..model.len()).all(|i| seq.get(i) == Some(&model[i])));
+    }
+
+    #[test]
+    fn equality_and_debug() {
+        let s1: Seq<_> = (0..3).collect();
+        let s2 = Seq::new().pushed_front(2).pushed_front(1).pushed_front(0);
+
+        assert_eq!(s1, s2);
+        assert_ne!(s1, s2.popped_front());
+        assert_eq!(format!("{:?}", s1), "[0, 1, 2]");
+    }
+}
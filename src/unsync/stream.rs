@@ -0,0 +1,156 @@
+use alloc::rc::Rc;
+
+use crate::shared::{
+    self,
+    stream::{Rebind, StreamLink, StreamNode},
+};
+use crate::unsync::{lazy::Memo, list::UnsyncLink};
+
+pub type Stream<T> = shared::stream::Stream<UnsyncStreamLink<T>>;
+
+pub(crate) type UnsyncStreamLink<T> = Rc<Node<T>>;
+
+pub struct Node<T>(StreamNode<UnsyncStreamLink<T>>);
+
+impl<T> StreamLink for Rc<Node<T>> {
+    type Element = T;
+    type Tail = Memo<Stream<T>>;
+    type ListLink = UnsyncLink<T>;
+
+    fn new(node: StreamNode<Self>) -> Self {
+        Rc::new(Node(node))
+    }
+
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+
+    fn node(&self) -> &StreamNode<Self> {
+        &self.0
+    }
+
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
+    }
+
+    fn try_unwrap(self) -> Result<StreamNode<Self>, Self> {
+        Rc::try_unwrap(self).map(|node| node.0)
+    }
+}
+
+impl<T, U> Rebind<U> for Rc<Node<T>> {
+    type Link = Rc<Node<U>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unsync::List;
+    use crate::unsynced_list;
+    use alloc::format;
+    use core::cell::Cell;
+
+    #[test]
+    fn create_empty() {
+        let stream = Stream::<i32>::new();
+
+        assert!(stream.is_empty());
+        assert_eq!(stream.front(), None);
+        assert_eq!(stream.iter().next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn popped_front_on_empty_stream_panics() {
+        Stream::<i32>::new().popped_front();
+    }
+
+    #[test]
+    fn cons_creates_evaluated_stream() {
+        let stream = Stream::cons(1, &Stream::cons(2, &Stream::new()));
+
+        assert_eq!(stream.front(), Some(&1));
+        assert_eq!(stream.popped_front().front(), Some(&2));
+        assert_eq!(format!("{:?}", stream), "[1, 2]");
+    }
+
+    #[test]
+    fn tail_is_evaluated_at_most_once() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let stream = Stream::cons_lazy(1, move || {
+            counter.set(counter.get() + 1);
+            Stream::cons(2, &Stream::new())
+        });
+        let shared = stream.clone();
+
+        assert_eq!(calls.get(), 0);
+        assert_eq!(format!("{:?}", stream), "[1, ..]");
+        assert_eq!(stream.popped_front().front(), Some(&2));
+        assert_eq!(shared.popped_front().front(), Some(&2));
+        assert_eq!(shared.iter().count(), 2);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(format!("{:?}", shared), "[1, 2]");
+    }
+
+    #[test]
+    fn iterate_take_and_to_list() {
+        let naturals = Stream::iterate(0, |x| x + 1);
+        let first = naturals.take(5);
+
+        assert_eq!(first.to_list(), unsynced_list!(4, 3, 2, 1, 0));
+        assert_eq!(List::from(&naturals.take(0)), List::new());
+    }
+
+    #[test]
+    fn map_and_filter_are_lazy() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let squares = Stream::iterate(0, |x| x + 1).map(move |x| {
+            counter.set(counter.get() + 1);
+            x * x
+        });
+        let even_squares = squares.filter(|x| x % 2 == 0);
+
+        assert_eq!(calls.get(), 1);
+        assert!(even_squares.take(3).iter().copied().eq([0, 4, 16]));
+        assert_eq!(calls.get(), 5);
+    }
+
+    #[test]
+    fn filter_without_matches_is_empty() {
+        let stream = Stream::from_fn({
+            let mut n = 0;
+            move || {
+                n += 1;
+                if n <= 10 {
+                    Some(n)
+                } else {
+                    None
+                }
+            }
+        });
+
+        assert!(stream.filter(|x| *x > 10).is_empty());
+        assert_eq!(stream.iter().count(), 10);
+    }
+
+    #[test]
+    fn zip_stops_at_shorter_stream() {
+        let letters = Stream::cons('a', &Stream::cons('b', &Stream::new()));
+        let numbers = Stream::iterate(1, |x| x + 1);
+
+        assert!(letters
+            .zip(&numbers)
+            .iter()
+            .cloned()
+            .eq([('a', 1), ('b', 2)]));
+    }
+
+    #[test]
+    fn dropping_long_evaluated_stream_does_not_overflow() {
+        let stream = Stream::iterate(0, |x| x + 1);
+        assert_eq!(stream.iter().nth(200_000), Some(&200_000));
+        drop(stream);
+    }
+}
@@ -1,104 +1,153 @@
-use crate::unsync::list::List;
 use alloc::rc::Rc;
+use core::mem;
 
-#[derive(Debug)]
-pub struct Tree<T> {
-    root: Option<Rc<TreeNode<T>>>,
-}
+use crate::shared::{
+    self,
+    tree::{drop_children, list_from, ConversionMemo, Crumb, TreeLink},
+};
+use crate::unsync::list::{List, UnsyncLink};
 
-impl<T> Default for Tree<T> {
-    fn default() -> Self {
-        Self { root: None }
-    }
+pub use crate::shared::selector::Selector;
+
+pub type Tree<T> = shared::tree::Tree<UnsyncTreeLink<T>>;
+pub type TreeEdit<T> = shared::tree::TreeEdit<UnsyncTreeLink<T>>;
+pub type TreeZipper<T> =
+    shared::tree::TreeZipper<UnsyncTreeLink<T>, UnsyncLink<Crumb<UnsyncTreeLink<T>>>>;
+
+pub(crate) type UnsyncTreeLink<T> = Rc<Node<T>>;
+
+pub struct Node<T> {
+    element: T,
+    children: List<Tree<T>>,
 }
 
-impl<T> Tree<T> {
-    pub fn empty() -> Self {
-        Tree::<T>::default()
-    }
+impl<T> TreeLink for Rc<Node<T>> {
+    type ValueType = T;
+    type ListLink = UnsyncLink<Tree<T>>;
 
-    pub fn leaf(x: T) -> Self {
-        Tree {
-            root: Some(Rc::new(TreeNode::leaf(x))),
-        }
+    fn node(element: Self::ValueType, children: List<Tree<T>>) -> Self {
+        Rc::new(Node { element, children })
     }
-
-    pub fn new(x: T, children: List<Self>) -> Self {
-        Tree {
-            root: Some(Rc::new(TreeNode::new(x, children))),
-        }
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
     }
-
-    pub fn is_empty(&self) -> bool {
-        self.root.is_none()
+    fn get_element(&self) -> &Self::ValueType {
+        &self.element
     }
-
-    pub fn root(&self) -> Option<&T> {
-        self.root.as_ref().map(|node| &node.element)
+    fn children(&self) -> &List<Tree<T>> {
+        &self.children
     }
-
-    pub fn children(&self) -> Option<&List<Tree<T>>> {
-        self.root.as_ref().map(|node| &node.children)
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
     }
-}
-
-impl<T> Clone for Tree<T> {
-    fn clone(&self) -> Self {
-        Tree {
-            root: self.root.clone(),
-        }
+    fn as_ptr(&self) -> *const () {
+        Rc::as_ptr(self) as *const ()
+    }
+    fn try_unwrap_children(self) -> Option<List<Tree<T>>> {
+        Rc::try_unwrap(self)
+            .ok()
+            .map(|mut node| mem::take(&mut node.children))
     }
 }
 
-impl<T> PartialEq for Tree<T>
-where
-    T: PartialEq,
-{
-    fn eq(&self, other: &Self) -> bool {
-        match self.root.as_ref() {
-            None => other.is_empty(),
-            Some(x) => match other.root.as_ref() {
-                None => false,
-                Some(y) => x == y,
-            },
-        }
+impl<T> Drop for Node<T> {
+    fn drop(&mut self) {
+        drop_children::<Rc<Node<T>>>(mem::take(&mut self.children));
     }
 }
 
-#[derive(Debug)]
-struct TreeNode<T> {
-    element: T,
-    children: List<Tree<T>>,
-}
+impl<T> Tree<T> {
+    /// Returns a tree of the same shape with `f` applied to every
+    /// element.
+    ///
+    /// `f` is called on the elements in preorder. Like all the
+    /// transformations on `Tree`, this keeps an explicit stack instead
+    /// of recursing, so it works for trees of any depth.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{tree::Tree, List};
+    ///
+    /// let tree = Tree::new(1, List::from_value(Tree::leaf(2)));
+    ///
+    /// assert!(tree.map(|x| x * 10).preorder().copied().eq([10, 20]));
+    /// ```
+    pub fn map<U, F>(&self, f: F) -> Tree<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        self.mapped(f)
+    }
 
-impl<T> TreeNode<T> {
-    fn leaf(element: T) -> Self {
-        Self {
-            element,
-            children: List::new(),
-        }
+    /// Folds the tree bottom up: `f` gets every element together with
+    /// the results for its children, in order. Returns `None` for an
+    /// empty tree; empty subtrees are left out of the children results.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{tree::Tree, List};
+    ///
+    /// let children = List::new().pushed_front(Tree::leaf(3)).pushed_front(Tree::leaf(2));
+    /// let tree = Tree::new(1, children);
+    /// let sum = tree.fold(|x, sums| x + sums.iter().sum::<i32>());
+    ///
+    /// assert_eq!(sum, Some(6));
+    /// ```
+    pub fn fold<R, F>(&self, mut f: F) -> Option<R>
+    where
+        F: FnMut(&T, List<R>) -> R,
+    {
+        self.folded(|x, children| f(x, list_from(children)))
     }
 
-    fn new(element: T, children: List<Tree<T>>) -> Self {
-        Self { element, children }
+    /// Combines two trees node by node with `f`. Where one node has more
+    /// children than the other the extra children are dropped, and a
+    /// position that is empty in either tree is empty in the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{tree::Tree, List};
+    ///
+    /// let a = Tree::new(1, List::new().pushed_front(Tree::leaf(3)).pushed_front(Tree::leaf(2)));
+    /// let b = Tree::new(10, List::from_value(Tree::leaf(20)));
+    ///
+    /// assert!(a.zip_with(&b, |x, y| x + y).preorder().copied().eq([11, 22]));
+    /// ```
+    pub fn zip_with<U, V, F>(&self, other: &Tree<U>, f: F) -> Tree<V>
+    where
+        F: FnMut(&T, &U) -> V,
+    {
+        self.zipped_with(other, f)
     }
 }
 
-impl<T> PartialEq for TreeNode<T>
-where
-    T: PartialEq,
-{
-    fn eq(&self, other: &Self) -> bool {
-        self.element == other.element && self.children == other.children
+impl<T: Clone> Tree<T> {
+    /// Rebuilds the tree on `Arc`s so it can be shared between threads.
+    ///
+    /// Subtrees and tails of child lists that are shared within the tree
+    /// are converted once and stay shared, so a DAG stays a DAG.
+    ///
+    /// Complexity: O(n)
+    ///
+    /// # Examples
+    /// ```
+    /// use persi_ds::unsync::{tree::Tree, List};
+    ///
+    /// let shared = Tree::leaf(2);
+    /// let tree = Tree::new(1, List::new().pushed_front(shared.clone()).pushed_front(shared));
+    /// let converted = tree.to_sync();
+    ///
+    /// let children: Vec<_> = converted.children().unwrap().iter().collect();
+    /// assert!(children[0].ptr_eq(children[1]));
+    /// ```
+    pub fn to_sync(&self) -> crate::sync::tree::Tree<T> {
+        self.converted(&mut ConversionMemo::new(), T::clone)
     }
 }
 
-impl<T: Clone> Clone for TreeNode<T> {
-    fn clone(&self) -> Self {
-        Self {
-            element: self.element.clone(),
-            children: self.children.clone(),
-        }
+impl<T: Clone> From<&Tree<T>> for crate::sync::tree::Tree<T> {
+    fn from(tree: &Tree<T>) -> Self {
+        tree.to_sync()
     }
 }
 
@@ -109,6 +158,32 @@ mod tests {
     use super::*;
     use crate::unsynced_list;
 
+    //        1
+    //      / | \
+    //     2  3  5
+    //        |
+    //        4
+    fn sample() -> Tree<i32> {
+        let t3 = Tree::new(3, List::from_value(Tree::leaf(4)));
+        let children = List::new()
+            .pushed_front(Tree::leaf(5))
+            .pushed_front(t3)
+            .pushed_front(Tree::leaf(2));
+        Tree::new(1, children)
+    }
+
+    fn child(tree: &Tree<i32>, index: usize) -> &Tree<i32> {
+        tree.children().unwrap().iter().nth(index).unwrap()
+    }
+
+    fn chain(depth: i32) -> Tree<i32> {
+        let mut tree = Tree::leaf(0);
+        for i in 1..depth {
+            tree = Tree::new(i, List::from_value(tree));
+        }
+        tree
+    }
+
     #[test]
     fn new_creates_empty_tree() {
         let tree = Tree::<&str>::empty();
@@ -166,6 +241,17 @@ mod tests {
         assert!(t6 == t6);
     }
 
+    #[test]
+    fn ptr_eq_detects_shared_root() {
+        let t1 = Tree::new(4, unsynced_list!(Tree::leaf(5)));
+        let t2 = Tree::new(4, unsynced_list!(Tree::leaf(5)));
+
+        assert!(t1.ptr_eq(&t1.clone()));
+        assert!(!t1.ptr_eq(&t2));
+        assert!(Tree::<i32>::empty().ptr_eq(&Tree::empty()));
+        assert!(!t1.ptr_eq(&Tree::empty()));
+    }
+
     #[test]
     fn tree_is_clone() {
         let t1 = Tree::<&str>::empty();
@@ -182,6 +268,9 @@ mod tests {
 
         use super::*;
 
+        // The box makes sure the element owns heap memory that must not be
+        // shared between clones of the tree.
+        #[allow(clippy::box_collection)]
         #[derive(Debug, PartialEq)]
         struct NoClone {
             pub v: Box<String>,
@@ -213,4 +302,891 @@ mod tests {
             assert_eq!(t2, t2_clone);
         }
     }
+
+    mod traversal {
+        use super::*;
+        use alloc::{vec, vec::Vec};
+
+        #[test]
+        fn empty_tree_yields_nothing() {
+            let tree = Tree::<i32>::empty();
+
+            assert_eq!(tree.preorder().count(), 0);
+            assert_eq!(tree.postorder().count(), 0);
+            assert_eq!(tree.level_order().count(), 0);
+            assert_eq!(tree.leaves().count(), 0);
+        }
+
+        #[test]
+        fn preorder_visits_parents_first() {
+            assert!(sample().preorder().copied().eq([1, 2, 3, 4, 5]));
+        }
+
+        #[test]
+        fn postorder_visits_children_first() {
+            assert!(sample().postorder().copied().eq([2, 4, 3, 5, 1]));
+        }
+
+        #[test]
+        fn level_order_visits_by_level() {
+            let tree = Tree::new(
+                0,
+                List::new()
+                    .pushed_front(Tree::leaf(2))
+                    .pushed_front(sample()),
+            );
+
+            assert!(tree.level_order().copied().eq([0, 1, 2, 2, 3, 5, 4]));
+        }
+
+        #[test]
+        fn leaves_yields_nodes_without_children() {
+            assert!(sample().leaves().copied().eq([2, 4, 5]));
+        }
+
+        #[test]
+        fn empty_children_are_skipped_but_keep_their_index() {
+            let children = List::new()
+                .pushed_front(Tree::leaf(2))
+                .pushed_front(Tree::empty());
+            let tree = Tree::new(1, children);
+
+            assert!(tree.preorder().copied().eq([1, 2]));
+            assert!(tree.leaves().copied().eq([2]));
+            let paths: Vec<_> = tree.preorder().with_paths().collect();
+            assert_eq!(paths, vec![(vec![], &1), (vec![1], &2)]);
+        }
+
+        #[test]
+        fn with_depth_reports_depth() {
+            let tree = sample();
+            let expected = [(0, &1), (1, &2), (1, &3), (2, &4), (1, &5)];
+
+            assert!(tree.preorder().with_depth().eq(expected));
+            assert!(tree.postorder().with_depth().eq([
+                (1, &2),
+                (2, &4),
+                (1, &3),
+                (1, &5),
+                (0, &1)
+            ]));
+            assert!(tree.level_order().with_depth().eq([
+                (0, &1),
+                (1, &2),
+                (1, &3),
+                (1, &5),
+                (2, &4)
+            ]));
+            assert!(tree.leaves().with_depth().eq([(1, &2), (2, &4), (1, &5)]));
+        }
+
+        #[test]
+        fn with_paths_reports_child_indices() {
+            let tree = sample();
+
+            let preorder: Vec<_> = tree.preorder().with_paths().collect();
+            assert_eq!(
+                preorder,
+                vec![
+                    (vec![], &1),
+                    (vec![0], &2),
+                    (vec![1], &3),
+                    (vec![1, 0], &4),
+                    (vec![2], &5)
+                ]
+            );
+            let postorder: Vec<_> = tree.postorder().with_paths().collect();
+            assert_eq!(postorder[1], (vec![1, 0], &4));
+            assert_eq!(postorder[4], (vec![], &1));
+            let level_order: Vec<_> = tree.level_order().with_paths().collect();
+            assert_eq!(level_order[3], (vec![2], &5));
+            assert_eq!(level_order[4], (vec![1, 0], &4));
+            let leaves: Vec<_> = tree.leaves().with_paths().map(|(p, _)| p).collect();
+            assert_eq!(leaves, vec![vec![0], vec![1, 0], vec![2]]);
+        }
+
+        #[test]
+        fn deep_tree_does_not_overflow() {
+            let mut tree = Tree::leaf(0);
+            for i in 1..10_000 {
+                tree = Tree::new(i, List::from_value(tree));
+            }
+
+            assert_eq!(tree.preorder().count(), 10_000);
+            assert_eq!(tree.postorder().next(), Some(&0));
+            assert_eq!(tree.level_order().with_depth().last(), Some((9_999, &0)));
+            assert_eq!(tree.leaves().with_paths().next().unwrap().0.len(), 9_999);
+        }
+    }
+
+    mod transform {
+        use super::*;
+        use alloc::{vec, vec::Vec};
+
+        #[test]
+        fn map_keeps_shape() {
+            let mapped = sample().map(|x| x * 2);
+
+            assert!(mapped.preorder().copied().eq([2, 4, 6, 8, 10]));
+            let paths = |t: &Tree<i32>| {
+                t.preorder()
+                    .with_paths()
+                    .map(|(p, _)| p)
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(paths(&mapped), paths(&sample()));
+        }
+
+        #[test]
+        fn map_keeps_empty_children() {
+            let tree = Tree::new(1, List::from_value(Tree::empty()));
+            let mapped = tree.map(|x| x + 1);
+
+            assert_eq!(mapped, Tree::new(2, List::from_value(Tree::empty())));
+            assert!(Tree::<i32>::empty().map(|x| x + 1).is_empty());
+        }
+
+        #[test]
+        fn fold_gets_children_results_in_order() {
+            let render = sample().fold(|x, children| {
+                let mut s = alloc::format!("{}", x);
+                for child in &children {
+                    s.push_str(&alloc::format!("({})", child));
+                }
+                s
+            });
+
+            assert_eq!(render.as_deref(), Some("1(2)(3(4))(5)"));
+            assert_eq!(Tree::<i32>::empty().fold(|x, _| *x), None);
+        }
+
+        #[test]
+        fn unfold_builds_children_in_order() {
+            let tree = Tree::unfold(1, |n| (n, (n * 2..n * 2 + 2).filter(|&c| c < 8)));
+
+            assert!(tree.preorder().copied().eq([1, 2, 4, 5, 3, 6, 7]));
+            assert!(tree.level_order().copied().eq(1..8));
+        }
+
+        #[test]
+        fn filter_prune_removes_whole_subtrees() {
+            let pruned = sample().filter_prune(|&x| x != 3);
+
+            assert!(pruned.preorder().copied().eq([1, 2, 5]));
+            assert!(sample().filter_prune(|&x| x != 1).is_empty());
+        }
+
+        #[test]
+        fn filter_prune_shares_untouched_subtrees() {
+            let tree = sample();
+            let pruned = tree.filter_prune(|&x| x != 5);
+            let kept: Vec<_> = pruned.children().unwrap().iter().collect();
+            let original: Vec<_> = tree.children().unwrap().iter().collect();
+
+            assert!(kept[0].ptr_eq(original[0]));
+            assert!(kept[1].ptr_eq(original[1]));
+            assert!(tree.filter_prune(|_| true).ptr_eq(&tree));
+        }
+
+        #[test]
+        fn zip_with_truncates_to_common_shape() {
+            let other = Tree::new(10, List::from_value(Tree::leaf(20)));
+            let zipped = sample().zip_with(&other, |a, b| a + b);
+
+            assert!(zipped.preorder().copied().eq([11, 22]));
+            assert!(sample()
+                .zip_with(&Tree::<i32>::empty(), |a, b| a + b)
+                .is_empty());
+        }
+
+        #[test]
+        fn zip_with_itself_pairs_every_node() {
+            let tree = sample();
+            let pairs: Vec<_> = tree
+                .zip_with(&tree, |a, b| (*a, *b))
+                .preorder()
+                .copied()
+                .collect();
+
+            assert_eq!(pairs, vec![(1, 1), (2, 2), (3, 3), (4, 4), (5, 5)]);
+        }
+
+        #[test]
+        fn transformations_handle_deep_trees() {
+            let tree = chain(10_000);
+
+            let mapped = tree.map(|x| x + 1);
+            assert_eq!(mapped.leaves().next(), Some(&1));
+            assert_eq!(
+                tree.fold(|_, children| 1 + children.iter().sum::<usize>()),
+                Some(10_000)
+            );
+            let pruned = tree.filter_prune(|&x| x != 0);
+            assert_eq!(pruned.leaves().next(), Some(&1));
+            let zipped = tree.zip_with(&mapped, |a, b| b - a);
+            assert!(zipped.preorder().all(|&x| x == 1));
+            let unfolded = Tree::unfold(0, |n| (n, if n < 9_999 { Some(n + 1) } else { None }));
+            assert_eq!(unfolded.preorder().count(), 10_000);
+        }
+    }
+
+    mod paths {
+        use super::*;
+        use alloc::vec::Vec;
+
+        #[test]
+        fn get_at_follows_child_indices() {
+            let tree = sample();
+
+            assert_eq!(tree.get_at(&[]), Some(&1));
+            assert_eq!(tree.get_at(&[1]), Some(&3));
+            assert_eq!(tree.get_at(&[1, 0]), Some(&4));
+            assert_eq!(tree.get_at(&[3]), None);
+            assert_eq!(tree.get_at(&[0, 0]), None);
+            assert_eq!(Tree::<i32>::empty().get_at(&[]), None);
+            assert!(tree.subtree_at(&[1]).unwrap().ptr_eq(child(&tree, 1)));
+        }
+
+        #[test]
+        fn get_at_agrees_with_with_paths() {
+            let tree = sample();
+
+            for (path, x) in tree.preorder().with_paths() {
+                assert_eq!(tree.get_at(&path), Some(x));
+            }
+        }
+
+        #[test]
+        fn updated_at_copies_only_ancestors() {
+            let t1 = sample();
+            let t2 = t1.updated_at(&[1, 0], |x| x * 10);
+
+            assert!(t2.preorder().copied().eq([1, 2, 3, 40, 5]));
+            assert!(t1.preorder().copied().eq([1, 2, 3, 4, 5]));
+            assert!(child(&t2, 0).ptr_eq(child(&t1, 0)));
+            assert!(!child(&t2, 1).ptr_eq(child(&t1, 1)));
+            assert!(child(&t2, 2).ptr_eq(child(&t1, 2)));
+        }
+
+        #[test]
+        fn updated_at_shares_children_of_node() {
+            let t1 = sample();
+            let t2 = t1.updated_at(&[1], |x| x + 1);
+
+            assert_eq!(t2.get_at(&[1]), Some(&4));
+            assert!(child(child(&t2, 1), 0).ptr_eq(child(child(&t1, 1), 0)));
+        }
+
+        #[test]
+        fn inserted_child_at_shifts_later_siblings() {
+            let t1 = sample();
+            let t2 = t1.inserted_child_at(&[], 1, Tree::leaf(9));
+            let t3 = t1.inserted_child_at(&[1], 1, Tree::leaf(9));
+
+            assert!(t2.preorder().copied().eq([1, 2, 9, 3, 4, 5]));
+            assert!(child(&t2, 2).ptr_eq(child(&t1, 1)));
+            assert!(child(&t2, 3).ptr_eq(child(&t1, 2)));
+            assert!(t3.preorder().copied().eq([1, 2, 3, 4, 9, 5]));
+            assert!(child(&t3, 2).ptr_eq(child(&t1, 2)));
+        }
+
+        #[test]
+        fn inserted_child_at_can_append_to_leaf() {
+            let tree = sample().inserted_child_at(&[2], 0, Tree::leaf(6));
+
+            assert_eq!(tree.get_at(&[2, 0]), Some(&6));
+        }
+
+        #[test]
+        fn removed_at_drops_subtree() {
+            let t1 = sample();
+            let t2 = t1.removed_at(&[1]);
+
+            assert!(t2.preorder().copied().eq([1, 2, 5]));
+            assert!(child(&t2, 1).ptr_eq(child(&t1, 2)));
+            assert!(t1.removed_at(&[]).is_empty());
+            let nested = t1.removed_at(&[1, 0]);
+            assert!(nested.preorder().copied().eq([1, 2, 3, 5]));
+        }
+
+        #[test]
+        #[should_panic]
+        fn updated_at_missing_path_panics() {
+            sample().updated_at(&[3], |x| *x);
+        }
+
+        #[test]
+        #[should_panic]
+        fn inserted_child_at_past_end_panics() {
+            sample().inserted_child_at(&[], 4, Tree::leaf(0));
+        }
+
+        #[test]
+        #[should_panic]
+        fn removed_at_missing_path_panics() {
+            sample().removed_at(&[0, 0]);
+        }
+
+        #[test]
+        fn updates_work_on_deep_paths() {
+            let mut tree = Tree::leaf(0);
+            for i in 1..10_000 {
+                tree = Tree::new(i, List::from_value(tree));
+            }
+            let path: Vec<_> = core::iter::repeat(0).take(9_999).collect();
+
+            let updated = tree.updated_at(&path, |x| x + 1);
+            assert_eq!(updated.get_at(&path), Some(&1));
+            assert_eq!(tree.get_at(&path), Some(&0));
+            let removed = tree.removed_at(&path);
+            assert_eq!(removed.leaves().next(), Some(&1));
+        }
+    }
+
+    mod zipper {
+        use super::*;
+
+        #[test]
+        fn new_focuses_on_root() {
+            let tree = sample();
+            let zipper = TreeZipper::new(&tree);
+
+            assert!(zipper.is_root());
+            assert!(zipper.focus().ptr_eq(&tree));
+            assert!(zipper.up().is_none());
+            assert!(zipper.left().is_none());
+            assert!(zipper.right().is_none());
+        }
+
+        #[test]
+        fn moves_follow_the_tree() {
+            let zipper = TreeZipper::new(&sample());
+
+            let z = zipper.down(1).unwrap();
+            assert_eq!(z.focus().root(), Some(&3));
+            assert_eq!(z.left().unwrap().focus().root(), Some(&2));
+            assert_eq!(z.right().unwrap().focus().root(), Some(&5));
+            assert!(z.right().unwrap().right().is_none());
+            assert!(z.left().unwrap().left().is_none());
+            assert_eq!(z.down(0).unwrap().focus().root(), Some(&4));
+            assert!(z.down(1).is_none());
+            assert!(zipper.down(3).is_none());
+            assert_eq!(z.up().unwrap().focus().root(), Some(&1));
+        }
+
+        #[test]
+        fn moving_without_edits_shares_everything() {
+            let tree = sample();
+            let zipper = TreeZipper::new(&tree)
+                .down(2)
+                .unwrap()
+                .left()
+                .unwrap()
+                .down(0)
+                .unwrap();
+
+            assert!(zipper.up().unwrap().focus().ptr_eq(child(&tree, 1)));
+            assert!(zipper.to_tree().ptr_eq(&tree));
+        }
+
+        #[test]
+        fn replaced_rebuilds_only_ancestors() {
+            let tree = sample();
+            let edited = TreeZipper::new(&tree)
+                .down(1)
+                .unwrap()
+                .down(0)
+                .unwrap()
+                .replaced(Tree::leaf(40))
+                .to_tree();
+
+            assert!(edited.preorder().copied().eq([1, 2, 3, 40, 5]));
+            assert!(tree.preorder().copied().eq([1, 2, 3, 4, 5]));
+            assert!(child(&edited, 0).ptr_eq(child(&tree, 0)));
+            assert!(child(&edited, 2).ptr_eq(child(&tree, 2)));
+        }
+
+        #[test]
+        fn edits_survive_moving_around() {
+            let edited = TreeZipper::new(&sample())
+                .down(0)
+                .unwrap()
+                .replaced(Tree::leaf(20))
+                .right()
+                .unwrap()
+                .down(0)
+                .unwrap()
+                .up()
+                .unwrap()
+                .right()
+                .unwrap()
+                .to_tree();
+
+            assert!(edited.preorder().copied().eq([1, 20, 3, 4, 5]));
+        }
+
+        #[test]
+        fn edits_below_survive_unchanged_levels() {
+            let edited = TreeZipper::new(&sample())
+                .down(1)
+                .unwrap()
+                .down(0)
+                .unwrap()
+                .replaced(Tree::leaf(40))
+                .up()
+                .unwrap()
+                .down(0)
+                .unwrap()
+                .to_tree();
+
+            assert!(edited.preorder().copied().eq([1, 2, 3, 40, 5]));
+        }
+
+        #[test]
+        fn inserted_siblings_keep_focus() {
+            let zipper = TreeZipper::new(&sample()).down(1).unwrap();
+            let edited = zipper
+                .inserted_left(Tree::leaf(6))
+                .inserted_right(Tree::leaf(7));
+
+            assert_eq!(edited.focus().root(), Some(&3));
+            assert_eq!(edited.left().unwrap().focus().root(), Some(&6));
+            assert!(edited
+                .to_tree()
+                .preorder()
+                .copied()
+                .eq([1, 2, 6, 3, 4, 7, 5]));
+        }
+
+        #[test]
+        #[should_panic]
+        fn inserted_left_of_root_panics() {
+            TreeZipper::new(&sample()).inserted_left(Tree::leaf(0));
+        }
+    }
+
+    mod diff {
+        use super::*;
+        use alloc::{vec, vec::Vec};
+
+        #[test]
+        fn diff_of_shared_trees_is_empty() {
+            let tree = sample();
+            assert!(Tree::diff(&tree, &tree.clone()).is_empty());
+            assert!(Tree::diff(&Tree::<i32>::empty(), &Tree::empty()).is_empty());
+        }
+
+        #[test]
+        fn diff_of_equal_copies_is_empty() {
+            assert!(Tree::diff(&sample(), &sample()).is_empty());
+        }
+
+        #[test]
+        fn diff_replaces_whole_tree_at_root() {
+            let tree = sample();
+            let empty = Tree::empty();
+
+            assert_eq!(
+                Tree::diff(&tree, &empty),
+                [TreeEdit::Delete { path: Vec::new() }]
+            );
+            let edits = Tree::diff(&empty, &tree);
+            assert_eq!(
+                edits,
+                [TreeEdit::Insert {
+                    path: Vec::new(),
+                    tree: tree.clone()
+                }]
+            );
+            assert!(empty.apply(&edits).ptr_eq(&tree));
+            assert!(tree.apply(&Tree::diff(&tree, &empty)).is_empty());
+        }
+
+        #[test]
+        fn diff_finds_deletes_and_relabels() {
+            let old = sample();
+            let new = old.removed_at(&[0]).updated_at(&[0, 0], |_| 40);
+            let edits = Tree::diff(&old, &new);
+
+            assert_eq!(
+                edits,
+                [
+                    TreeEdit::Delete { path: vec![0] },
+                    TreeEdit::Relabel {
+                        path: vec![0, 0],
+                        element: 40
+                    },
+                ]
+            );
+            assert_eq!(old.apply(&edits), new);
+        }
+
+        #[test]
+        fn diff_moves_shared_children() {
+            let old = sample();
+            let children = List::new()
+                .pushed_front(child(&old, 0).clone())
+                .pushed_front(child(&old, 2).clone())
+                .pushed_front(child(&old, 1).clone());
+            let new = Tree::new(1, children);
+            let edits = Tree::diff(&old, &new);
+
+            assert_eq!(
+                edits,
+                [TreeEdit::Move {
+                    from: vec![0],
+                    to: vec![2]
+                }]
+            );
+            let patched = old.apply(&edits);
+            assert_eq!(patched, new);
+            assert!(child(&patched, 0).ptr_eq(child(&old, 1)));
+        }
+
+        #[test]
+        fn diff_prefers_shared_children_over_equal_roots() {
+            let shared = Tree::new(3, List::from_value(Tree::leaf(4)));
+            let other = Tree::leaf(3);
+            let old = Tree::new(
+                1,
+                List::new().pushed_front(shared.clone()).pushed_front(other),
+            );
+            let new = Tree::new(1, List::from_value(shared));
+
+            assert_eq!(Tree::diff(&old, &new), [TreeEdit::Delete { path: vec![0] }]);
+        }
+
+        #[test]
+        fn apply_shares_untouched_and_inserted_subtrees() {
+            let old = sample();
+            let inserted = Tree::new(6, List::from_value(Tree::leaf(7)));
+            let new = old
+                .inserted_child_at(&[1], 1, inserted.clone())
+                .updated_at(&[], |_| 10);
+            let patched = old.apply(&Tree::diff(&old, &new));
+
+            assert_eq!(patched, new);
+            assert!(child(&patched, 0).ptr_eq(child(&old, 0)));
+            assert!(child(&patched, 2).ptr_eq(child(&old, 2)));
+            assert!(patched
+                .subtree_at(&[1, 0])
+                .unwrap()
+                .ptr_eq(child(child(&old, 1), 0)));
+            assert!(patched.subtree_at(&[1, 1]).unwrap().ptr_eq(&inserted));
+        }
+
+        #[test]
+        fn apply_reproduces_random_edits() {
+            let mut seed = 7u32;
+            let mut next = |bound: usize| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (seed >> 16) as usize % bound
+            };
+            for _ in 0..200 {
+                let old = sample();
+                let mut new = old.clone();
+                for _ in 0..1 + next(6) {
+                    let paths: Vec<_> = new.preorder().with_paths().map(|(p, _)| p).collect();
+                    let path = &paths[next(paths.len())];
+                    new = match next(4) {
+                        0 if !path.is_empty() => new.removed_at(path),
+                        1 => new.updated_at(path, |x| x + 100),
+                        2 => {
+                            let len = new
+                                .subtree_at(path)
+                                .unwrap()
+                                .children()
+                                .unwrap()
+                                .iter()
+                                .count();
+                            let tree = Tree::leaf(next(10) as i32);
+                            new.inserted_child_at(path, next(len + 1), tree)
+                        }
+                        _ if !path.is_empty() => {
+                            let subtree = new.subtree_at(path).unwrap().clone();
+                            let (&index, parent) = path.split_last().unwrap();
+                            let len = new
+                                .subtree_at(parent)
+                                .unwrap()
+                                .children()
+                                .unwrap()
+                                .iter()
+                                .count();
+                            let to = (index + 1 + next(len)) % len;
+                            new.removed_at(path).inserted_child_at(parent, to, subtree)
+                        }
+                        _ => new,
+                    };
+                }
+                assert_eq!(old.apply(&Tree::diff(&old, &new)), new);
+                assert_eq!(new.apply(&Tree::diff(&new, &old)), old);
+            }
+        }
+
+        #[test]
+        fn diff_handles_deep_trees() {
+            let old = (0..2000).fold(Tree::leaf(0), |tree, x| {
+                Tree::new(x, List::from_value(tree))
+            });
+            let path = vec![0; 2000];
+            let new = old.updated_at(&path, |_| -1);
+            let edits = Tree::diff(&old, &new);
+
+            assert_eq!(edits, [TreeEdit::Relabel { path, element: -1 }]);
+            assert_eq!(old.apply(&edits), new);
+        }
+    }
+
+    mod select {
+        use super::*;
+        use alloc::{format, vec, vec::Vec};
+
+        //       a
+        //    /  |  \
+        //   b   c   b
+        //   |   |
+        //   a   b
+        fn sample() -> Tree<char> {
+            let children = List::new()
+                .pushed_front(Tree::leaf('b'))
+                .pushed_front(Tree::new('c', List::from_value(Tree::leaf('b'))))
+                .pushed_front(Tree::new('b', List::from_value(Tree::leaf('a'))));
+            Tree::new('a', children)
+        }
+
+        fn paths(found: Vec<(Vec<usize>, &Tree<char>)>) -> Vec<Vec<usize>> {
+            found.into_iter().map(|(path, _)| path).collect()
+        }
+
+        #[test]
+        fn filter_finds_nodes_anywhere() {
+            let tree = sample();
+            let found = tree.select(&Selector::new().filter(|x| *x == 'b'));
+
+            assert_eq!(paths(found), [vec![0], vec![1, 0], vec![2]]);
+        }
+
+        #[test]
+        fn root_anchors_the_selection() {
+            let tree = sample();
+
+            assert_eq!(paths(tree.select(&Selector::root())), [Vec::<usize>::new()]);
+            let found = tree.select(&Selector::root().child().filter(|x| *x == 'c'));
+            assert_eq!(paths(found), [vec![1]]);
+            assert!(tree
+                .select(&Selector::root().filter(|x| *x == 'b'))
+                .is_empty());
+        }
+
+        #[test]
+        fn child_steps_keep_preorder() {
+            let tree = sample();
+            let found = tree.select(&Selector::new().child());
+
+            assert_eq!(
+                paths(found),
+                [vec![0], vec![0, 0], vec![1], vec![1, 0], vec![2]]
+            );
+        }
+
+        #[test]
+        fn descendant_steps_visit_each_node_once() {
+            let tree = sample();
+            let found = tree.select(&Selector::new().descendant());
+            assert_eq!(
+                paths(found),
+                [vec![0], vec![0, 0], vec![1], vec![1, 0], vec![2]]
+            );
+
+            let below_b = Selector::new().filter(|x| *x == 'b').descendant();
+            assert_eq!(paths(tree.select(&below_b)), [vec![0, 0]]);
+        }
+
+        #[test]
+        fn selected_subtrees_match_their_paths() {
+            let tree = sample();
+            let selector = Selector::new().filter(|x| *x == 'c').descendant();
+
+            for (path, subtree) in tree.select(&selector) {
+                assert!(tree.subtree_at(&path).unwrap().ptr_eq(subtree));
+            }
+        }
+
+        #[test]
+        fn paths_feed_into_updates() {
+            let tree = sample();
+            let found = tree.select(&Selector::new().filter(|x| *x == 'b'));
+            let updated = found
+                .iter()
+                .fold(tree.clone(), |t, (path, _)| t.updated_at(path, |_| 'B'));
+
+            assert!(updated
+                .preorder()
+                .copied()
+                .eq(['a', 'B', 'a', 'c', 'B', 'B']));
+        }
+
+        #[test]
+        fn select_skips_empty_trees() {
+            let tree = Tree::new(
+                'a',
+                List::new()
+                    .pushed_front(Tree::leaf('b'))
+                    .pushed_front(Tree::empty()),
+            );
+
+            assert_eq!(paths(tree.select(&Selector::new().child())), [vec![1]]);
+            assert!(Tree::<char>::empty().select(&Selector::new()).is_empty());
+        }
+
+        #[test]
+        fn select_handles_deep_trees() {
+            let tree = (0..2000).fold(Tree::leaf(0), |tree, x| {
+                Tree::new(x, List::from_value(tree))
+            });
+            let found = tree.select(&Selector::new().descendant().filter(|x| *x == 0));
+
+            assert_eq!(found.len(), 2);
+            assert_eq!(found[1].0.len(), 2000);
+        }
+
+        #[test]
+        fn debug_lists_steps() {
+            let selector = Selector::root()
+                .descendant()
+                .filter(|x: &char| *x == 'a')
+                .child();
+            assert_eq!(
+                format!("{:?}", selector),
+                "[Root, Descendant, Filter, Child]"
+            );
+        }
+    }
+
+    mod draw {
+        use super::*;
+
+        #[test]
+        fn pretty_draws_outline() {
+            let expected = "1\n├── 2\n├── 3\n│   └── 4\n└── 5";
+            assert_eq!(sample().pretty(), expected);
+            assert_eq!(Tree::<i32>::empty().pretty(), "·");
+        }
+
+        #[test]
+        fn pretty_handles_deep_trees() {
+            let tree = (0..1000).fold(Tree::leaf(0), |tree, x| {
+                Tree::new(x, List::from_value(tree))
+            });
+            assert_eq!(tree.pretty().lines().count(), 1001);
+        }
+
+        #[test]
+        fn to_dot_draws_shared_nodes_once() {
+            let t1 = sample();
+            let t2 = t1.updated_at(&[0], |x| x * 10);
+            let dot = Tree::to_dot(&[&t1, &t2]);
+
+            assert!(dot.contains("v0 -> n0 [style=dashed];"));
+            assert!(dot.contains("v1 -> n"));
+            assert_eq!(dot.matches("label=\"3\"").count(), 1);
+            assert_eq!(dot.matches("label=\"1\"").count(), 2);
+            assert_eq!(dot.matches("label=\"20\"").count(), 1);
+            assert!(!dot.contains("tailport"));
+        }
+
+        #[test]
+        fn to_dot_escapes_labels() {
+            let dot = Tree::to_dot(&[&Tree::leaf("say \"hi\"")]);
+            assert!(dot.contains(r#"label="\"say \\\"hi\\\"\"""#));
+        }
+    }
+
+    mod convert {
+        use super::*;
+        use alloc::vec::Vec;
+
+        #[test]
+        fn to_sync_keeps_shape() {
+            let tree = Tree::new(
+                1,
+                List::new()
+                    .pushed_front(Tree::leaf(3))
+                    .pushed_front(Tree::leaf(2)),
+            );
+            let converted = tree.to_sync();
+
+            assert!(converted
+                .preorder()
+                .with_paths()
+                .eq(tree.preorder().with_paths()));
+            assert_eq!(converted.to_unsync(), tree);
+            assert!(Tree::<i32>::empty().to_sync().is_empty());
+        }
+
+        #[test]
+        fn to_sync_keeps_shared_subtrees_shared() {
+            let shared = Tree::new(2, List::from_value(Tree::leaf(3)));
+            let a = Tree::new(4, List::from_value(shared.clone()));
+            let tree = Tree::new(1, List::new().pushed_front(a).pushed_front(shared));
+
+            let converted = tree.to_sync();
+            let children: Vec<_> = converted.children().unwrap().iter().collect();
+            let nested = children[1].children().unwrap().front().unwrap();
+            assert!(children[0].ptr_eq(nested));
+        }
+
+        #[test]
+        fn to_sync_keeps_shared_child_lists_shared() {
+            let tail = List::new().pushed_front(Tree::leaf(3));
+            let a = Tree::new(1, tail.pushed_front(Tree::leaf(2)));
+            let b = Tree::new(4, tail.clone());
+            let tree = Tree::new(0, List::new().pushed_front(b).pushed_front(a));
+
+            let converted = tree.to_sync();
+            let children: Vec<_> = converted.children().unwrap().iter().collect();
+            let a_children = children[0].children().unwrap();
+            let b_children = children[1].children().unwrap();
+            assert!(a_children.popped_front().ptr_eq(b_children));
+        }
+
+        #[test]
+        fn to_sync_handles_deep_trees() {
+            let mut tree = Tree::leaf(0);
+            for i in 1..10_000 {
+                tree = Tree::new(i, List::from_value(tree));
+            }
+
+            let converted = tree.to_sync();
+            assert_eq!(converted.leaves().with_depth().next(), Some((9_999, &0)));
+        }
+    }
+
+    mod drop {
+        use super::*;
+
+        #[test]
+        fn dropping_deep_chain_does_not_overflow() {
+            drop(chain(1_000_000));
+        }
+
+        #[test]
+        fn dropping_list_of_deep_trees_does_not_overflow() {
+            let list = List::new()
+                .pushed_front(chain(100_000))
+                .pushed_front(chain(100_000));
+
+            drop(list);
+        }
+
+        #[test]
+        fn dropping_keeps_shared_subtrees_alive() {
+            let tree = chain(100_000);
+            let bigger = Tree::new(0, List::from_value(tree.clone()));
+
+            drop(bigger);
+            assert_eq!(tree.preorder().count(), 100_000);
+            assert_eq!(tree.leaves().next(), Some(&0));
+        }
+    }
 }
@@ -0,0 +1,243 @@
+use alloc::rc::Rc;
+
+use crate::shared::{
+    self,
+    vector::{VectorLink, VectorNode},
+};
+
+pub type Vector<T> = shared::vector::Vector<UnsyncVectorLink<T>>;
+
+pub(crate) type UnsyncVectorLink<T> = Rc<Node<T>>;
+
+pub struct Node<T>(VectorNode<UnsyncVectorLink<T>>);
+
+impl<T> VectorLink for Rc<Node<T>> {
+    type ValueType = T;
+
+    fn new(node: VectorNode<Self>) -> Self {
+        Rc::new(Node(node))
+    }
+    fn clone(&self) -> Self {
+        <Self as Clone>::clone(self)
+    }
+    fn node(&self) -> &VectorNode<Self> {
+        &self.0
+    }
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec::Vec};
+
+    #[test]
+    fn empty_creates_vector() {
+        let vector = Vector::<i32>::new();
+
+        assert!(vector.is_empty());
+        assert_eq!(vector.len(), 0);
+        assert_eq!(vector.get(0), None);
+        assert_eq!(vector.back(), None);
+        assert_eq!(vector.iter().next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn popped_back_on_empty_vector_panics() {
+        Vector::<i32>::new().popped_back();
+    }
+
+    #[test]
+    #[should_panic]
+    fn updated_out_of_bounds_panics() {
+        Vector::new().pushed_back(1).updated(1, 2);
+    }
+
+    #[test]
+    fn pushed_back_keeps_old_versions() {
+        let mut versions = Vec::new();
+        let mut vector = Vector::new();
+        for i in 0..2000 {
+            versions.push(vector.clone());
+            vector = vector.pushed_back(i);
+        }
+
+        assert_eq!(vector.len(), 2000);
+        assert!(vector.iter().copied().eq(0..2000));
+        for (len, version) in versions.iter().enumerate().step_by(97) {
+            assert_eq!(version.len(), len);
+            assert!(version.iter().copied().eq(0..len));
+        }
+    }
+
+    #[test]
+    fn get_returns_every_element() {
+        for n in [0, 1, 32, 33, 1024, 1056, 1057, 40_000] {
+            let vector: Vector<usize> = (0..n).collect();
+
+            assert_eq!(vector.len(), n);
+            assert!((0..n).all(|i| vector.get(i) == Some(&i)));
+            assert_eq!(vector.get(n), None);
+            assert_eq!(vector.iter().len(), n);
+        }
+    }
+
+    #[test]
+    fn popped_back_undoes_pushed_back() {
+        let full: Vector<_> = (0..3000).collect();
+        let mut vector = full.clone();
+        for len in (0..3000).rev() {
+            vector = vector.popped_back();
+            assert_eq!(vector.len(), len);
+            assert_eq!(vector.back(), len.checked_sub(1).as_ref());
+        }
+
+        assert!(vector.is_empty());
+        assert_eq!(full.len(), 3000);
+    }
+
+    #[test]
+    fn updated_shares_the_rest() {
+        let v1: Vector<_> = (0..1000).collect();
+        let v2 = v1.updated(500, -1).updated(999, -2);
+
+        assert_eq!(v2.get(500), Some(&-1));
+        assert_eq!(v2.back(), Some(&-2));
+        assert_eq!(v1.get(500), Some(&500));
+        assert_eq!(v2.iter().filter(|&&x| x < 0).count(), 2);
+    }
+
+    #[test]
+    fn appended_joins_in_order() {
+        for (m, n) in [
+            (0, 5),
+            (5, 0),
+            (10, 10),
+            (30, 5),
+            (100, 3),
+            (3, 100),
+            (1000, 40_000),
+        ] {
+            let left: Vector<_> = (0..m).collect();
+            let right: Vector<_> = (m..m + n).collect();
+            let joined = left.appended(&right);
+
+            assert_eq!(joined.len(), m + n);
+            assert!(joined.iter().copied().eq(0..m + n));
+            assert!((0..m + n).step_by(7).all(|i| joined.get(i) == Some(&i)));
+        }
+    }
+
+    #[test]
+    fn many_small_appends_stay_indexable() {
+        let mut vector = Vector::new();
+        for i in 0..500 {
+            let piece: Vector<_> = (i * 37..(i + 1) * 37).collect();
+            vector = if i % 2 == 0 {
+                vector.appended(&piece)
+            } else {
+                vector
+                    .appended(&piece)
+                    .popped_back()
+                    .pushed_back((i + 1) * 37 - 1)
+            };
+        }
+
+        assert_eq!(vector.len(), 500 * 37);
+        assert!((0..500 * 37).all(|i| vector.get(i) == Some(&i)));
+        assert!(vector.iter().copied().eq(0..500 * 37));
+    }
+
+    #[test]
+    fn split_at_every_index() {
+        let vector: Vector<_> = (0..1100).collect();
+        for index in (0..=1100).step_by(13).chain([32, 1024, 1056, 1100]) {
+            let (left, right) = vector.split_at(index);
+
+            assert_eq!(left.len(), index);
+            assert!(left.iter().copied().eq(0..index));
+            assert!(right.iter().copied().eq(index..1100));
+            assert_eq!(left.appended(&right), vector);
+        }
+    }
+
+    #[test]
+    fn split_halves_can_grow_and_shrink() {
+        let vector: Vector<_> = (0..5000).collect();
+        let (left, right) = vector.split_at(2345);
+        let left = left.pushed_back(-1).popped_back().popped_back();
+        let right = right.updated(0, -1).pushed_back(5000);
+
+        assert!(left.iter().copied().eq(0..2344));
+        assert_eq!(right.get(0), Some(&-1));
+        assert!(right.iter().skip(1).copied().eq(2346..5001));
+        assert!((1..right.len()).all(|i| right.get(i) == Some(&(i as i32 + 2345))));
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_at_past_the_end_panics() {
+        Vector::new().pushed_back(1).split_at(2);
+    }
+
+    #[test]
+    fn matches_vec_model() {
+        let mut seed = 7u32;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 8) as usize % bound
+        };
+        let mut vector = Vector::new();
+        let mut model = Vec::new();
+        for step in 0..3000 {
+            match next(6) {
+                0 | 1 => {
+                    vector = vector.pushed_back(step);
+                    model.push(step);
+                }
+                2 if !model.is_empty() => {
+                    vector = vector.popped_back();
+                    model.pop();
+                }
+                3 if !model.is_empty() => {
+                    let index = next(model.len());
+                    vector = vector.updated(index, step);
+                    model[index] = step;
+                }
+                4 => {
+                    let piece: Vec<_> = (0..next(100)).map(|i| step + i).collect();
+                    vector = vector.appended(&piece.iter().copied().collect());
+                    model.extend(piece);
+                }
+                _ => {
+                    let index = next(model.len() + 1);
+                    let (left, right) = vector.split_at(index);
+                    vector = if next(2) == 0 {
+                        model.rotate_left(index);
+                        right.appended(&left)
+                    } else {
+                        left.appended(&right)
+                    };
+                }
+            }
+            assert_eq!(vector.len(), model.len());
+        }
+        assert!(vector.iter().eq(model.iter()));
+        assert!((0..model.len()).all(|i| vector.get(i) == Some(&model[i])));
+    }
+
+    #[test]
+    fn equality_and_debug() {
+        let v1: Vector<_> = (0..3).collect();
+        let v2 = Vector::new().pushed_back(0).pushed_back(1).pushed_back(2);
+
+        assert_eq!(v1, v2);
+        assert_ne!(v1, v2.popped_back());
+        assert!(v1.ptr_eq(&v1.clone()));
+        assert!(!v1.ptr_eq(&v2));
+        assert_eq!(format!("{:?}", v1), "[0, 1, 2]");
+    }
+}
@@ -1,2 +1,10 @@
+mod synced_hash_map;
+mod synced_int_map;
+mod synced_leftist_heap;
 mod synced_list;
+mod synced_queue;
 mod synced_rbmap;
+mod synced_seq;
+mod synced_stream;
+mod synced_tree;
+mod synced_vector;
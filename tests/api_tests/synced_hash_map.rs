@@ -0,0 +1,26 @@
+use std::thread;
+
+use persi_ds::sync::{HashMap, HashSet};
+
+#[test]
+fn share_map_between_threads() {
+    let map: HashMap<i32, i32> = (0..1000).map(|x| (x, x * x)).collect();
+
+    let map_clone = map.clone();
+    let handle = thread::spawn(move || {
+        let evens = (0..1000).step_by(2).fold(map_clone, |m, x| m.removed(&x));
+        evens.values().sum::<i32>()
+    });
+
+    let odd_squares: i32 = (1..1000).step_by(2).map(|x| x * x).sum();
+    assert_eq!(handle.join().unwrap(), odd_squares);
+    assert_eq!(map.len(), 1000);
+}
+
+#[test]
+fn store_str_in_set() {
+    let set: HashSet<&str> = HashSet::new().inserted("a");
+
+    let handle = thread::spawn(move || set.contains("a"));
+    assert!(handle.join().unwrap());
+}
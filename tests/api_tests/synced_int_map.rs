@@ -0,0 +1,29 @@
+use std::thread;
+
+use persi_ds::sync::{IntMap, IntSet};
+
+#[test]
+fn union_maps_built_in_threads() {
+    let handles: Vec<_> = (0..4u64)
+        .map(|t| thread::spawn(move || (0..250).map(|x| (x * 4 + t, t)).collect::<IntMap<u64>>()))
+        .collect();
+    let map = handles
+        .into_iter()
+        .map(|h| h.join().unwrap())
+        .fold(IntMap::new(), |acc, m| acc.union(&m));
+
+    assert_eq!(map.len(), 1000);
+    assert!(map.keys().copied().eq(0..1000));
+    assert_eq!(map.get(&7), Some(&3));
+}
+
+#[test]
+fn share_set_between_threads() {
+    let set: IntSet = (0..100).collect();
+
+    let set_clone = set.clone();
+    let handle = thread::spawn(move || set_clone.intersection(&(50..150).collect()).len());
+
+    assert_eq!(handle.join().unwrap(), 50);
+    assert_eq!(set.len(), 100);
+}
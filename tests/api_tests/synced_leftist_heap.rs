@@ -0,0 +1,20 @@
+use std::thread;
+
+use persi_ds::sync::LeftistHeap;
+
+#[test]
+fn schedule_snapshot_in_other_thread() {
+    let events: LeftistHeap<(u32, &str)> = [(30, "c"), (10, "a"), (20, "b")].into_iter().collect();
+
+    let snapshot = events.clone();
+    let handle = thread::spawn(move || {
+        let later = snapshot.popped_min().pushed((5, "d"));
+        later
+            .iter_sorted()
+            .map(|&(_, name)| name)
+            .collect::<String>()
+    });
+
+    assert_eq!(handle.join().unwrap(), "dbc");
+    assert_eq!(events.peek_min(), Some(&(10, "a")));
+}
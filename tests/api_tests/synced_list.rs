@@ -30,3 +30,15 @@ fn send_list() {
 
     assert_eq!(list, synced_list!(1, 2, 3, 4));
 }
+
+#[test]
+fn send_converted_unsync_list() {
+    let list = persi_ds::unsync::List::new()
+        .pushed_front(2)
+        .pushed_front(1);
+    let list = list.to_sync();
+
+    let handle = thread::spawn(move || foldl(|a, b| a + b, 0, &list));
+
+    assert_eq!(handle.join().unwrap(), 3);
+}
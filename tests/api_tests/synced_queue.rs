@@ -0,0 +1,14 @@
+use std::thread;
+
+use persi_ds::sync::Queue;
+
+#[test]
+fn share_queue_between_threads() {
+    let queue: Queue<i32> = (0..100).collect();
+
+    let queue_clone = queue.clone();
+    let handle = thread::spawn(move || queue_clone.popped_front().iter().sum::<i32>());
+
+    assert_eq!(handle.join().unwrap(), 4950);
+    assert_eq!(queue.len(), 100);
+}
@@ -9,3 +9,11 @@ fn store_str_as_key() {
 
     assert_eq!(rbmap.get(&key), Some(&3));
 }
+
+#[test]
+fn ptr_eq_detects_unchanged_version() {
+    let m1: RBMap<i32, &str> = RBMap::new().inserted(1, "a");
+
+    assert!(m1.ptr_eq(&m1.inserted(1, "b")));
+    assert!(!m1.ptr_eq(&m1.inserted(2, "b")));
+}
@@ -0,0 +1,26 @@
+use std::thread;
+
+use persi_ds::sync::{OrderedSeq, PriorityQueue, Seq};
+
+#[test]
+fn split_seq_in_threads() {
+    let seq: Seq<usize> = (0..1000).collect();
+
+    let (front, back) = seq.split_at(400);
+    let handles = [front, back].map(|half| thread::spawn(move || half.iter().sum::<usize>()));
+    let sums: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+    assert_eq!(sums, [(0..400).sum::<usize>(), (400..1000).sum()]);
+    assert_eq!(seq.len(), 1000);
+}
+
+#[test]
+fn send_queue_and_ordered_seq() {
+    let queue: PriorityQueue<u32> = [3, 1, 2].into_iter().collect();
+    let seq: OrderedSeq<u32> = [3, 1, 2].into_iter().collect();
+
+    let handle =
+        thread::spawn(move || (queue.popped_max().peek_max().copied(), seq.first().copied()));
+
+    assert_eq!(handle.join().unwrap(), (Some(2), Some(1)));
+}
@@ -0,0 +1,27 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use persi_ds::sync::Stream;
+
+#[test]
+fn forced_tail_is_shared_between_threads() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    let naturals = Stream::iterate(0u64, move |x| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        x + 1
+    });
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let naturals = naturals.clone();
+            thread::spawn(move || naturals.iter().take(1000).sum::<u64>())
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 499_500);
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 999);
+}
@@ -0,0 +1,39 @@
+use std::thread;
+
+use persi_ds::sync::tree::Tree;
+use persi_ds::sync::List;
+
+#[test]
+fn share_tree_between_threads() {
+    let tree = Tree::new(
+        1,
+        List::new()
+            .pushed_front(Tree::leaf(3))
+            .pushed_front(Tree::leaf(2)),
+    );
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let tree = tree.clone();
+            thread::spawn(move || tree.updated_at(&[0], |x| x + i).preorder().sum::<i32>())
+        })
+        .collect();
+
+    for (i, handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.join().unwrap(), 6 + i as i32);
+    }
+    assert!(tree.preorder().copied().eq([1, 2, 3]));
+}
+
+#[test]
+fn send_converted_unsync_tree() {
+    let tree = persi_ds::unsync::tree::Tree::new(
+        1,
+        persi_ds::unsync::List::from_value(persi_ds::unsync::tree::Tree::leaf(2)),
+    );
+    let tree = tree.to_sync();
+
+    let handle = thread::spawn(move || tree.preorder().copied().collect::<Vec<_>>());
+
+    assert_eq!(handle.join().unwrap(), vec![1, 2]);
+}
@@ -0,0 +1,24 @@
+use std::thread;
+
+use persi_ds::sync::Vector;
+
+#[test]
+fn share_vector_between_threads() {
+    let vector: Vector<usize> = (0..1000).collect();
+
+    let handles: Vec<_> = (0..4)
+        .map(|t| {
+            let vector = vector.clone();
+            thread::spawn(move || {
+                let (_, right) = vector.split_at(t * 250);
+                right.updated(0, 0).iter().sum::<usize>()
+            })
+        })
+        .collect();
+
+    for (t, handle) in handles.into_iter().enumerate() {
+        let expected: usize = (t * 250 + 1..1000).sum();
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+    assert!(vector.iter().copied().eq(0..1000));
+}